@@ -14,20 +14,21 @@
 //!   webarcade app --locked          Build with plugins embedded in binary
 //!   webarcade package               Package the app (interactive)
 //!   webarcade package --locked      Package with embedded plugins
+//!   webarcade config validate       Validate webarcade.config.json
 
 use anyhow::{Context, Result};
 use clap::{Parser, Subcommand};
-use dialoguer::{Input, Select, Confirm, theme::ColorfulTheme};
-use console::{style, Term};
+use dialoguer::{Input, MultiSelect, Select, Confirm, theme::ColorfulTheme};
+use console::{style, Key, Term};
 use indicatif::{ProgressBar, ProgressStyle};
-use sha2::{Sha256, Digest};
+use sha2::{Sha256, Sha512, Digest};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs;
 use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
-use std::io::BufRead;
+use std::io::{BufRead, Read};
 use sysinfo::System;
 use walkdir::WalkDir;
 
@@ -60,6 +61,43 @@ struct PluginConfigEntry {
     #[serde(default)]
     #[serde(skip_serializing_if = "Vec::is_empty")]
     dependencies: Vec<String>,
+    /// Cargo features to enable when building this plugin
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    features: Vec<String>,
+    /// Where the plugin was installed from (e.g. github:user/repo), None for local plugins
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    source: Option<String>,
+    /// CPU features the backend was built with (`build --target-feature`)
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    target_features: Vec<String>,
+    /// A precompiled `.cwasm` cache sits next to the module (`build --aot`)
+    #[serde(default)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    has_aot_cache: bool,
+    /// wasm-bindgen module for the web, next to the desktop library (`build --dual-target`)
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    wasm_path: Option<String>,
+    /// Where `build --upload-artifact` published the last build
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    artifact_url: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    homepage: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    repository: Option<String>,
+    /// SPDX license expression
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    license: Option<String>,
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    keywords: Vec<String>,
 }
 
 fn default_has_frontend() -> bool { true }
@@ -73,37 +111,422 @@ struct WebArcadeConfig {
     #[serde(default)]
     #[serde(rename = "$schema")]
     schema: Option<String>,
+    /// Format version, see `CONFIG_SCHEMA_VERSION`
+    #[serde(default)]
+    #[serde(rename = "$schemaVersion")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema_version: Option<u64>,
     name: String,
     version: String,
     #[serde(default)]
     default_layout: Option<String>,
+    /// Author used for new plugins when --author isn't given
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_author: Option<String>,
+    /// Refuse to package unless the CLI is the latest release
+    #[serde(default)]
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    package_require_latest_cli: bool,
+    /// SPDX licenses plugin dependencies may use (`build --check-licenses`)
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    allowed_licenses: Vec<String>,
+    /// Minimum Rust version for plugins without a `rust-version` (`build --check-msrv`)
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    msrv: Option<String>,
     #[serde(default)]
     plugins: HashMap<String, PluginConfigEntry>,
+    /// Other settings (e.g. a registry URL), kept as-is on save
+    #[serde(flatten)]
+    extra: serde_json::Map<String, serde_json::Value>,
+    /// User-level defaults and the raw project file this config was merged from,
+    /// so `save` writes back only project-level values
+    #[serde(skip)]
+    layers: Option<(serde_json::Value, serde_json::Value)>,
+}
+
+/// User-level defaults merged under every project's webarcade.config.json
+fn user_config_path() -> Option<PathBuf> {
+    Some(user_config_dir()?.join("project_defaults.json"))
+}
+
+/// `~/.config/webarcade` (or `$XDG_CONFIG_HOME/webarcade`)
+fn user_config_dir() -> Option<PathBuf> {
+    let config_dir = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"))?).join(".config"),
+    };
+    Some(config_dir.join("webarcade"))
+}
+
+/// CLI settings that aren't project defaults, such as credentials
+fn cli_settings_path() -> Option<PathBuf> {
+    Some(user_config_dir()?.join("config.toml"))
+}
+
+/// GitHub token from WEBARCADE_GITHUB_TOKEN, or `[github] token` in the CLI settings
+fn github_token() -> Option<String> {
+    if let Some(token) = std::env::var("WEBARCADE_GITHUB_TOKEN").ok().filter(|t| !t.trim().is_empty()) {
+        return Some(token.trim().to_string());
+    }
+    let content = fs::read_to_string(cli_settings_path()?).ok()?;
+    let doc: toml::Value = content.parse().ok()?;
+    doc.get("github")?.get("token")?.as_str().map(String::from).filter(|t| !t.is_empty())
+}
+
+/// HTTPS clone URL for `user/repo`, authenticated when a GitHub token is configured
+fn github_clone_url(repo: &str) -> String {
+    match github_token() {
+        Some(token) => format!("https://{}@github.com/{}.git", token, repo),
+        None => format!("https://github.com/{}.git", repo),
+    }
+}
+
+/// First non-empty value of an environment variable, upper- or lowercase (curl accepts both).
+/// An empty variable counts as unset, so `HTTPS_PROXY=` doesn't hide `https_proxy`.
+fn proxy_env_var(name: &str) -> Option<String> {
+    [name.to_string(), name.to_lowercase()]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .map(|v| v.trim().to_string())
+        .find(|v| !v.is_empty())
+}
+
+/// Proxy for a request to `url` from HTTPS_PROXY (https URLs) or HTTP_PROXY, unless NO_PROXY
+/// lists the host. `http://` and `socks5://` proxy URLs are supported.
+fn proxy_from_env(url: &str) -> Option<ureq::Proxy> {
+    let (scheme, rest) = url.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or("");
+    let host = authority.rsplit('@').next().unwrap_or("");
+    let host = host.split(':').next().unwrap_or("").to_lowercase();
+
+    if let Some(no_proxy) = proxy_env_var("NO_PROXY") {
+        let bypass = no_proxy.split(',').map(|entry| entry.trim().to_lowercase()).any(|entry| {
+            let entry = entry.split(':').next().unwrap_or("").trim_start_matches('.').to_string();
+            entry == "*" || (!entry.is_empty() && (host == entry || host.ends_with(&format!(".{}", entry))))
+        });
+        if bypass {
+            return None;
+        }
+    }
+
+    let proxy_url = if scheme.eq_ignore_ascii_case("https") {
+        proxy_env_var("HTTPS_PROXY").or_else(|| proxy_env_var("HTTP_PROXY"))
+    } else {
+        proxy_env_var("HTTP_PROXY")
+    }?;
+    match ureq::Proxy::new(&proxy_url) {
+        Ok(proxy) => Some(proxy),
+        Err(e) => {
+            // The URL may carry proxy credentials, so only the error is shown
+            println!("  {} {}", style("⚠").yellow(), style(format!("Ignoring invalid proxy setting: {}", e)).yellow());
+            None
+        }
+    }
+}
+
+/// HTTP agent for a request to `url`, going through the proxy from the environment if any
+fn http_agent(url: &str) -> ureq::Agent {
+    let builder = ureq::AgentBuilder::new().user_agent("webarcade-cli");
+    match proxy_from_env(url) {
+        Some(proxy) => builder.proxy(proxy).build(),
+        None => builder.build(),
+    }
+}
+
+/// GET request against the GitHub REST API, authenticated when a GitHub token is configured
+fn github_api_get(url: &str) -> ureq::Request {
+    github_api_request("GET", url)
+}
+
+/// GitHub API request with any method, authenticated when a token is configured
+fn github_api_request(method: &str, url: &str) -> ureq::Request {
+    let request = http_agent(url).request(method, url)
+        .set("Accept", "application/vnd.github+json");
+    match github_token() {
+        Some(token) => request.set("Authorization", &format!("Bearer {}", token)),
+        None => request,
+    }
+}
+
+/// Replace the GitHub token in a message so it never reaches the terminal or logs
+fn scrub_token(message: &str) -> String {
+    match github_token() {
+        Some(token) => message.replace(&token, "***"),
+        None => message.to_string(),
+    }
+}
+
+/// Store `[github] <key>` in the CLI settings file, readable only by the current user
+fn set_github_setting(key: &str, value: &str) -> Result<()> {
+    let path = cli_settings_path().context("Could not determine the user config directory (HOME is not set)")?;
+    let mut doc: toml_edit::DocumentMut = match fs::read_to_string(&path) {
+        Ok(content) => content.parse().with_context(|| format!("Failed to parse {}", path.display()))?,
+        Err(_) => toml_edit::DocumentMut::new(),
+    };
+    doc["github"].or_insert(toml_edit::table())[key] = toml_edit::value(value);
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    write_atomic_private(&path, &doc.to_string())?;
+
+    let shown = if key == "token" { "***".to_string() } else { value.to_string() };
+    println!("  {} github.{} = {} in {}", style("✓").green(), key, shown, path.display());
+    Ok(())
+}
+
+/// Apply `overlay` on top of `base` following JSON Merge Patch (RFC 7396):
+/// objects merge recursively, `null` removes a key, anything else replaces.
+fn merge_json(base: serde_json::Value, overlay: serde_json::Value) -> serde_json::Value {
+    match overlay {
+        serde_json::Value::Object(overlay) => {
+            let mut merged = match base {
+                serde_json::Value::Object(base) => base,
+                _ => serde_json::Map::new(),
+            };
+            for (key, value) in overlay {
+                if value.is_null() {
+                    merged.shift_remove(&key);
+                } else {
+                    let slot = merged.entry(key).or_insert(serde_json::Value::Null);
+                    *slot = merge_json(slot.take(), value);
+                }
+            }
+            serde_json::Value::Object(merged)
+        }
+        overlay => overlay,
+    }
+}
+
+/// Remove values from `merged` that were inherited unchanged from `defaults`
+/// (i.e. absent from the original `project` value)
+fn strip_inherited(merged: &mut serde_json::Value, defaults: &serde_json::Value, project: &serde_json::Value) {
+    let (Some(merged), Some(defaults)) = (merged.as_object_mut(), defaults.as_object()) else {
+        return;
+    };
+    for (key, default_value) in defaults {
+        match project.get(key) {
+            None => {
+                if merged.get(key) == Some(default_value) {
+                    merged.shift_remove(key);
+                }
+            }
+            Some(project_value) => {
+                if let Some(merged_value) = merged.get_mut(key) {
+                    strip_inherited(merged_value, default_value, project_value);
+                }
+            }
+        }
+    }
+}
+
+/// Recursively sort object keys so saved files diff cleanly
+fn sort_json_keys(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<(String, serde_json::Value)> = std::mem::take(map).into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            for (key, mut value) in entries {
+                sort_json_keys(&mut value);
+                map.insert(key, value);
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(sort_json_keys),
+        _ => {}
+    }
+}
+
+/// Current `$schemaVersion` of webarcade.config.json; configs without one are version 1
+const CONFIG_SCHEMA_VERSION: u64 = 2;
+
+/// Steps that bring a config from `$schemaVersion` n to n + 1, at index n - 1
+const CONFIG_MIGRATIONS: &[fn(&mut serde_json::Value)] = &[migrate_config_v1_to_v2];
+
+/// What loading does with a config that has an older `$schemaVersion`
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum ConfigMigration {
+    /// `--no-migrate`: load it as it is
+    Skip,
+    /// Migrate it in memory only, for commands that don't change the project
+    InMemory,
+    /// Migrate it and write it back when that changed any keys
+    Write,
+}
+
+/// Set once in `main` from `--no-migrate` and the command; loads default to `Write`
+static CONFIG_MIGRATION: std::sync::OnceLock<ConfigMigration> = std::sync::OnceLock::new();
+
+/// Bring a project config up to `CONFIG_SCHEMA_VERSION`. Returns the version it had
+/// when it was older, None when it was already current (or newer).
+fn migrate_config(config: &mut serde_json::Value) -> Option<u64> {
+    let from = config.get("$schemaVersion").and_then(|v| v.as_u64()).unwrap_or(1).max(1);
+    if from >= CONFIG_SCHEMA_VERSION {
+        return None;
+    }
+    for migration in &CONFIG_MIGRATIONS[(from - 1) as usize..] {
+        migration(config);
+    }
+    if let Some(map) = config.as_object_mut() {
+        map.insert("$schemaVersion".to_string(), CONFIG_SCHEMA_VERSION.into());
+    }
+    Some(from)
+}
+
+/// Version 1 → 2: hand-written configs used snake_case keys, which deserialization silently
+/// ignored, and plugin entries could lack the fields a load fails without
+fn migrate_config_v1_to_v2(config: &mut serde_json::Value) {
+    fn rename_snake_case(map: &mut serde_json::Map<String, serde_json::Value>, keys: &[&str]) {
+        for key in keys {
+            let camel: String = key
+                .split('_')
+                .enumerate()
+                .map(|(i, part)| match (i, part.chars().next()) {
+                    (0, _) | (_, None) => part.to_string(),
+                    (_, Some(first)) => first.to_uppercase().chain(part.chars().skip(1)).collect(),
+                })
+                .collect();
+            if map.contains_key(&camel) {
+                continue;
+            }
+            if let Some(value) = map.shift_remove(*key) {
+                map.insert(camel, value);
+            }
+        }
+    }
+
+    let Some(map) = config.as_object_mut() else {
+        return;
+    };
+    rename_snake_case(map, &["default_layout", "default_author", "package_require_latest_cli", "allowed_licenses"]);
+    map.entry("name").or_insert_with(|| "WebArcade".into());
+    map.entry("version").or_insert_with(|| "0.1.0".into());
+
+    let Some(plugins) = map.get_mut("plugins").and_then(|p| p.as_object_mut()) else {
+        return;
+    };
+    for (id, entry) in plugins.iter_mut() {
+        let Some(entry) = entry.as_object_mut() else {
+            continue;
+        };
+        rename_snake_case(entry, &["has_backend", "has_frontend", "target_features", "has_aot_cache", "wasm_path", "artifact_url"]);
+        entry.entry("name").or_insert_with(|| id.clone().into());
+        entry.entry("version").or_insert_with(|| "1.0.0".into());
+        if !entry.contains_key("path") {
+            let has_backend = entry.get("hasBackend").and_then(|v| v.as_bool()).unwrap_or(false);
+            let path = if has_backend { format!("{}.dll", id) } else { format!("{}.js", id) };
+            entry.insert("path".to_string(), path.into());
+        }
+    }
+}
+
+/// Put config keys in a stable order: `$schema`, `name` and `version` first,
+/// then other settings and finally `plugins`, each sorted alphabetically
+fn sort_config_keys(value: &mut serde_json::Value) {
+    sort_json_keys(value);
+    if let serde_json::Value::Object(map) = value {
+        let mut ordered = serde_json::Map::new();
+        for key in ["$schema", "$schemaVersion", "name", "version"] {
+            if let Some(v) = map.shift_remove(key) {
+                ordered.insert(key.to_string(), v);
+            }
+        }
+        let plugins = map.shift_remove("plugins");
+        ordered.append(map);
+        if let Some(plugins) = plugins {
+            ordered.insert("plugins".to_string(), plugins);
+        }
+        *map = ordered;
+    }
 }
 
 impl WebArcadeConfig {
-    /// Load config from file, or create default if it doesn't exist
+    /// Load config from file, or create default if it doesn't exist.
+    /// User-level defaults are merged underneath; project values take precedence.
     fn load_or_create(config_path: &Path) -> Result<Self> {
-        if config_path.exists() {
+        let migration = CONFIG_MIGRATION.get().copied().unwrap_or(ConfigMigration::Write);
+        Self::load_layered(config_path, migration, user_config_path())
+    }
+
+    /// `load_or_create` with the migration mode and the user-level defaults file given
+    fn load_layered(config_path: &Path, migration: ConfigMigration, defaults_path: Option<PathBuf>) -> Result<Self> {
+        let project = if config_path.exists() {
             let content = fs::read_to_string(config_path)?;
-            let config: WebArcadeConfig = serde_json::from_str(&content)?;
-            Ok(config)
+            let mut project: serde_json::Value = serde_json::from_str(&content)?;
+            let original = project.clone();
+            let migrated_from = match migration {
+                ConfigMigration::Skip => None,
+                ConfigMigration::InMemory | ConfigMigration::Write => migrate_config(&mut project),
+            };
+            // The file is only rewritten when the migration changed more than the version number
+            let without_version = |value: &serde_json::Value| {
+                let mut value = value.clone();
+                if let Some(map) = value.as_object_mut() {
+                    map.shift_remove("$schemaVersion");
+                }
+                value
+            };
+            if let (Some(from), ConfigMigration::Write) = (migrated_from, migration) {
+                if without_version(&project) != without_version(&original) {
+                    sort_config_keys(&mut project);
+                    write_atomic(config_path, &serde_json::to_string_pretty(&project)?)?;
+                    println!(
+                        "  {} Migrated {} from schema version {} to {}",
+                        style("→").dim(),
+                        config_path.display(),
+                        from,
+                        CONFIG_SCHEMA_VERSION
+                    );
+                }
+            }
+            project
         } else {
-            Ok(Self {
-                schema: Some("./webarcade.config.schema.json".to_string()),
-                name: "WebArcade".to_string(),
-                version: "0.1.0".to_string(),
-                default_layout: Some("welcome".to_string()),
-                plugins: HashMap::new(),
+            serde_json::json!({
+                "$schema": "./webarcade.config.schema.json",
+                "$schemaVersion": CONFIG_SCHEMA_VERSION,
+                "name": "WebArcade",
+                "version": "0.1.0",
+                "defaultLayout": "welcome",
+                "plugins": {}
             })
+        };
+
+        let defaults = match defaults_path.filter(|p| p.exists()) {
+            Some(path) => {
+                let content = fs::read_to_string(&path)?;
+                Some(serde_json::from_str::<serde_json::Value>(&content)
+                    .with_context(|| format!("Failed to parse {}", path.display()))?)
+            }
+            None => None,
+        };
+
+        match defaults {
+            Some(defaults) => {
+                let merged = merge_json(defaults.clone(), project.clone());
+                let mut config: WebArcadeConfig = serde_json::from_value(merged)?;
+                config.layers = Some((defaults, project));
+                Ok(config)
+            }
+            None => Ok(serde_json::from_value(project)?),
         }
     }
 
-    /// Save config to file
+    /// Save config to file, leaving out values inherited from user-level defaults
     fn save(&self, config_path: &Path) -> Result<()> {
-        let content = serde_json::to_string_pretty(self)?;
-        fs::write(config_path, content)?;
-        Ok(())
+        let content = serde_json::to_string_pretty(&self.project_value()?)?;
+        write_atomic(config_path, &content)
+    }
+
+    /// The project-level values `save` writes, with keys in their canonical order
+    fn project_value(&self) -> Result<serde_json::Value> {
+        let mut value = serde_json::to_value(self)?;
+        if let Some((defaults, project)) = &self.layers {
+            strip_inherited(&mut value, defaults, project);
+        }
+        sort_config_keys(&mut value);
+        Ok(value)
     }
 
     /// Add or update a plugin entry
@@ -177,77 +600,74 @@ impl WebArcadeConfig {
         Ok(())
     }
 
-    /// Get build order respecting dependencies (topological sort).
-    /// Returns plugin IDs in the order they should be built.
-    fn get_build_order(&self, plugin_ids: &[String]) -> Result<Vec<String>> {
-        use std::collections::HashSet;
-
-        // First, read dependencies from package.json for plugins not yet in config
+    /// Dependencies of each plugin in `plugin_ids`, limited to plugins in that list.
+    /// Declared dependencies in the plugin's source win over the (possibly stale) config entry.
+    fn build_dependencies(&self, plugin_ids: &[String]) -> Result<HashMap<String, Vec<String>>> {
         let plugins_dir = get_plugins_dir()?;
         let mut all_deps: HashMap<String, Vec<String>> = HashMap::new();
 
         for plugin_id in plugin_ids {
-            // Try to get deps from config first, then from package.json
-            let deps = if let Some(entry) = self.plugins.get(plugin_id) {
-                entry.dependencies.clone()
-            } else {
-                // Read from package.json
-                let package_json_path = plugins_dir.join(plugin_id).join("package.json");
-                if package_json_path.exists() {
-                    let content = fs::read_to_string(&package_json_path)?;
-                    let pkg: serde_json::Value = serde_json::from_str(&content)?;
-                    pkg.get("pluginDependencies")
-                        .and_then(|v| v.as_array())
-                        .map(|arr| arr.iter()
-                            .filter_map(|v| v.as_str().map(String::from))
-                            .collect())
-                        .unwrap_or_default()
-                } else {
-                    Vec::new()
-                }
+            let deps = match read_plugin_dependencies(&plugins_dir.join(plugin_id))? {
+                Some(deps) => deps,
+                None => self.plugins.get(plugin_id).map(|e| e.dependencies.clone()).unwrap_or_default(),
             };
+            let mut deps: Vec<String> = deps.into_iter().filter(|dep| plugin_ids.contains(dep) && dep != plugin_id).collect();
+            // A dependency listed twice would otherwise count twice and never reach zero
+            deps.sort();
+            deps.dedup();
             all_deps.insert(plugin_id.clone(), deps);
         }
 
-        // Topological sort
-        let mut order = Vec::new();
-        let mut visited = HashSet::new();
-        let mut visiting = HashSet::new();
-
-        fn visit(
-            plugin_id: &str,
-            all_deps: &HashMap<String, Vec<String>>,
-            plugin_ids: &[String],
-            order: &mut Vec<String>,
-            visited: &mut HashSet<String>,
-            visiting: &mut HashSet<String>,
-        ) -> Result<()> {
-            if visited.contains(plugin_id) {
-                return Ok(());
-            }
-            if visiting.contains(plugin_id) {
-                anyhow::bail!("Circular dependency detected involving plugin '{}'", plugin_id);
-            }
+        Ok(all_deps)
+    }
 
-            visiting.insert(plugin_id.to_string());
+    /// Get build order respecting dependencies (Kahn's algorithm, ties keep the input order).
+    /// Returns plugin IDs in the order they should be built, or an error naming a cycle.
+    fn get_build_order(&self, plugin_ids: &[String]) -> Result<Vec<String>> {
+        let all_deps = self.build_dependencies(plugin_ids)?;
 
-            if let Some(deps) = all_deps.get(plugin_id) {
-                for dep in deps {
-                    // Only visit if it's in our build list
-                    if plugin_ids.contains(dep) {
-                        visit(dep, all_deps, plugin_ids, order, visited, visiting)?;
+        let mut remaining: HashMap<&str, usize> = plugin_ids
+            .iter()
+            .map(|id| (id.as_str(), all_deps.get(id).map(|d| d.len()).unwrap_or(0)))
+            .collect();
+        let mut order: Vec<String> = Vec::new();
+
+        loop {
+            let ready: Vec<&String> = plugin_ids
+                .iter()
+                .filter(|id| remaining.get(id.as_str()) == Some(&0))
+                .collect();
+            if ready.is_empty() {
+                break;
+            }
+            for plugin_id in ready {
+                remaining.remove(plugin_id.as_str());
+                for (dependent, deps) in &all_deps {
+                    if deps.contains(plugin_id) {
+                        if let Some(count) = remaining.get_mut(dependent.as_str()) {
+                            *count -= 1;
+                        }
                     }
                 }
+                order.push(plugin_id.clone());
             }
-
-            visiting.remove(plugin_id);
-            visited.insert(plugin_id.to_string());
-            order.push(plugin_id.to_string());
-            Ok(())
         }
 
-        for plugin_id in plugin_ids {
-            visit(plugin_id, &all_deps, plugin_ids, &mut order, &mut visited, &mut visiting)?;
+        if !remaining.is_empty() {
+            // Every plugin left has an unbuilt dependency that is also left, so following
+            // dependencies from any of them must eventually revisit a plugin
+            let mut path: Vec<&str> = Vec::new();
+            let mut current = plugin_ids.iter().find(|id| remaining.contains_key(id.as_str())).map(|s| s.as_str());
+            while let Some(id) = current {
+                if let Some(start) = path.iter().position(|p| *p == id) {
+                    let mut cycle = path[start..].to_vec();
+                    cycle.push(id);
+                    anyhow::bail!("Circular plugin dependency: {}", cycle.join(" → "));
+                }
+                path.push(id);
+                current = all_deps[id].iter().find(|dep| remaining.contains_key(dep.as_str())).map(|s| s.as_str());
+            }
+            anyhow::bail!("Circular plugin dependency among: {}", remaining.keys().copied().collect::<Vec<_>>().join(", "));
         }
 
         Ok(order)
@@ -269,12 +689,211 @@ impl WebArcadeConfig {
     }
 }
 
+/// A plugin's parsed plugin.toml, or None when it doesn't have one
+fn read_plugin_manifest(plugin_dir: &Path) -> Result<Option<toml::Value>> {
+    let plugin_toml_path = plugin_dir.join("plugin.toml");
+    if !plugin_toml_path.exists() {
+        return Ok(None);
+    }
+    let doc = fs::read_to_string(&plugin_toml_path)?
+        .parse()
+        .with_context(|| format!("Failed to parse {}", plugin_toml_path.display()))?;
+    Ok(Some(doc))
+}
+
+/// `services` from a plugin's plugin.toml: the docker-compose.yml services `dev --start-services`
+/// runs. None when there is no plugin.toml or it doesn't set `services`.
+fn read_plugin_services(plugin_dir: &Path) -> Result<Option<Vec<String>>> {
+    let Some(doc) = read_plugin_manifest(plugin_dir)? else {
+        return Ok(None);
+    };
+    Ok(doc.get("services").and_then(|v| v.as_array())
+        .map(|services| services.iter().filter_map(|v| v.as_str().map(String::from)).collect()))
+}
+
+/// Homepage, repository, license and keywords a plugin publishes about itself
+#[derive(Debug, Default)]
+struct PluginLinks {
+    homepage: Option<String>,
+    repository: Option<String>,
+    license: Option<String>,
+    keywords: Vec<String>,
+}
+
+impl PluginLinks {
+    /// Read from package.json, with plugin.toml filling in fields package.json doesn't set.
+    /// `repository` may be a URL string or npm's `{ "type": "git", "url": ... }` object.
+    fn read(plugin_dir: &Path) -> Result<Self> {
+        let mut links = PluginLinks::default();
+        let package_json_path = plugin_dir.join("package.json");
+        let package_json = if package_json_path.exists() {
+            let content = fs::read_to_string(&package_json_path)?;
+            Some(serde_json::from_str(&content).with_context(|| format!("Failed to parse {}", package_json_path.display()))?)
+        } else {
+            None
+        };
+        let plugin_toml = read_plugin_manifest(plugin_dir)?.map(serde_json::to_value).transpose()?;
+        for doc in [package_json, plugin_toml].into_iter().flatten() {
+            let text = |key: &str| doc.get(key).and_then(|v| v.as_str()).map(String::from);
+            links.homepage = links.homepage.or_else(|| text("homepage"));
+            links.repository = links.repository.or_else(|| {
+                text("repository").or_else(|| doc.get("repository")?.get("url")?.as_str().map(String::from))
+            });
+            links.license = links.license.or_else(|| text("license"));
+            if links.keywords.is_empty() {
+                links.keywords = doc.get("keywords").and_then(|v| v.as_array())
+                    .map(|keywords| keywords.iter().filter_map(|k| k.as_str().map(String::from)).collect())
+                    .unwrap_or_default();
+            }
+        }
+        Ok(links)
+    }
+
+    /// Print the fields that are set, aligned with `info`'s other rows
+    fn print(&self, indent: &str) {
+        if let Some(homepage) = &self.homepage {
+            println!("{}Homepage:    {}", indent, homepage);
+        }
+        if let Some(repository) = &self.repository {
+            println!("{}Repository:  {}", indent, repository);
+        }
+        if let Some(license) = &self.license {
+            println!("{}License:     {}", indent, license);
+        }
+        if !self.keywords.is_empty() {
+            println!("{}Keywords:    {}", indent, self.keywords.join(", "));
+        }
+    }
+}
+
+/// `workspaceMember` from a plugin's plugin.toml: whether its backend is already a member of
+/// the repo's Cargo workspace and is built in place
+fn read_plugin_workspace_member(plugin_dir: &Path) -> Result<bool> {
+    let Some(doc) = read_plugin_manifest(plugin_dir)? else {
+        return Ok(false);
+    };
+    Ok(doc.get("workspaceMember").and_then(|v| v.as_bool()).unwrap_or(false))
+}
+
+/// Plugin dependencies declared in source: `depends` in plugin.toml, or the
+/// `pluginDependencies` array in package.json. None if neither file declares any.
+fn read_plugin_dependencies(plugin_dir: &Path) -> Result<Option<Vec<String>>> {
+    if let Some(doc) = read_plugin_manifest(plugin_dir)? {
+        if let Some(depends) = doc.get("depends").and_then(|v| v.as_array()) {
+            return Ok(Some(depends.iter().filter_map(|v| v.as_str().map(String::from)).collect()));
+        }
+    }
+
+    let package_json_path = plugin_dir.join("package.json");
+    if package_json_path.exists() {
+        let pkg: serde_json::Value = serde_json::from_str(&fs::read_to_string(&package_json_path)?)?;
+        if let Some(deps) = pkg.get("pluginDependencies").and_then(|v| v.as_array()) {
+            return Ok(Some(deps.iter().filter_map(|v| v.as_str().map(String::from)).collect()));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Sidecar file used while a file is being written atomically
+fn atomic_tmp_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".tmp");
+    path.with_file_name(name)
+}
+
+/// Write a file atomically: write a `.tmp` sidecar, sync it to disk, then rename it
+/// over the destination so a crash never leaves a truncated file behind
+fn write_atomic(path: &Path, content: &str) -> Result<()> {
+    write_atomic_impl(path, content, false)
+}
+
+/// Like `write_atomic`, but the file is only readable by the current user (0600 on Unix)
+fn write_atomic_private(path: &Path, content: &str) -> Result<()> {
+    write_atomic_impl(path, content, true)
+}
+
+fn write_atomic_impl(path: &Path, content: &str, private: bool) -> Result<()> {
+    let tmp_path = atomic_tmp_path(path);
+
+    {
+        let mut open_options = fs::OpenOptions::new();
+        open_options.write(true).create(true).truncate(true);
+        #[cfg(unix)]
+        if private {
+            use std::os::unix::fs::OpenOptionsExt;
+            open_options.mode(0o600);
+        }
+        #[cfg(not(unix))]
+        let _ = private;
+        let mut file = open_options.open(&tmp_path)
+            .with_context(|| format!("Failed to create {}", tmp_path.display()))?;
+        file.write_all(content.as_bytes())?;
+        file.sync_all()?;
+    }
+
+    if let Err(e) = fs::rename(&tmp_path, path) {
+        // Windows refuses to rename over a file another process has open
+        if cfg!(target_os = "windows") {
+            fs::copy(&tmp_path, path)
+                .with_context(|| format!("Failed to replace {}", path.display()))?;
+            fs::remove_file(&tmp_path)?;
+        } else {
+            let _ = fs::remove_file(&tmp_path);
+            return Err(e).with_context(|| format!("Failed to replace {}", path.display()));
+        }
+    }
+
+    Ok(())
+}
+
 fn get_config_path() -> Result<PathBuf> {
     Ok(get_repo_root()?.join("webarcade.config.json"))
 }
 
-/// Update webarcade.config.json with plugin info after a successful build
-fn update_config_for_plugin(plugin_id: &str, has_backend: bool, has_frontend: bool, routes: Vec<serde_json::Value>) -> Result<()> {
+/// Cargo features configured for a plugin in webarcade.config.json
+fn configured_plugin_features(plugin_id: &str) -> Result<Vec<String>> {
+    let config = WebArcadeConfig::load_or_create(&get_config_path()?)?;
+    Ok(config.plugins.get(plugin_id)
+        .map(|entry| entry.features.clone())
+        .unwrap_or_default())
+}
+
+/// Features declared in a plugin's Cargo.toml `[features]` table (excluding `default`)
+fn available_plugin_features(plugin_dir: &Path) -> Vec<String> {
+    let cargo_toml_path = plugin_dir.join("Cargo.toml");
+    let mut features: Vec<String> = fs::read_to_string(&cargo_toml_path)
+        .ok()
+        .and_then(|content| content.parse::<toml::Value>().ok())
+        .and_then(|doc| doc.get("features").and_then(|f| f.as_table()).cloned())
+        .map(|table| table.keys().filter(|k| *k != "default").cloned().collect())
+        .unwrap_or_default();
+    features.sort();
+    features
+}
+
+/// Update webarcade.config.json with plugin info after a successful build.
+/// `output_path` is recorded as-is when the build was sent to a custom target directory.
+/// A `source` of None keeps whatever source the existing entry already records.
+/// `target_features` are the --target-feature flags the backend was compiled with, and
+/// `dual_target` whether a --dual-target wasm-bindgen module was built next to it.
+/// `artifact_url` is where --upload-artifact put the artifact, if it was uploaded.
+/// `install` calls this before building with `built` false, which writes the entry
+/// disabled, so the app never loads an artifact that doesn't exist yet.
+#[allow(clippy::too_many_arguments)]
+fn update_config_for_plugin(
+    plugin_id: &str,
+    has_backend: bool,
+    has_frontend: bool,
+    routes: Vec<serde_json::Value>,
+    output_path: Option<&Path>,
+    features: &[String],
+    target_features: &[String],
+    dual_target: bool,
+    artifact_url: Option<&str>,
+    source: Option<&str>,
+    built: bool,
+) -> Result<()> {
     let config_path = get_config_path()?;
     let plugins_dir = get_plugins_dir()?;
     let plugin_dir = plugins_dir.join(plugin_id);
@@ -284,14 +903,7 @@ fn update_config_for_plugin(plugin_id: &str, has_backend: bool, has_frontend: bo
     let (name, version, description, author, dependencies) = if package_json_path.exists() {
         let content = fs::read_to_string(&package_json_path)?;
         let pkg: serde_json::Value = serde_json::from_str(&content)?;
-
-        // Read plugin dependencies from "pluginDependencies" array
-        let deps: Vec<String> = pkg.get("pluginDependencies")
-            .and_then(|v| v.as_array())
-            .map(|arr| arr.iter()
-                .filter_map(|v| v.as_str().map(String::from))
-                .collect())
-            .unwrap_or_default();
+        let deps = read_plugin_dependencies(&plugin_dir)?.unwrap_or_default();
 
         (
             pkg.get("name").and_then(|v| v.as_str()).unwrap_or(plugin_id).to_string(),
@@ -301,15 +913,36 @@ fn update_config_for_plugin(plugin_id: &str, has_backend: bool, has_frontend: bo
             deps,
         )
     } else {
-        (plugin_id.to_string(), "1.0.0".to_string(), String::new(), String::new(), Vec::new())
+        let deps = read_plugin_dependencies(&plugin_dir)?.unwrap_or_default();
+        (plugin_id.to_string(), "1.0.0".to_string(), String::new(), String::new(), deps)
     };
 
     // Determine the path to the built plugin
-    let path = if has_backend {
+    let path = if let Some(output_path) = output_path {
+        output_path.to_string_lossy().to_string()
+    } else if has_backend {
         format!("{}.dll", plugin_id) // DLL path (backend handles serving from embedded)
     } else {
         format!("{}.js", plugin_id) // JS file in app/plugins/
     };
+    let has_aot_cache = match output_path {
+        Some(output_path) => output_path.with_extension("cwasm").exists(),
+        None => has_backend && get_dist_plugins_dir()?.join(format!("{}.cwasm", plugin_id)).exists(),
+    };
+    let wasm_name = format!("{}_bg.wasm", plugin_id);
+    let wasm_path = match output_path {
+        _ if !(dual_target && has_backend) => None,
+        Some(output_path) => Some(output_path.with_file_name(&wasm_name)).filter(|p| p.exists()).map(|p| p.to_string_lossy().to_string()),
+        None => Some(wasm_name).filter(|name| get_dist_plugins_dir().map(|d| d.join(name).exists()).unwrap_or(false)),
+    };
+
+    let links = PluginLinks::read(&plugin_dir)?;
+
+    let mut config = WebArcadeConfig::load_or_create(&config_path)?;
+
+    // Rebuilding an installed plugin shouldn't forget where it came from
+    let source = source.map(String::from)
+        .or_else(|| config.plugins.get(plugin_id).and_then(|e| e.source.clone()));
 
     let entry = PluginConfigEntry {
         name,
@@ -320,41 +953,566 @@ fn update_config_for_plugin(plugin_id: &str, has_backend: bool, has_frontend: bo
         has_backend,
         has_frontend,
         priority: default_priority(), // Will be recalculated after all plugins are built
-        enabled: true,
+        enabled: built,
         routes,
         dependencies,
+        features: features.to_vec(),
+        source,
+        target_features: target_features.to_vec(),
+        has_aot_cache,
+        wasm_path,
+        artifact_url: artifact_url.map(String::from),
+        homepage: links.homepage,
+        repository: links.repository,
+        license: links.license,
+        keywords: links.keywords,
     };
 
-    let mut config = WebArcadeConfig::load_or_create(&config_path)?;
     config.upsert_plugin(plugin_id, entry);
     config.save(&config_path)?;
 
     Ok(())
 }
 
-#[derive(Parser)]
-#[command(name = "webarcade")]
-#[command(about = "WebArcade CLI - Build plugins and package apps")]
-#[command(version)]
-struct Cli {
-    #[command(subcommand)]
-    command: Option<Commands>,
-}
+/// Validate webarcade.config.json against its JSON schema and check that
+/// the plugins it references line up with what is on disk
+fn validate_config() -> Result<()> {
+    let repo_root = get_repo_root()?;
+    let config_path = get_config_path()?;
 
-#[derive(Subcommand)]
-enum Commands {
-    /// Initialize a new WebArcade project
-    Init {
-        /// Project name (creates directory with this name)
-        project_name: String,
+    if !config_path.exists() {
+        anyhow::bail!("Config file not found: {}", config_path.display());
+    }
 
-        /// Git branch to clone (default: main)
-        #[arg(short, long, default_value = "main")]
-        branch: String,
-    },
-    /// Create a new plugin project
-    New {
-        /// Plugin ID (e.g., my-plugin)
+    println!();
+    println!("{}", style("Validating webarcade.config.json...").cyan().bold());
+    println!();
+
+    let mut errors: Vec<String> = Vec::new();
+
+    let content = fs::read_to_string(&config_path)?;
+    let instance: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+
+    // Schema validation
+    let schema_ref = instance.get("$schema")
+        .and_then(|v| v.as_str())
+        .unwrap_or("./webarcade.config.schema.json");
+    let schema_path = repo_root.join(schema_ref);
+
+    let schema_found = schema_path.exists();
+    if schema_found {
+        let schema_content = fs::read_to_string(&schema_path)
+            .with_context(|| format!("Failed to read {}", schema_path.display()))?;
+        let schema: serde_json::Value = serde_json::from_str(&schema_content)
+            .with_context(|| format!("Failed to parse {}", schema_path.display()))?;
+        let compiled = jsonschema::JSONSchema::compile(&schema)
+            .map_err(|e| anyhow::anyhow!("Invalid schema {}: {}", schema_path.display(), e))?;
+
+        if let Err(validation_errors) = compiled.validate(&instance) {
+            for error in validation_errors {
+                let pointer = error.instance_path.to_string();
+                let pointer = if pointer.is_empty() { "/".to_string() } else { pointer };
+                errors.push(format!("{}: {}", pointer, error));
+            }
+        }
+        println!("  {} Checked against {}", style("✓").green(), schema_ref);
+    } else {
+        // Older templates don't ship a schema; the structural checks below still run,
+        // but the config can't be reported as valid
+        println!("  {} {}", style("⚠").yellow(),
+            style(format!("No schema at {}, skipping schema validation", schema_ref)).yellow());
+    }
+
+    // Structural checks that JSON Schema can't express
+    let configured: HashMap<String, Option<String>> = instance.get("plugins")
+        .and_then(|v| v.as_object())
+        .map(|plugins| plugins.iter()
+            .map(|(id, entry)| {
+                // A disabled entry (e.g. installed but not built yet) isn't loaded
+                let enabled = entry.get("enabled").and_then(|e| e.as_bool()).unwrap_or(true);
+                let path = entry.get("path").and_then(|p| p.as_str()).filter(|_| enabled).map(String::from);
+                (id.clone(), path)
+            })
+            .collect())
+        .unwrap_or_default();
+
+    let dist_plugins_dir = get_dist_plugins_dir()?;
+    let mut plugin_ids: Vec<&String> = configured.keys().collect();
+    plugin_ids.sort();
+
+    for plugin_id in plugin_ids {
+        if let Some(path) = &configured[plugin_id] {
+            if !plugin_artifact_exists(&dist_plugins_dir, path) {
+                errors.push(format!(
+                    "/plugins/{}/path: '{}' does not exist in {}",
+                    plugin_id, path, dist_plugins_dir.display()
+                ));
+            }
+        }
+    }
+
+    let plugins_dir = get_plugins_dir()?;
+    if plugins_dir.exists() {
+        let mut sources: Vec<String> = Vec::new();
+        for entry in fs::read_dir(&plugins_dir)? {
+            let entry = entry?;
+            if entry.path().is_dir() {
+                sources.push(entry.file_name().to_string_lossy().to_string());
+            }
+        }
+        sources.sort();
+
+        for plugin_id in sources {
+            if !configured.contains_key(&plugin_id) {
+                errors.push(format!(
+                    "/plugins: no entry for plugin '{}' (run 'webarcade build {}')",
+                    plugin_id, plugin_id
+                ));
+            }
+        }
+    }
+    println!("  {} Checked plugin entries against disk", style("✓").green());
+
+    println!();
+
+    if !errors.is_empty() {
+        println!("  {}", style("Errors:").red().bold());
+        for error in &errors {
+            println!("    {} {}", style("✗").red(), error);
+        }
+        println!();
+        anyhow::bail!("Config validation failed with {} error(s)", errors.len());
+    }
+
+    if !schema_found {
+        anyhow::bail!(
+            "Config was not validated: {} not found (plugin entries match what's on disk)",
+            schema_path.display()
+        );
+    }
+
+    println!("  {} {}", style("✓").green().bold(), style("Config is valid!").green());
+    println!();
+
+    Ok(())
+}
+
+/// `config export`: the project config in another format. JSON stays the storage format.
+fn export_config(format: &str, output: Option<&Path>) -> Result<()> {
+    let config = WebArcadeConfig::load_or_create(&get_config_path()?)?;
+    let value = config.project_value()?;
+    let content = match format {
+        "toml" => toml::to_string_pretty(&value)
+            .context("The config can't be represented as TOML (TOML has no null values)")?,
+        "yaml" => serde_yaml::to_string(&value)?,
+        _ => serde_json::to_string_pretty(&value)? + "\n",
+    };
+
+    match output {
+        Some(path) => {
+            fs::write(path, &content).with_context(|| format!("Failed to write {}", path.display()))?;
+            println!("{} Exported config to {}", style("✓").green(), path.display());
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+/// `config import`: read a TOML, YAML or JSON config and save it as webarcade.config.json
+fn import_config(format: &str, file: &Path) -> Result<()> {
+    let content = fs::read_to_string(file).with_context(|| format!("Failed to read {}", file.display()))?;
+    let value: serde_json::Value = match format {
+        "toml" => toml::from_str(&content).with_context(|| format!("Failed to parse {} as TOML", file.display()))?,
+        "yaml" => serde_yaml::from_str(&content).with_context(|| format!("Failed to parse {} as YAML", file.display()))?,
+        _ => serde_json::from_str(&content).with_context(|| format!("Failed to parse {} as JSON", file.display()))?,
+    };
+    let config: WebArcadeConfig = serde_json::from_value(value)
+        .with_context(|| format!("{} is not a valid webarcade config", file.display()))?;
+
+    let config_path = get_config_path()?;
+    config.save(&config_path)?;
+    println!("{} Imported {} into {}", style("✓").green(), file.display(), config_path.display());
+    Ok(())
+}
+
+/// Set `key` in the project config, or in the user-level defaults with `global`.
+/// `github.*` keys (credentials) always go to the user's CLI settings, never a project file.
+fn set_config_value(key: &str, value: &str, global: bool) -> Result<()> {
+    if let Some(github_key) = key.strip_prefix("github.") {
+        return set_github_setting(github_key, value);
+    }
+
+    let path = if global {
+        user_config_path().context("Could not determine the user config directory (HOME is not set)")?
+    } else {
+        get_config_path()?
+    };
+
+    // Edit the file's own JSON so merged-in values never leak between layers
+    let mut doc: serde_json::Value = if path.exists() {
+        serde_json::from_str(&fs::read_to_string(&path)?)
+            .with_context(|| format!("Failed to parse {}", path.display()))?
+    } else if global {
+        serde_json::json!({})
+    } else {
+        anyhow::bail!("Config file not found: {}", path.display());
+    };
+
+    let parsed = serde_json::from_str(value).unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+
+    let segments: Vec<&str> = key.split('.').collect();
+    if segments.iter().any(|s| s.is_empty()) {
+        anyhow::bail!("Invalid key '{}'", key);
+    }
+    let mut target = &mut doc;
+    for segment in &segments[..segments.len() - 1] {
+        let object = target.as_object_mut()
+            .with_context(|| format!("Cannot set '{}': a parent of it is not an object", key))?;
+        target = object.entry(segment.to_string()).or_insert_with(|| serde_json::json!({}));
+    }
+    target.as_object_mut()
+        .with_context(|| format!("Cannot set '{}': a parent of it is not an object", key))?
+        .insert(segments[segments.len() - 1].to_string(), parsed.clone());
+
+    // The project file must still load on its own merged with the user defaults
+    if !global {
+        let defaults = match user_config_path().filter(|p| p.exists()) {
+            Some(p) => serde_json::from_str(&fs::read_to_string(&p)?)?,
+            None => serde_json::json!({}),
+        };
+        serde_json::from_value::<WebArcadeConfig>(merge_json(defaults, doc.clone()))
+            .with_context(|| format!("Setting '{}' would make {} invalid", key, path.display()))?;
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    sort_config_keys(&mut doc);
+    write_atomic(&path, &serde_json::to_string_pretty(&doc)?)?;
+
+    println!("  {} {} = {} in {}", style("✓").green(), key, parsed, path.display());
+
+    Ok(())
+}
+
+/// Compare webarcade.config.json against the version in HEAD, plugin by plugin
+fn diff_config() -> Result<()> {
+    let repo_root = get_repo_root()?;
+    let config_path = get_config_path()?;
+
+    let output = Command::new("git")
+        .current_dir(&repo_root)
+        .args(["show", "HEAD:./webarcade.config.json"])
+        .output()
+        .context("Failed to run git show. Is git installed?")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "Could not read webarcade.config.json from HEAD: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let old: WebArcadeConfig = serde_json::from_slice(&output.stdout)
+        .context("Failed to parse webarcade.config.json from HEAD")?;
+    // Compare the project file itself, without user-level defaults merged in
+    let new: WebArcadeConfig = serde_json::from_str(&fs::read_to_string(&config_path)?)
+        .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+
+    // Render a JSON value compactly, without quotes around plain strings
+    let show = |value: &serde_json::Value| match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Null => "(none)".to_string(),
+        other => other.to_string(),
+    };
+    let field_changes = |old: &serde_json::Value, new: &serde_json::Value| -> Vec<String> {
+        let empty = serde_json::Map::new();
+        let old_fields = old.as_object().unwrap_or(&empty);
+        let new_fields = new.as_object().unwrap_or(&empty);
+        let mut keys: Vec<&String> = old_fields.keys().chain(new_fields.keys()).collect();
+        keys.sort();
+        keys.dedup();
+        keys.into_iter()
+            .filter_map(|key| {
+                let before = old_fields.get(key).unwrap_or(&serde_json::Value::Null);
+                let after = new_fields.get(key).unwrap_or(&serde_json::Value::Null);
+                (before != after).then(|| format!("{}: {} → {}", key, show(before), show(after)))
+            })
+            .collect()
+    };
+
+    println!();
+    println!("  {}  {}", style("▶").cyan().bold(), style("webarcade.config.json changes since HEAD").cyan().bold());
+    println!("  {}", style("─".repeat(50)).dim());
+    println!();
+
+    let mut changed = false;
+
+    // Top-level fields (plugins are compared individually below)
+    let mut old_top = serde_json::to_value(&old)?;
+    let mut new_top = serde_json::to_value(&new)?;
+    for top in [&mut old_top, &mut new_top] {
+        if let Some(obj) = top.as_object_mut() {
+            obj.remove("plugins");
+        }
+    }
+    for change in field_changes(&old_top, &new_top) {
+        println!("  {} {}", style("~").yellow(), change);
+        changed = true;
+    }
+
+    let mut plugin_ids: Vec<&String> = old.plugins.keys().chain(new.plugins.keys()).collect();
+    plugin_ids.sort();
+    plugin_ids.dedup();
+
+    for plugin_id in plugin_ids {
+        match (old.plugins.get(plugin_id), new.plugins.get(plugin_id)) {
+            (None, Some(entry)) => {
+                println!("  {} {} (v{})", style("+").green().bold(), style(plugin_id).green(), entry.version);
+                changed = true;
+            }
+            (Some(entry), None) => {
+                println!("  {} {} (v{})", style("-").red().bold(), style(plugin_id).red(), entry.version);
+                changed = true;
+            }
+            (Some(before), Some(after)) => {
+                let changes = field_changes(&serde_json::to_value(before)?, &serde_json::to_value(after)?);
+                if !changes.is_empty() {
+                    println!("  {} {}", style("~").yellow().bold(), style(plugin_id).yellow());
+                    for change in changes {
+                        println!("      {}", change);
+                    }
+                    changed = true;
+                }
+            }
+            (None, None) => {}
+        }
+    }
+
+    if !changed {
+        println!("  {} No changes", style("✓").green());
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Remove `.tmp` sidecars left behind by a process that crashed mid-write
+fn repair_config() -> Result<()> {
+    let config_path = get_config_path()?;
+    let mut sidecars = vec![atomic_tmp_path(&config_path)];
+    sidecars.extend(BuildCache::all_cache_paths()?.iter().map(|(_, path)| atomic_tmp_path(path)));
+
+    println!();
+    let mut removed = 0;
+    for sidecar in &sidecars {
+        if sidecar.exists() {
+            fs::remove_file(sidecar)?;
+            println!("  {} Removed {}", style("✓").green(), sidecar.display());
+            removed += 1;
+        }
+    }
+    if removed == 0 {
+        println!("  {} No leftover .tmp files found", style("✓").green());
+    }
+
+    // The sidecars are harmless on their own; a broken config is what actually needs attention
+    if config_path.exists() {
+        let content = fs::read_to_string(&config_path)?;
+        if let Err(e) = serde_json::from_str::<WebArcadeConfig>(&content) {
+            println!("  {} {} is not valid: {}", style("✗").red(), config_path.display(), e);
+            println!("    Delete it and run {} to regenerate it", style("webarcade build --all -f").cyan());
+        }
+    }
+    println!();
+
+    Ok(())
+}
+
+/// Prune build cache entries: those of deleted plugins, one plugin's, or all of them.
+/// Cached artifacts in .webarcade/cache/ are pruned the same way, except when only
+/// one `--prefix` cache is reset, since the artifacts are shared by all of them.
+fn reset_build_cache(plugin: Option<&str>, all: bool, prefixed: bool) -> Result<()> {
+    let cache_path = BuildCache::cache_path()?;
+    println!();
+
+    let artifact_cache = artifact_cache_dir()?;
+    if prefixed {
+        // Artifacts are keyed by source hash, so other caches may still point at them
+    } else if all {
+        if artifact_cache.exists() {
+            fs::remove_dir_all(&artifact_cache)
+                .with_context(|| format!("Failed to delete {}", artifact_cache.display()))?;
+            println!("  {} Deleted {}", style("✓").green(), artifact_cache.display());
+        }
+        // Target dirs kept between `build --incremental` runs
+        let incremental = get_repo_root()?.join(".webarcade").join("incremental");
+        if incremental.exists() {
+            fs::remove_dir_all(&incremental)
+                .with_context(|| format!("Failed to delete {}", incremental.display()))?;
+            println!("  {} Deleted {}", style("✓").green(), incremental.display());
+        }
+    } else if let Ok(entries) = fs::read_dir(&artifact_cache) {
+        let plugins_dir = get_plugins_dir()?;
+        for entry in entries.filter_map(|e| e.ok()) {
+            let id = entry.file_name().to_string_lossy().to_string();
+            let stale = match plugin {
+                Some(plugin) => id == plugin,
+                None => !plugins_dir.join(&id).is_dir(),
+            };
+            if stale {
+                fs::remove_dir_all(entry.path())?;
+            }
+        }
+    }
+
+    if all {
+        // Without --prefix, --all deletes every cache, including the prefixed ones
+        let paths = if prefixed {
+            vec![cache_path].into_iter().filter(|p| p.exists()).collect()
+        } else {
+            BuildCache::all_cache_paths()?.into_iter().map(|(_, path)| path).collect::<Vec<_>>()
+        };
+        for path in &paths {
+            fs::remove_file(path)
+                .with_context(|| format!("Failed to delete {}", path.display()))?;
+            println!("  {} Deleted {}", style("✓").green(), path.display());
+        }
+        if paths.is_empty() {
+            println!("  {} No build cache to delete", style("✓").green());
+        }
+        println!();
+        return Ok(());
+    }
+
+    // Keys are the plugin ID, or `<id>@<target-dir>` for --target-dir builds
+    let plugin_of = |key: &str| key.split('@').next().unwrap_or(key).to_string();
+    let mut cache = BuildCache::load()?;
+    let before = cache.plugins.len();
+    match plugin {
+        Some(id) => cache.plugins.retain(|key, _| plugin_of(key) != id),
+        None => {
+            let plugins_dir = get_plugins_dir()?;
+            cache.plugins.retain(|key, _| plugins_dir.join(plugin_of(key)).is_dir());
+        }
+    }
+    let removed = before - cache.plugins.len();
+
+    if removed > 0 {
+        cache.save()?;
+    }
+    match (plugin, removed) {
+        (Some(id), 0) => println!("  {} No cache entry for {}", style("!").yellow(), style(id).cyan()),
+        (Some(id), n) => println!(
+            "  {} Removed {} cache entr{} for {}; its next build will recompile",
+            style("✓").green(), n, if n == 1 { "y" } else { "ies" }, style(id).cyan()
+        ),
+        (None, 0) => println!("  {} No stale cache entries ({} kept)", style("✓").green(), before),
+        (None, n) => println!(
+            "  {} Removed {} stale cache entr{} ({} kept)",
+            style("✓").green(), n, if n == 1 { "y" } else { "ies" }, cache.plugins.len()
+        ),
+    }
+    println!();
+
+    Ok(())
+}
+
+/// List the default build cache and each `build --cache-key-prefix` cache
+fn list_build_caches() -> Result<()> {
+    let caches = BuildCache::all_cache_paths()?;
+    println!();
+    if caches.is_empty() {
+        println!("  {} No build caches", style("✓").green());
+        println!();
+        return Ok(());
+    }
+    for (prefix, path) in &caches {
+        let entries = fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<BuildCache>(&content).ok())
+            .map(|cache| cache.plugins.len())
+            .unwrap_or(0);
+        let name = match prefix {
+            Some(prefix) => style(prefix.as_str()).cyan().to_string(),
+            None => style("(default)").dim().to_string(),
+        };
+        println!(
+            "  {} {} {} entr{}",
+            name,
+            style(path.display()).dim(),
+            entries,
+            if entries == 1 { "y" } else { "ies" }
+        );
+    }
+    println!();
+    println!("  Reset one with {}", style("webarcade config reset-cache --prefix <prefix> [--all]").cyan());
+    println!();
+    Ok(())
+}
+
+/// Check whether a plugin's configured `path` exists in the dist plugins directory.
+/// Backend plugins are recorded as `<id>.dll`, so the platform library name is accepted too.
+fn plugin_artifact_exists(dist_plugins_dir: &Path, path: &str) -> bool {
+    if dist_plugins_dir.join(path).exists() {
+        return true;
+    }
+
+    match path.strip_suffix(".dll") {
+        Some(stem) => {
+            dist_plugins_dir.join(format!("lib{}.so", stem)).exists()
+                || dist_plugins_dir.join(format!("lib{}.dylib", stem)).exists()
+        }
+        None => false,
+    }
+}
+
+#[derive(Parser)]
+#[command(name = "webarcade")]
+#[command(about = "WebArcade CLI - Build plugins and package apps")]
+#[command(version)]
+struct Cli {
+    /// Project root to use instead of searching upward from the current directory
+    #[arg(long, global = true, value_name = "PATH")]
+    project_dir: Option<PathBuf>,
+
+    /// Don't upgrade a webarcade.config.json with an older $schemaVersion; load it as it is
+    #[arg(long, global = true)]
+    no_migrate: bool,
+
+    #[command(subcommand)]
+    command: Option<Commands>,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    /// Initialize a new WebArcade project
+    Init {
+        /// Project name (creates directory with this name)
+        project_name: String,
+
+        /// App display name, e.g. "My Game Launcher" (default: the template's "WebArcade")
+        #[arg(long, value_name = "DISPLAY_NAME", conflicts_with = "overwrite")]
+        name: Option<String>,
+
+        /// Git branch to clone (default: main)
+        #[arg(short, long, default_value = "main")]
+        branch: String,
+
+        /// Also create a working full-stack example plugin (default ID: hello-world)
+        #[arg(long, value_name = "ID", num_args = 0..=1, require_equals = true, default_missing_value = "hello-world", conflicts_with = "overwrite")]
+        with_example_plugin: Option<String>,
+
+        /// Re-initialize an existing project from the latest template, keeping its plugins and config
+        #[arg(long)]
+        overwrite: bool,
+
+        /// With --overwrite, only replace app/ and leave everything else alone
+        #[arg(long, requires = "overwrite")]
+        plugins_only: bool,
+    },
+    /// Create a new plugin project
+    New {
+        /// Plugin ID (e.g., my-plugin)
         plugin_id: String,
 
         /// Plugin display name
@@ -368,6 +1526,39 @@ enum Commands {
         /// Create frontend-only plugin (no Rust backend)
         #[arg(long)]
         frontend_only: bool,
+
+        /// Document a required environment variable in .env.example (repeatable)
+        #[arg(long = "env", value_name = "KEY")]
+        env: Vec<String>,
+
+        /// Don't generate a README.md
+        #[arg(long)]
+        no_readme: bool,
+
+        /// Scaffold a dev-server.js sidecar listening on this port (started by `webarcade dev`)
+        #[arg(long)]
+        port: Option<u16>,
+
+        /// Register only left/bottom panels instead of a full viewport
+        #[arg(long, conflicts_with = "frontend_only")]
+        no_viewport: bool,
+
+        /// Generate a docker-compose.yml with service stubs (started by `webarcade dev --start-services`)
+        #[arg(long)]
+        with_docker_compose: bool,
+
+        /// Frontend framework for the generated components (default: solid)
+        #[arg(long, value_parser = ["solid", "react", "preact"])]
+        framework: Option<String>,
+
+        /// Minimum supported Rust version, written as `rust-version` in Cargo.toml (e.g. 1.75)
+        #[arg(long, value_name = "VERSION", value_parser = parse_rust_version)]
+        msrv: Option<String>,
+
+        /// SPDX license for Cargo.toml and plugin.toml; MIT, Apache-2.0, GPL-3.0, BSD-3-Clause
+        /// and MPL-2.0 also get a LICENSE file
+        #[arg(long, value_name = "SPDX")]
+        license: Option<String>,
     },
     /// Build a plugin from source
     Build {
@@ -385,66 +1576,355 @@ enum Commands {
         /// Cross-compile for a specific Rust target triple (e.g. x86_64-apple-darwin)
         #[arg(long)]
         target: Option<String>,
-    },
-    /// List available plugins in projects/
-    List,
-    /// Build frontend and run app in development mode
-    Dev,
-    /// Build frontend and run app in development mode (alias for dev)
-    Run,
-    /// Build production app with installer
-    App {
-        /// Build with plugins embedded in binary (locked mode)
+
+        /// Directory to place compiled plugins in (default: app/plugins)
         #[arg(long)]
-        locked: bool,
-    },
-    /// Package the app for distribution
-    Package {
-        /// Skip interactive prompts and use current config
+        target_dir: Option<PathBuf>,
+
+        /// Don't use sccache even if it is installed
         #[arg(long)]
-        skip_prompts: bool,
+        no_sccache: bool,
 
-        /// Use locked mode (embed plugins in binary)
+        /// Print compiled artifact sizes after building
         #[arg(long)]
-        locked: bool,
+        size_report: bool,
 
-        /// Skip plugin rebuild (use cached builds)
+        /// Faster rebuilds while iterating: incremental, 16 codegen units, no LTO, persistent target dir
+        #[arg(long, conflicts_with = "size_report")]
+        incremental: bool,
+
+        /// Override the release profile's opt-level (default: z)
+        #[arg(long, value_name = "LEVEL", value_parser = ["0", "1", "2", "3", "s", "z"])]
+        opt_level: Option<String>,
+
+        /// Smallest possible library: opt-level z, stripped symbols, panic = abort (a panicking
+        /// handler aborts the app instead of returning a 500) and an explicit export list;
+        /// reports the size change against the installed build
+        #[arg(long, conflicts_with_all = ["opt_level", "keep_symbols", "incremental"])]
+        min_size: bool,
+
+        /// Fail if any compiled artifact is larger than this many KB
         #[arg(long)]
-        no_rebuild: bool,
+        max_size_kb: Option<u64>,
 
-        /// Skip binary/frontend rebuild (use existing build)
+        /// Install a missing --target with rustup without prompting
         #[arg(long)]
-        skip_binary: bool,
+        auto_install_targets: bool,
 
-        /// App name (skips prompt)
+        /// Compile-time constant for frontend bundles, as KEY=VALUE (repeatable)
+        #[arg(long = "define", value_name = "KEY=VALUE", value_parser = parse_define)]
+        defines: Vec<(String, String)>,
+
+        /// Keep the esbuild metafile at build/<plugin-id>/bundle-meta.json
         #[arg(long)]
-        name: Option<String>,
+        bundle_analyze: bool,
 
-        /// App version (skips prompt)
+        /// Generate API.md in the plugin directory from router.rs and [routes]
         #[arg(long)]
-        version: Option<String>,
+        emit_docs: bool,
 
-        /// App description (skips prompt)
+        /// Treat [routes] handlers missing from router.rs as errors instead of warnings
         #[arg(long)]
-        description: Option<String>,
+        strict: bool,
 
-        /// App author (skips prompt)
+        /// Fail on uninitialized plugin submodules instead of running `git submodule update --init`
         #[arg(long)]
-        author: Option<String>,
-    },
-    /// Install a plugin from GitHub (e.g., username/repo)
-    Install {
-        /// GitHub repository in format username/repo
-        repo: String,
+        no_submodule_update: bool,
+
+        /// Check Rust dependency licenses against allowedLicenses (requires cargo-license)
+        #[arg(long)]
+        check_licenses: bool,
+
+        /// Also allow this SPDX license for this run (repeatable)
+        #[arg(long = "allow-license", value_name = "SPDX", requires = "check_licenses")]
+        allow_licenses: Vec<String>,
+
+        /// Run `cargo deny check` on each compiled backend (requires cargo-deny)
+        #[arg(long)]
+        deny: bool,
+
+        /// Keep debug symbols in release builds (drops `strip`, sets `debug = true`)
+        #[arg(long, conflicts_with = "strip_symbols")]
+        keep_symbols: bool,
+
+        /// Strip symbols from release builds even if the plugin's Cargo.toml doesn't
+        #[arg(long)]
+        strip_symbols: bool,
+
+        /// Link with mold or lld when installed (Linux and macOS)
+        #[arg(long)]
+        fast_linker: bool,
+
+        /// Run cargo with --timings=html and open the report (kept at build/<plugin-id>/timing.html)
+        #[arg(long, conflicts_with = "all")]
+        timings: bool,
+
+        /// Build the plugin in place as a member of the repo's Cargo workspace
+        #[arg(long)]
+        workspace_member: bool,
+
+        /// Build a self-contained library that doesn't resolve symbols from the host app
+        #[arg(long = "static")]
+        static_link: bool,
+
+        /// Parallel cargo jobs (default: WEBARCADE_BUILD_JOBS, then CARGO_BUILD_JOBS, then the CPU count)
+        #[arg(short, long, value_parser = clap::value_parser!(u32).range(1..))]
+        jobs: Option<u32>,
+
+        /// Shrink wasm32 plugin modules with wasm-opt (Binaryen)
+        #[arg(long)]
+        strip_wasm: bool,
+
+        /// Split the frontend bundle into chunks loaded on demand (installed to app/plugins/<id>/chunks/)
+        #[arg(long)]
+        code_split: bool,
+
+        /// CPU features to compile backends with, e.g. +avx2,+sse4.1 (rustc -C target-feature)
+        #[arg(long, value_name = "FEATURES", value_parser = parse_target_features)]
+        target_feature: Option<Vec<String>>,
+
+        /// With --all, rebuild failed plugins up to N more times without asking
+        #[arg(long, value_name = "N", requires = "all")]
+        retry: Option<u32>,
+
+        /// Precompile wasm32 plugin modules to a native .cwasm cache with wasmtime (Cranelift)
+        #[arg(long)]
+        aot: bool,
+
+        /// wasm-opt optimization level for --strip-wasm (default: z)
+        #[arg(long, value_name = "LEVEL", value_parser = ["0", "1", "2", "3", "4", "s", "z"], requires = "strip_wasm")]
+        wasm_opt_level: Option<String>,
+
+        /// Show the largest contributors to the compiled library with twiggy or bloaty
+        /// (saved to build/<plugin-id>/size-analysis.txt)
+        #[arg(long)]
+        analyze_binary: bool,
+
+        /// Tool for --analyze-binary (default: whichever is installed, twiggy first)
+        #[arg(long = "format", value_name = "TOOL", value_parser = ["twiggy", "bloaty", "both"], requires = "analyze_binary")]
+        analyze_format: Option<String>,
+
+        /// List dependencies the plugin's Cargo.toml declares but never uses (cargo-udeps, needs nightly)
+        #[arg(long)]
+        check_unused_deps: bool,
+
+        /// Remove unused dependencies from the plugin's Cargo.toml (implies --check-unused-deps)
+        #[arg(long)]
+        fix_unused_deps: bool,
+
+        /// Profile-guided optimization: build instrumented, run --pgo-workload, rebuild with the profile
+        #[arg(long, alias = "profile-guided", requires = "pgo_workload")]
+        pgo: bool,
+
+        /// Script or command that exercises the instrumented library for --pgo
+        /// (gets its path in WEBARCADE_PLUGIN_LIB)
+        #[arg(long, value_name = "SCRIPT", requires = "pgo")]
+        pgo_workload: Option<String>,
+
+        /// Also build each backend with its minimum Rust version (`rust-version`, or `msrv` in
+        /// webarcade.config.json) and report the APIs that need a newer one
+        #[arg(long)]
+        check_msrv: bool,
+
+        /// Run the backend's tests (`cargo test --lib`) after compiling; on failure the library
+        /// goes to build/.failed/ instead of app/plugins/
+        #[arg(long)]
+        test_after_build: bool,
+
+        /// Build each backend twice: the desktop library, plus a wasm32-unknown-unknown module
+        /// with wasm-bindgen exports and JS glue (<plugin-id>_bg.wasm + <plugin-id>.js)
+        #[arg(long, conflicts_with = "target")]
+        dual_target: bool,
+
+        /// Keep the build cache in build/.build_cache_<PREFIX>.json, so parallel CI jobs
+        /// (debug/release, feature sets) don't overwrite each other's entries
+        #[arg(long, value_name = "PREFIX", value_parser = parse_cache_key_prefix)]
+        cache_key_prefix: Option<String>,
+
+        /// Build bit-for-bit reproducible libraries: stable metadata, remapped paths and
+        /// SOURCE_DATE_EPOCH from git, checked by compiling each backend twice
+        #[arg(long, conflicts_with_all = ["incremental", "pgo"])]
+        reproducible: bool,
+
+        /// POST each compiled artifact to this URL as multipart form data (bearer token from
+        /// WEBARCADE_UPLOAD_TOKEN); the returned artifact_url goes into webarcade.config.json
+        #[arg(long, value_name = "URL")]
+        upload_artifact: Option<String>,
+
+        /// Report backend panics to Sentry: adds the sentry crate and initializes it with this DSN
+        /// in plugin_init (the DSN is compiled in with env!, not written to lib.rs)
+        #[arg(long, value_name = "DSN")]
+        sentry_dsn: Option<String>,
+
+        /// Sentry environment tag for --sentry-dsn
+        #[arg(long, value_name = "ENV", value_parser = ["prod", "staging", "dev"], requires = "sentry_dsn")]
+        sentry_environment: Option<String>,
+    },
+    /// List available plugins in projects/
+    List {
+        /// Show plugins installed from GitHub that have a newer version available
+        #[arg(long)]
+        outdated: bool,
+
+        /// With --outdated, exit with an error if any plugin is outdated (implies --outdated)
+        #[arg(long)]
+        check_only: bool,
+
+        /// Like --outdated, but compare against each repo's latest GitHub release through the API
+        /// (no clones; stops early when the rate limit runs low)
+        #[arg(long)]
+        check_updates: bool,
+
+        /// Show each plugin's frontend, backend files and routes as a tree
+        #[arg(long, conflicts_with_all = ["outdated", "check_only", "check_updates"])]
+        tree: bool,
+
+        /// Also show each plugin's version, description, homepage, repository, license and keywords
+        #[arg(short, long, conflicts_with_all = ["outdated", "check_only", "check_updates", "tree"])]
+        verbose: bool,
+    },
+    /// Build frontend and run app in development mode
+    Dev(DevArgs),
+    /// Build frontend and run app in development mode (alias for dev)
+    Run(DevArgs),
+    /// Build production app with installer
+    App {
+        /// Build with plugins embedded in binary (locked mode)
+        #[arg(long)]
+        locked: bool,
+
+        /// Don't kill running app processes first (e.g. one attached to a debugger)
+        #[arg(long)]
+        no_kill: bool,
+    },
+    /// Package the app for distribution
+    Package {
+        /// Skip interactive prompts and use current config
+        #[arg(long)]
+        skip_prompts: bool,
+
+        /// Use locked mode (embed plugins in binary)
+        #[arg(long)]
+        locked: bool,
+
+        /// Skip plugin rebuild (use cached builds)
+        #[arg(long)]
+        no_rebuild: bool,
+
+        /// Skip binary/frontend rebuild (use existing build)
+        #[arg(long)]
+        skip_binary: bool,
+
+        /// App name (skips prompt)
+        #[arg(long)]
+        name: Option<String>,
+
+        /// App version (skips prompt)
+        #[arg(long)]
+        version: Option<String>,
+
+        /// Take the version from the latest v* git tag (with -dev appended if the tree is dirty)
+        #[arg(long, conflicts_with = "version")]
+        auto_version: bool,
+
+        /// App description (skips prompt)
+        #[arg(long)]
+        description: Option<String>,
+
+        /// App author (skips prompt)
+        #[arg(long)]
+        author: Option<String>,
+
+        /// Markdown changelog to take installer release notes from (default: CHANGELOG.md)
+        #[arg(long)]
+        changelog: Option<PathBuf>,
+
+        /// Release notes for the installer, release-notes.md and --github-release (instead of the changelog)
+        #[arg(long, value_name = "TEXT", conflicts_with_all = ["changelog", "release_notes_file"])]
+        release_notes: Option<String>,
+
+        /// Read the release notes from this file (instead of the changelog)
+        #[arg(long, value_name = "PATH", conflicts_with = "changelog")]
+        release_notes_file: Option<PathBuf>,
+
+        /// Create the GitHub release v<version> for the origin remote and attach the installers
+        /// and checksums.json (token from WEBARCADE_GITHUB_TOKEN or `webarcade config set github.token`)
+        #[arg(long)]
+        github_release: bool,
+
+        /// Audit plugin dependencies first and refuse to package if vulnerabilities are found
+        #[arg(long)]
+        audit: bool,
+
+        /// Compile-time constant for frontend bundles, as KEY=VALUE (repeatable)
+        #[arg(long = "define", value_name = "KEY=VALUE", value_parser = parse_define)]
+        defines: Vec<(String, String)>,
+
+        /// Refuse to package if a newer CLI version is available
+        #[arg(long)]
+        update_check: bool,
+
+        /// Skip the CLI version check even if packageRequireLatestCli is set (for offline builds)
+        #[arg(long, conflicts_with = "update_check")]
+        skip_update_check: bool,
+
+        /// Don't kill running app processes first (e.g. one attached to a debugger)
+        #[arg(long)]
+        no_kill: bool,
+
+        /// Notarize and staple the macOS .dmg (credentials from a keychain profile or API key in WEBARCADE_APPLE_* env vars)
+        #[arg(long)]
+        notarize: bool,
+
+        /// Build a portable ZIP instead of an installer
+        #[arg(long, conflicts_with = "notarize")]
+        portable: bool,
+
+        /// Build one installer per platform (Windows NSIS, macOS DMG, Linux AppImage) by
+        /// cross-compiling from a macOS host
+        #[arg(long, conflicts_with = "portable")]
+        split_installers: bool,
+    },
+    /// Check packaged artifacts against the checksums.json written by `package`
+    VerifyPackage {
+        /// Checksum manifest; artifact names are resolved relative to its directory
+        #[arg(long)]
+        checksums: PathBuf,
+    },
+    /// Install a plugin from GitHub (e.g., username/repo)
+    Install {
+        /// GitHub repository in format username/repo, optionally with @ref
+        repo: String,
 
         /// Force reinstall even if already installed
         #[arg(short, long)]
         force: bool,
+
+        /// Branch, tag or commit SHA to install (overrides an @ref suffix)
+        #[arg(short, long)]
+        branch: Option<String>,
+
+        /// Install the source archive of this GitHub release instead of the latest one
+        #[arg(long, value_name = "TAG", conflicts_with = "branch")]
+        release_tag: Option<String>,
+
+        /// Don't build the plugin after installing it
+        #[arg(long)]
+        no_build: bool,
+
+        /// Don't verify the source against a checksum published with the GitHub release
+        #[arg(long)]
+        skip_checksum: bool,
     },
     /// Update webarcade CLI to the latest version
     Update,
     /// Uninstall webarcade CLI
     Uninstall,
+    /// Set the app version in app/Cargo.toml, app/package.json and webarcade.config.json
+    Version {
+        /// New semver version, e.g. 1.2.0
+        version: String,
+    },
     /// Sync project's app folder with latest core (updates Rust backend)
     Sync {
         /// Git branch to sync from (default: main)
@@ -455,15 +1935,198 @@ enum Commands {
         #[arg(long)]
         dry_run: bool,
     },
+    /// Show details about a plugin
+    Info {
+        /// Plugin ID
+        plugin_id: String,
+
+        /// Print the plugin's README.md
+        #[arg(long)]
+        readme: bool,
+    },
+    /// Remove a plugin's source, build output and config entry
+    Remove {
+        /// Plugin ID to remove
+        plugin_id: String,
+
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Manage a plugin's CHANGELOG.md
+    Changelog {
+        /// Plugin ID
+        plugin_id: String,
+
+        /// Add an entry under the plugin's current version
+        #[arg(long)]
+        add: String,
+    },
+    /// Check plugin dependencies for known vulnerabilities with cargo audit
+    Audit {
+        /// Exit with an error if any advisories are found
+        #[arg(long)]
+        deny_warnings: bool,
+    },
+    /// Inspect and maintain webarcade.config.json
+    Config {
+        /// Apply `set` to the user-level defaults (~/.config/webarcade/project_defaults.json)
+        #[arg(long)]
+        global: bool,
+
+        #[command(subcommand)]
+        command: ConfigCommands,
+    },
+    /// Generate project support files
+    Scaffold {
+        #[command(subcommand)]
+        command: ScaffoldCommands,
+    },
+    /// Work with a single plugin's source
+    Plugin {
+        #[command(subcommand)]
+        command: PluginCommands,
+    },
+}
+
+/// Options shared by `dev` and its `run` alias
+#[derive(clap::Args, Debug, Clone, Default)]
+struct DevArgs {
+    /// Load environment variables for the app from this file (default: .env at repo root)
+    #[arg(long)]
+    env_file: Option<PathBuf>,
+
+    /// Rebuild backend plugins on change and signal the running app to reload them
+    #[arg(long)]
+    hot_reload: bool,
+
+    /// Run `docker compose up -d` for plugins with a docker-compose.yml (and `down` on exit)
+    #[arg(long)]
+    start_services: bool,
+
+    /// Disable this plugin for this run only (repeatable; webarcade.config.json is left alone)
+    #[arg(long = "exclude", value_name = "PLUGIN_ID")]
+    exclude: Vec<String>,
+
+    /// Build all plugins without asking when app/plugins/ has no compiled plugins
+    #[arg(long)]
+    auto_build: bool,
+
+    /// Serve stub responses from this JSON file ("GET /hello": { "status", "body" }) instead of
+    /// building Rust backends; the app gets the URL in WEBARCADE_MOCK_API_URL
+    #[arg(long, value_name = "SPEC_FILE")]
+    mock_api: Option<PathBuf>,
+
+    /// Port for the --mock-api server
+    #[arg(long, value_name = "PORT", default_value_t = 3030, requires = "mock_api")]
+    mock_port: u16,
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Validate webarcade.config.json against its JSON schema
+    Validate,
+    /// Remove leftover .tmp files from interrupted config writes
+    Repair,
+    /// Show plugin changes in webarcade.config.json since the last commit
+    Diff,
+    /// Remove build cache entries for plugins that no longer exist
+    ResetCache {
+        /// Only reset this plugin's entry, forcing its next build to recompile
+        #[arg(long, value_name = "ID", conflicts_with = "all")]
+        plugin: Option<String>,
+
+        /// Delete the whole build cache
+        #[arg(long)]
+        all: bool,
+
+        /// Reset the cache of `build --cache-key-prefix <PREFIX>` instead of the default one
+        #[arg(long, value_name = "PREFIX", value_parser = parse_cache_key_prefix)]
+        prefix: Option<String>,
+
+        /// List the build caches and their entry counts
+        #[arg(long, conflicts_with_all = ["plugin", "all", "prefix"])]
+        list: bool,
+    },
+    /// Convert the config to TOML, YAML or JSON (printed, or written with --output)
+    Export {
+        /// Output format
+        #[arg(long, value_parser = ["toml", "yaml", "json"])]
+        format: String,
+
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Replace webarcade.config.json with a config converted from TOML, YAML or JSON
+    Import {
+        /// Input format
+        #[arg(long, value_parser = ["toml", "yaml", "json"])]
+        format: String,
+
+        /// File to import
+        file: PathBuf,
+    },
+    /// Set a value (dotted keys like plugins.my-plugin.enabled reach into objects)
+    Set {
+        /// Key to set, e.g. defaultAuthor
+        key: String,
+        /// Value, parsed as JSON when possible and stored as a string otherwise
+        value: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum ScaffoldCommands {
+    /// Generate CI/CD workflow files that build plugins and package tagged releases
+    Hooks {
+        /// CI provider to generate for (repeatable; prompts when omitted)
+        #[arg(long = "provider", value_parser = ["github", "gitlab", "bitbucket"])]
+        providers: Vec<String>,
+
+        /// Overwrite existing workflow files
+        #[arg(short, long)]
+        force: bool,
+    },
+    /// Generate a starter deny.toml at the repo root for `build --deny`
+    DenyConfig {
+        /// Overwrite an existing deny.toml
+        #[arg(short, long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+enum PluginCommands {
+    /// Run the plugin backend's Rust tests (`cargo test --lib`)
+    Test {
+        /// Plugin ID
+        plugin_id: String,
+
+        /// Only run tests whose name contains this string
+        #[arg(long = "test", value_name = "NAME")]
+        filter: Option<String>,
+    },
+    /// Print a release archive's SHA-256 in checksums.sha256 format, for publishing with a release
+    Hash {
+        /// The .tar.gz attached to the release
+        archive: PathBuf,
+    },
 }
 
 fn main() {
     let cli = Cli::parse();
+    let migration = match &cli.command {
+        _ if cli.no_migrate => ConfigMigration::Skip,
+        Some(command) if command.is_read_only() => ConfigMigration::InMemory,
+        _ => ConfigMigration::Write,
+    };
+    let _ = CONFIG_MIGRATION.set(migration);
 
-    let result = match cli.command {
+    let result = set_project_dir(cli.project_dir.as_deref()).and_then(|()| match cli.command {
         Some(cmd) => run_command(cmd),
         None => interactive_menu(),
-    };
+    });
 
     if let Err(e) = result {
         eprintln!("{} {}", style("Error:").red().bold(), e);
@@ -471,33 +2134,228 @@ fn main() {
     }
 }
 
+impl Commands {
+    /// Commands that only report on the project, so loading the config mustn't rewrite it
+    fn is_read_only(&self) -> bool {
+        matches!(
+            self,
+            Commands::List { .. } | Commands::Info { .. } | Commands::Config { command: ConfigCommands::Validate, .. }
+        )
+    }
+}
+
 fn run_command(cmd: Commands) -> Result<()> {
     match cmd {
-        Commands::Init { project_name, branch } => {
-            init_project(&project_name, &branch)
+        Commands::Init { project_name, name, branch, with_example_plugin, overwrite, plugins_only } => {
+            if overwrite {
+                reinit_project(&project_name, &branch, plugins_only)
+            } else {
+                init_project(&project_name, name.as_deref(), &branch, with_example_plugin.as_deref())
+            }
         }
-        Commands::New { plugin_id, name, author, frontend_only } => {
-            create_plugin(&plugin_id, name, author, frontend_only)
+        Commands::New { plugin_id, name, author, frontend_only, env, no_readme, port, no_viewport, with_docker_compose, framework, msrv, license } => {
+            create_plugin(&plugin_id, NewPluginOptions {
+                name,
+                author,
+                frontend_only,
+                env_keys: env,
+                no_readme,
+                port,
+                no_viewport,
+                with_docker_compose,
+                framework,
+                msrv,
+                license,
+            })
         }
-        Commands::Build { plugin_id, all, force, target } => {
+        Commands::Build {
+            plugin_id,
+            all,
+            force,
+            target,
+            target_dir,
+            no_sccache,
+            size_report,
+            incremental,
+            opt_level,
+            min_size,
+            max_size_kb,
+            auto_install_targets,
+            defines,
+            bundle_analyze,
+            emit_docs,
+            strict,
+            no_submodule_update,
+            check_licenses,
+            allow_licenses,
+            deny,
+            keep_symbols,
+            strip_symbols,
+            fast_linker,
+            timings,
+            workspace_member,
+            static_link,
+            jobs,
+            strip_wasm,
+            code_split,
+            target_feature,
+            retry,
+            aot,
+            wasm_opt_level,
+            analyze_binary,
+            analyze_format,
+            check_unused_deps,
+            fix_unused_deps,
+            pgo: _,
+            pgo_workload,
+            check_msrv,
+            test_after_build,
+            dual_target,
+            cache_key_prefix,
+            reproducible,
+            upload_artifact,
+            sentry_dsn,
+            sentry_environment,
+        } => {
+            set_cache_key_prefix(cache_key_prefix);
+            let target_dir = match target_dir {
+                Some(dir) if dir.is_relative() => Some(std::env::current_dir()?.join(dir)),
+                other => other,
+            };
+            let options = BuildOptions {
+                force,
+                target,
+                target_dir,
+                no_sccache,
+                size_report,
+                incremental,
+                opt_level,
+                min_size,
+                max_size_kb,
+                auto_install_targets,
+                defines,
+                bundle_analyze,
+                emit_docs,
+                strict,
+                no_submodule_update,
+                check_licenses,
+                allow_licenses,
+                deny,
+                symbols: if keep_symbols {
+                    Some(SymbolMode::Keep)
+                } else if strip_symbols {
+                    Some(SymbolMode::Strip)
+                } else {
+                    None
+                },
+                fast_linker,
+                timings,
+                workspace_member,
+                static_link,
+                jobs,
+                wasm_opt_level: strip_wasm.then(|| wasm_opt_level.unwrap_or_else(|| "z".to_string())),
+                code_split,
+                aot,
+                retry,
+                target_features: target_feature.unwrap_or_default(),
+                analyze_binary: analyze_binary.then(|| analyze_format.unwrap_or_else(|| "auto".to_string())),
+                check_unused_deps: check_unused_deps || fix_unused_deps,
+                fix_unused_deps,
+                check_msrv,
+                test_after_build,
+                dual_target,
+                reproducible,
+                upload_artifact,
+                sentry_dsn,
+                sentry_environment,
+                pgo_workload: pgo_workload.map(|workload| {
+                    // A script path given relative to where the command was run
+                    match std::env::current_dir().map(|dir| dir.join(&workload)) {
+                        Ok(path) if path.is_file() => path.to_string_lossy().to_string(),
+                        _ => workload,
+                    }
+                }),
+                ..Default::default()
+            };
             if all {
-                build_all_plugins(force, target.as_deref())
+                build_all_plugins(&options)
             } else if let Some(id) = plugin_id {
-                build_plugin(&id, force, target.as_deref())
+                build_plugin(&id, &options)
             } else {
                 anyhow::bail!("Please specify a plugin ID or use --all");
             }
         }
-        Commands::List => list_plugins(),
-        Commands::Dev | Commands::Run => dev_app(),
-        Commands::App { locked } => build_app(locked),
-        Commands::Package { skip_prompts, locked, no_rebuild, skip_binary, name, version, description, author } => {
-            package_app(skip_prompts, locked, no_rebuild, skip_binary, name, version, description, author)
+        Commands::List { outdated, check_only, check_updates, tree, verbose } => {
+            if outdated || check_only || check_updates {
+                list_outdated_plugins(check_only, check_updates)
+            } else if tree {
+                list_plugins_tree()
+            } else {
+                list_plugins(verbose)
+            }
+        }
+        Commands::Dev(args) | Commands::Run(args) => dev_app(&args),
+        Commands::App { locked, no_kill } => build_app(locked, no_kill),
+        Commands::Package { skip_prompts, locked, no_rebuild, skip_binary, name, version, auto_version, description, author, changelog, release_notes, release_notes_file, github_release, audit, defines, update_check, skip_update_check, no_kill, notarize, portable, split_installers } => {
+            package_app(PackageOptions {
+                skip_prompts,
+                locked,
+                no_rebuild,
+                skip_binary,
+                name,
+                version,
+                auto_version,
+                description,
+                author,
+                changelog,
+                release_notes,
+                release_notes_file,
+                github_release,
+                audit,
+                defines,
+                update_check,
+                skip_update_check,
+                no_kill,
+                notarize,
+                portable,
+                split_installers,
+            })
+        }
+        Commands::VerifyPackage { checksums } => verify_package(&checksums),
+        Commands::Install { repo, force, branch, release_tag, no_build, skip_checksum } => {
+            install_plugin(&repo, force, branch.as_deref(), release_tag.as_deref(), !no_build, !skip_checksum)
         }
-        Commands::Install { repo, force } => install_plugin(&repo, force),
         Commands::Update => update_cli(),
         Commands::Uninstall => uninstall_cli(),
+        Commands::Version { version } => set_project_version(&version),
         Commands::Sync { branch, dry_run } => sync_project(&branch, dry_run),
+        Commands::Info { plugin_id, readme } => show_plugin_info(&plugin_id, readme),
+        Commands::Remove { plugin_id, force } => remove_plugin(&plugin_id, force),
+        Commands::Changelog { plugin_id, add } => changelog_add(&plugin_id, &add),
+        Commands::Audit { deny_warnings } => audit_plugins(deny_warnings),
+        Commands::Config { global, command } => match command {
+            ConfigCommands::Set { key, value } => set_config_value(&key, &value, global),
+            _ if global => anyhow::bail!("--global is only supported with 'config set'"),
+            ConfigCommands::Validate => validate_config(),
+            ConfigCommands::Repair => repair_config(),
+            ConfigCommands::Diff => diff_config(),
+            ConfigCommands::ResetCache { list: true, .. } => list_build_caches(),
+            ConfigCommands::ResetCache { plugin, all, prefix, .. } => {
+                let prefixed = prefix.is_some();
+                set_cache_key_prefix(prefix);
+                reset_build_cache(plugin.as_deref(), all, prefixed)
+            }
+            ConfigCommands::Export { format, output } => export_config(&format, output.as_deref()),
+            ConfigCommands::Import { format, file } => import_config(&format, &file),
+        },
+        Commands::Scaffold { command } => match command {
+            ScaffoldCommands::Hooks { providers, force } => scaffold_hooks(&providers, force),
+            ScaffoldCommands::DenyConfig { force } => scaffold_deny_config(force),
+        },
+        Commands::Plugin { command } => match command {
+            PluginCommands::Test { plugin_id, filter } => test_plugin(&plugin_id, filter.as_deref()),
+            PluginCommands::Hash { archive } => print_archive_hash(&archive),
+        },
     }
 }
 
@@ -507,10 +2365,7 @@ fn check_latest_version() -> Option<String> {
     // Query crates.io API for latest version
     let url = "https://crates.io/api/v1/crates/webarcade";
 
-    match ureq::get(url)
-        .set("User-Agent", "webarcade-cli")
-        .call()
-    {
+    match http_agent(url).get(url).call() {
         Ok(response) => {
             let body = response.into_string().ok()?;
             let json: serde_json::Value = serde_json::from_str(&body).ok()?;
@@ -544,6 +2399,90 @@ fn compare_cli_versions(current: &str, latest: &str) -> std::cmp::Ordering {
     std::cmp::Ordering::Equal
 }
 
+/// Run `cargo install` with a progress bar that advances as crates are compiled
+fn cargo_install_with_progress(args: &[&str]) -> Result<()> {
+    let mut child = Command::new("cargo")
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("Failed to run cargo install")?;
+
+    let progress = ProgressBar::new(0);
+    progress.set_style(
+        ProgressStyle::default_bar()
+            .template("  {spinner:.cyan} [{bar:30.cyan/dim}] {percent:>3}% {msg}")
+            .unwrap()
+            .progress_chars("=> ")
+    );
+    progress.enable_steady_tick(std::time::Duration::from_millis(100));
+    progress.set_message("Resolving dependencies...");
+
+    let stderr = child.stderr.take().expect("Failed to capture stderr");
+    let reader = std::io::BufReader::new(stderr);
+
+    let mut total_crates = 0u64;
+    let mut compiled_count = 0u64;
+    let mut error_output = String::new();
+
+    // Count the first number in lines like "Locking 152 packages" / "Downloaded 148 crates (12 MB)"
+    let leading_count = |line: &str| -> Option<u64> {
+        line.split_whitespace().nth(1).and_then(|n| n.parse().ok())
+    };
+
+    for line in reader.lines() {
+        let line = match line {
+            Ok(l) => l,
+            Err(_) => continue,
+        };
+        let trimmed = line.trim_start();
+
+        if trimmed.starts_with("Locking ") || (trimmed.starts_with("Downloaded ") && trimmed.contains(" crates")) {
+            if let Some(count) = leading_count(trimmed) {
+                total_crates = total_crates.max(count);
+                progress.set_length(total_crates);
+            }
+        } else if let Some(rest) = trimmed.strip_prefix("Downloaded ") {
+            let crate_name = rest.split_whitespace().next().unwrap_or("");
+            progress.set_message(format!("Downloading {}", crate_name));
+        } else if let Some(rest) = trimmed.strip_prefix("Compiling ") {
+            compiled_count += 1;
+            // Estimate if cargo didn't report a package count, and grow if we exceed it
+            if total_crates == 0 {
+                total_crates = 150;
+            }
+            if compiled_count > total_crates {
+                total_crates = compiled_count + 10;
+            }
+            progress.set_length(total_crates);
+            progress.set_position(compiled_count);
+
+            let crate_name = rest.split_whitespace().next().unwrap_or("");
+            progress.set_message(format!("Compiling {}", crate_name));
+        } else if trimmed.starts_with("Installing ") || trimmed.starts_with("Replacing ") {
+            progress.set_message("Installing binary...");
+        } else if line.contains("error") || line.contains("Error") {
+            error_output.push_str(&line);
+            error_output.push('\n');
+        }
+    }
+
+    let status = child.wait().context("Failed to wait for cargo install")?;
+    if status.success() {
+        progress.set_position(progress.length().unwrap_or(compiled_count));
+    }
+    progress.finish_and_clear();
+
+    if !status.success() {
+        if error_output.is_empty() {
+            error_output = "cargo install failed (unknown error)".to_string();
+        }
+        anyhow::bail!("Failed to update webarcade CLI:\n{}", error_output);
+    }
+
+    Ok(())
+}
+
 fn update_cli() -> Result<()> {
     println!();
     println!("  {}  {}", style("▶").cyan().bold(), style("WebArcade CLI Update").cyan().bold());
@@ -582,20 +2521,13 @@ fn update_cli() -> Result<()> {
                         println!("  {} Installing update...", style("→").cyan());
                         println!();
 
-                        let status = Command::new("cargo")
-                            .args(["install", "webarcade", "--force"])
-                            .status()
-                            .context("Failed to run cargo install")?;
+                        cargo_install_with_progress(&["install", "webarcade", "--force"])?;
 
-                        if status.success() {
-                            println!();
-                            println!("  {} Successfully updated to v{}!",
-                                style("✓").green().bold(),
-                                style(&latest).green().bold()
-                            );
-                        } else {
-                            anyhow::bail!("Failed to update webarcade CLI");
-                        }
+                        println!();
+                        println!("  {} Successfully updated to v{}!",
+                            style("✓").green().bold(),
+                            style(&latest).green().bold()
+                        );
                     } else {
                         println!("  Update cancelled.");
                     }
@@ -784,53 +2716,334 @@ impl PluginInfo {
 /// - Some(Ordering::Less) if v1 < v2 (v1 is older)
 /// - Some(Ordering::Equal) if they're the same
 /// - None if versions couldn't be parsed
+///
+/// Pre-release versions sort before their release (`1.0.0-alpha < 1.0.0`) and build
+/// metadata is ignored. A leading `v` and missing minor/patch numbers are accepted.
 fn compare_versions(v1: &str, v2: &str) -> Option<std::cmp::Ordering> {
-    let parse = |v: &str| -> Option<(u32, u32, u32)> {
-        let parts: Vec<&str> = v.trim_start_matches('v').split('.').collect();
-        if parts.len() >= 3 {
-            Some((
-                parts[0].parse().ok()?,
-                parts[1].parse().ok()?,
-                parts[2].split('-').next()?.parse().ok()?,
-            ))
-        } else if parts.len() == 2 {
-            Some((
-                parts[0].parse().ok()?,
-                parts[1].parse().ok()?,
-                0,
-            ))
-        } else if parts.len() == 1 {
-            Some((parts[0].parse().ok()?, 0, 0))
-        } else {
-            None
-        }
+    let parse = |v: &str| -> Option<semver::Version> {
+        let v = v.trim().trim_start_matches('v');
+        // Pad "1" and "1.2" out to a full major.minor.patch
+        let core_len = v.find(['-', '+']).unwrap_or(v.len());
+        let padding = match v[..core_len].matches('.').count() {
+            0 => ".0.0",
+            1 => ".0",
+            _ => "",
+        };
+        semver::Version::parse(&format!("{}{}{}", &v[..core_len], padding, &v[core_len..])).ok()
     };
 
-    let v1_parts = parse(v1)?;
-    let v2_parts = parse(v2)?;
+    let v1 = parse(v1)?;
+    let v2 = parse(v2)?;
 
-    Some(v1_parts.cmp(&v2_parts))
+    Some(v1.cmp_precedence(&v2))
 }
 
-fn install_plugin(repo: &str, force: bool) -> Result<()> {
-    let theme = ColorfulTheme::default();
+/// Whether a git ref looks like a (possibly abbreviated) commit SHA
+fn is_commit_sha(git_ref: &str) -> bool {
+    (7..=40).contains(&git_ref.len()) && git_ref.chars().all(|c| c.is_ascii_hexdigit())
+}
 
-    // Parse the repo format (username/repo)
-    let parts: Vec<&str> = repo.split('/').collect();
-    if parts.len() != 2 {
+/// Run a git command in `dir`, failing with its stderr
+fn run_git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .current_dir(dir)
+        .args(args)
+        .output()
+        .context("Failed to run git. Is git installed?")?;
+    if !output.status.success() {
+        anyhow::bail!("git {} failed: {}", args.join(" "), String::from_utf8_lossy(&output.stderr).trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Submodule paths declared in the repo's .gitmodules, relative to the repo root
+fn submodule_paths(repo_root: &Path) -> Vec<String> {
+    let Ok(content) = fs::read_to_string(repo_root.join(".gitmodules")) else {
+        return Vec::new();
+    };
+    let path_re = regex::Regex::new(r"(?m)^\s*path\s*=\s*(.+?)\s*$").unwrap();
+    path_re
+        .captures_iter(&content)
+        .map(|c| c[1].trim_end_matches('/').to_string())
+        .collect()
+}
+
+/// Path of a plugin directory relative to the repo root, if it is a git submodule
+fn plugin_submodule_path(repo_root: &Path, plugin_dir: &Path) -> Option<String> {
+    let relative = plugin_dir.strip_prefix(repo_root).ok()?.to_string_lossy().replace('\\', "/");
+    submodule_paths(repo_root).into_iter().find(|p| *p == relative)
+}
+
+/// Run `git submodule update --init` for a plugin whose submodule was never checked out
+/// (its directory is missing or empty). Without `update`, fail with instructions instead.
+fn ensure_plugin_submodule(plugin_id: &str, update: bool) -> Result<()> {
+    let repo_root = get_repo_root()?;
+    let plugin_dir = get_plugins_dir()?.join(plugin_id);
+    let Some(submodule) = plugin_submodule_path(&repo_root, &plugin_dir) else {
+        return Ok(());
+    };
+
+    let initialized = fs::read_dir(&plugin_dir)
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false);
+    if initialized {
+        return Ok(());
+    }
+
+    if !update {
         anyhow::bail!(
-            "Invalid repository format. Expected 'username/repo', got '{}'",
-            repo
+            "Plugin '{}' is a git submodule that hasn't been initialized. Run: git submodule update --init {}",
+            plugin_id,
+            submodule
         );
     }
 
-    let username = parts[0];
-    let repo_name = parts[1];
+    println!("{} Initializing submodule {}...", style("→").dim(), submodule);
+    run_git(&repo_root, &["submodule", "update", "--init", &submodule])
+        .with_context(|| format!("Failed to initialize submodule for plugin '{}'", plugin_id))?;
+    Ok(())
+}
 
-    println!();
+/// Clone `url` into `dest` at `git_ref` (branch, tag or commit SHA; default branch when None)
+fn clone_repo_at(url: &str, dest: &Path, git_ref: Option<&str>) -> Result<()> {
+    let dest_str = dest.to_string_lossy();
+
+    match git_ref {
+        Some(git_ref) => {
+            // A hex-looking name like "deadbeef" or "1234567" can be a branch or tag, so
+            // try it as one first and only then treat it as a commit SHA
+            let cwd = std::env::current_dir()?;
+            let cloned = run_git(&cwd, &["clone", "--depth", "1", "--branch", git_ref, url, &dest_str]);
+            match cloned {
+                Ok(_) => {}
+                Err(_) if is_commit_sha(git_ref) => {
+                    if dest.exists() {
+                        fs::remove_dir_all(dest)?;
+                    }
+                    checkout_commit(url, dest, git_ref)?;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        None => {
+            let cwd = std::env::current_dir()?;
+            run_git(&cwd, &["clone", "--depth", "1", url, &dest_str])?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Shallow clones can't check out an arbitrary commit, so fetch it by SHA
+fn checkout_commit(url: &str, dest: &Path, sha: &str) -> Result<()> {
+    fs::create_dir_all(dest)?;
+    run_git(dest, &["init", "--quiet"])?;
+    run_git(dest, &["remote", "add", "origin", url])?;
+    if run_git(dest, &["fetch", "--depth", "1", "origin", sha]).is_err() {
+        // Servers only serve full SHAs directly; fall back to the whole history
+        run_git(dest, &["fetch", "origin"])?;
+    }
+    run_git(dest, &["checkout", "--quiet", sha])
+        .or_else(|_| run_git(dest, &["checkout", "--quiet", "FETCH_HEAD"]))?;
+    Ok(())
+}
+
+/// The SHA-256 a GitHub release publishes for its asset `file_name`, as (checksum asset name, hash).
+/// The hash comes from a `<plugin-id>-<version>.sha256` or `checksums.sha256` asset of the
+/// release tagged `tag`. Returns None when there is no such asset.
+fn fetch_release_checksum(repo: &str, tag: &str, plugin_id: &str, version: &str, file_name: &str) -> Result<Option<(String, String)>> {
+    let body = github_api_get(&format!("https://api.github.com/repos/{}/releases/tags/{}", repo, tag))
+        .call()?
+        .into_string()?;
+    let release: serde_json::Value = serde_json::from_str(&body).context("Failed to parse the GitHub release")?;
+
+    let versioned_name = format!("{}-{}.sha256", plugin_id, version);
+    let assets = release.get("assets").and_then(|a| a.as_array()).cloned().unwrap_or_default();
+    let Some(asset) = [versioned_name.as_str(), "checksums.sha256"].iter().find_map(|name| {
+        assets.iter().find(|a| a.get("name").and_then(|n| n.as_str()) == Some(*name))
+    }) else {
+        return Ok(None);
+    };
+    let asset_name = asset.get("name").and_then(|n| n.as_str()).unwrap_or_default().to_string();
+    let url = asset.get("url").and_then(|u| u.as_str()).context("Release asset has no download URL")?;
+
+    // The API asset URL serves the file itself with this Accept header, for private repos too
+    let content = github_api_get(url)
+        .set("Accept", "application/octet-stream")
+        .call()?
+        .into_string()?;
+
+    let expected = parse_sha256_listing(&content, file_name)
+        .with_context(|| format!("No SHA-256 for {} in {}", file_name, asset_name))?;
+    Ok(Some((asset_name, expected)))
+}
+
+/// Source archive of a GitHub release
+struct ReleaseArchive {
+    tag: String,
+    /// Asset file name, or `<tag>.tar.gz` for GitHub's generated archive
+    name: String,
+    url: String,
+}
+
+/// The source archive of `repo`'s latest release, or of the release tagged `tag`. A `.tar.gz`
+/// asset wins over the archive GitHub generates for every release. None if there is no such release.
+fn find_release_archive(repo: &str, tag: Option<&str>) -> Result<Option<ReleaseArchive>> {
+    let url = match tag {
+        Some(tag) => format!("https://api.github.com/repos/{}/releases/tags/{}", repo, tag),
+        None => format!("https://api.github.com/repos/{}/releases/latest", repo),
+    };
+    let body = match github_api_get(&url).call() {
+        Ok(response) => response.into_string()?,
+        Err(ureq::Error::Status(404, _)) => return Ok(None),
+        Err(e) => return Err(e.into()),
+    };
+    let release: serde_json::Value = serde_json::from_str(&body).context("Failed to parse the GitHub release")?;
+    let tag = release["tag_name"].as_str().context("GitHub release has no tag")?.to_string();
+
+    let asset = release["assets"].as_array().into_iter().flatten().find(|asset| {
+        asset["name"].as_str().is_some_and(|name| name.ends_with(".tar.gz") || name.ends_with(".tgz"))
+    });
+    let (name, url) = match asset {
+        Some(asset) => (asset["name"].as_str(), asset["url"].as_str()),
+        None => (None, release["tarball_url"].as_str()),
+    };
+    Ok(Some(ReleaseArchive {
+        name: name.map(String::from).unwrap_or_else(|| format!("{}.tar.gz", tag)),
+        url: url.context("GitHub release has no source archive")?.to_string(),
+        tag,
+    }))
+}
+
+/// Download a release archive to `dest` with a progress bar
+fn download_release_archive(archive: &ReleaseArchive, dest: &Path) -> Result<()> {
+    // The API asset URL serves the file itself with this Accept header, for private repos too
+    let response = github_api_get(&archive.url)
+        .set("Accept", "application/octet-stream")
+        .call()?;
+    let bar = match response.header("Content-Length").and_then(|len| len.parse().ok()) {
+        Some(len) => {
+            let bar = ProgressBar::new(len);
+            bar.set_style(
+                ProgressStyle::default_bar()
+                    .template("    [{bar:30.cyan/dim}] {bytes}/{total_bytes}")
+                    .unwrap()
+                    .progress_chars("=> ")
+            );
+            bar
+        }
+        // Generated archives are streamed without a length
+        None => {
+            let bar = ProgressBar::new_spinner();
+            bar.set_style(ProgressStyle::default_spinner().template("    {spinner:.cyan} {bytes}").unwrap());
+            bar
+        }
+    };
+    let mut reader = bar.wrap_read(response.into_reader());
+    let mut file = fs::File::create(dest)?;
+    let copied = std::io::copy(&mut reader, &mut file);
+    bar.finish_and_clear();
+    copied?;
+    Ok(())
+}
+
+/// Unpack a `.tar.gz` into `dest`; entries that would escape `dest` are skipped. Release
+/// archives wrap the source in one top directory (`owner-repo-<sha>/`), which is unwrapped
+/// so the repository root ends up at `dest`.
+fn extract_tar_gz(archive: &Path, dest: &Path) -> Result<()> {
+    let file = fs::File::open(archive)?;
+    tar::Archive::new(flate2::read::GzDecoder::new(file))
+        .unpack(dest)
+        .with_context(|| format!("Failed to extract {}", archive.display()))?;
+
+    let entries: Vec<PathBuf> = fs::read_dir(dest)?.filter_map(|e| e.ok()).map(|e| e.path()).collect();
+    if let [top] = entries.as_slice() {
+        if top.is_dir() {
+            // Moved aside first, in case it holds an entry with its own name
+            let wrapper = dest.with_extension("top");
+            if wrapper.exists() {
+                fs::remove_dir_all(&wrapper)?;
+            }
+            fs::rename(top, &wrapper)?;
+            for entry in fs::read_dir(&wrapper)?.filter_map(|e| e.ok()) {
+                fs::rename(entry.path(), dest.join(entry.file_name()))?;
+            }
+            fs::remove_dir(&wrapper)?;
+        }
+    }
+    Ok(())
+}
+
+/// The hash for `file_name` in `sha256sum`-style output (`<hash>  <name>` per line). Names
+/// must match exactly; a file holding only a bare hash is taken as-is.
+fn parse_sha256_listing(content: &str, file_name: &str) -> Option<String> {
+    let entries: Vec<(&str, &str)> = content
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let hash = parts.next()?;
+            let name = parts.next().unwrap_or("").trim_start_matches('*');
+            let is_hash = hash.len() == 64 && hash.chars().all(|c| c.is_ascii_hexdigit());
+            is_hash.then_some((hash, name))
+        })
+        .collect();
+
+    match entries.as_slice() {
+        [(hash, "")] => Some(hash.to_string()),
+        _ => entries.iter().find(|(_, name)| *name == file_name).map(|(hash, _)| hash.to_string()),
+    }
+}
+
+fn install_plugin(
+    repo: &str,
+    force: bool,
+    branch: Option<&str>,
+    release_tag: Option<&str>,
+    build: bool,
+    verify_checksum: bool,
+) -> Result<()> {
+    let theme = ColorfulTheme::default();
+
+    // Split off an optional @ref suffix; --branch wins if both are given
+    let (repo, suffix_ref) = match repo.split_once('@') {
+        Some((repo, git_ref)) if !git_ref.is_empty() => (repo, Some(git_ref)),
+        Some((repo, _)) => (repo, None),
+        None => (repo, None),
+    };
+    if let (Some(flag), Some(suffix)) = (branch, suffix_ref) {
+        if flag != suffix {
+            println!(
+                "{} Both --branch {} and @{} given; using --branch",
+                style("!").yellow(),
+                flag,
+                suffix
+            );
+        }
+    }
+    let git_ref = branch.or(suffix_ref);
+    if let (Some(tag), Some(suffix)) = (release_tag, suffix_ref) {
+        anyhow::bail!("Both --release-tag {} and @{} given; use one of them", tag, suffix);
+    }
+
+    // Parse the repo format (username/repo)
+    let parts: Vec<&str> = repo.split('/').collect();
+    if parts.len() != 2 {
+        anyhow::bail!(
+            "Invalid repository format. Expected 'username/repo', got '{}'",
+            repo
+        );
+    }
+
+    let username = parts[0];
+    let repo_name = parts[1];
+
+    println!();
     println!("{}", style("Installing plugin from GitHub...").cyan().bold());
     println!();
     println!("  Repository: {}", style(format!("{}/{}", username, repo_name)).yellow());
+    if let Some(git_ref) = git_ref {
+        println!("  Ref:        {}", style(git_ref).yellow());
+    }
     println!();
 
     // Create temp directory for cloning
@@ -839,30 +3052,68 @@ fn install_plugin(repo: &str, force: bool) -> Result<()> {
         fs::remove_dir_all(&temp_dir)?;
     }
 
-    // Clone the repository
-    println!("  {} Cloning repository...", style("[1/4]").bold().dim());
-    let github_url = format!("https://github.com/{}/{}.git", username, repo_name);
-
-    let clone_output = Command::new("git")
-        .args([
-            "clone",
-            "--depth", "1",
-            &github_url,
-            &temp_dir.to_string_lossy(),
-        ])
-        .output()
-        .context("Failed to run git clone. Is git installed?")?;
-
-    if !clone_output.status.success() {
-        let stderr = String::from_utf8_lossy(&clone_output.stderr);
-        anyhow::bail!("Failed to clone repository: {}", stderr.trim());
+    // An explicit ref always clones; otherwise prefer the source archive of a GitHub release
+    let repo_slug = format!("{}/{}", username, repo_name);
+    let release = if git_ref.is_some() {
+        None
+    } else {
+        match find_release_archive(&repo_slug, release_tag) {
+            Ok(release) => release,
+            Err(e) if release_tag.is_none() => {
+                println!(
+                    "  {} Could not check GitHub releases ({}); cloning instead",
+                    style("!").yellow(),
+                    scrub_token(&e.to_string())
+                );
+                None
+            }
+            Err(e) => return Err(anyhow::anyhow!("Failed to fetch release {}: {}", release_tag.unwrap_or_default(), scrub_token(&e.to_string()))),
+        }
+    };
+    if let (Some(tag), None) = (release_tag, &release) {
+        anyhow::bail!("No release tagged '{}' in {}", tag, repo_slug);
     }
-    println!("    {} Repository cloned", style("✓").green());
+
+    // The tag or commit the installed source came from
+    let installed_tag = release.as_ref().map(|r| r.tag.clone());
+    // The downloaded archive's name and SHA-256, checked against the release's checksums
+    let mut archive_checksum: Option<(String, String)> = None;
+    let resolved_ref = match release {
+        Some(release) => {
+            println!("  {} Downloading release {}...", style("[1/4]").bold().dim(), release.tag);
+            fs::create_dir_all(&temp_dir)?;
+            let archive_path = temp_dir.with_extension("tar.gz");
+            let unpacked = download_release_archive(&release, &archive_path)
+                .map_err(|e| anyhow::anyhow!("Failed to download {}: {}", release.name, scrub_token(&e.to_string())))
+                .and_then(|_| extract_tar_gz(&archive_path, &temp_dir))
+                .and_then(|_| Ok(format!("{:x}", Sha256::digest(fs::read(&archive_path)?))));
+            let _ = fs::remove_file(&archive_path);
+            match unpacked {
+                Ok(sha256) => archive_checksum = Some((release.name.clone(), sha256)),
+                Err(e) => {
+                    let _ = fs::remove_dir_all(&temp_dir);
+                    return Err(e);
+                }
+            }
+            println!("    {} Release {} downloaded ({})", style("✓").green(), release.tag, release.name);
+            release.tag
+        }
+        None => {
+            println!("  {} Cloning repository...", style("[1/4]").bold().dim());
+            let github_url = github_clone_url(&repo_slug);
+
+            clone_repo_at(&github_url, &temp_dir, git_ref)
+                .map_err(|e| anyhow::anyhow!("Failed to clone repository: {}", scrub_token(&e.to_string())))?;
+            let resolved_sha = run_git(&temp_dir, &["rev-parse", "HEAD"])?;
+            println!("    {} Repository cloned at {}", style("✓").green(), &resolved_sha[..resolved_sha.len().min(12)]);
+            resolved_sha
+        }
+    };
 
     // Determine plugin directory - could be the repo root or a subdirectory
     println!("  {} Validating plugin...", style("[2/4]").bold().dim());
 
-    let plugin_source_dir = find_plugin_in_dir(&temp_dir)?;
+    let plugin_source_dir = find_plugin_in_dir(&temp_dir, PLUGIN_SEARCH_DEPTH)?;
     let remote_info = PluginInfo::from_dir(&plugin_source_dir)?;
 
     let plugin_id = &remote_info.id;
@@ -883,6 +3134,41 @@ fn install_plugin(repo: &str, force: bool) -> Result<()> {
         println!("      Author: {}", style(author).cyan());
     }
 
+    // Release checksums cover the published archives, so a clone has nothing to check against
+    match (verify_checksum, &archive_checksum, &installed_tag) {
+        (true, Some((archive_name, actual)), Some(tag)) => {
+            let repo = format!("{}/{}", username, repo_name);
+            match fetch_release_checksum(&repo, tag, plugin_id, &remote_info.version, archive_name) {
+                Ok(Some((asset, expected))) => {
+                    if !actual.eq_ignore_ascii_case(&expected) {
+                        let _ = fs::remove_dir_all(&temp_dir);
+                        anyhow::bail!(
+                            "Checksum mismatch: {} publishes {} for {}, but the download hashes to {}. \
+                             The archive may have been changed after the release; use --skip-checksum to install anyway.",
+                            asset,
+                            expected,
+                            archive_name,
+                            actual
+                        );
+                    }
+                    println!("    {} {} matches {} from the release", style("✓").green(), archive_name, asset);
+                }
+                Ok(None) => {
+                    println!("    {} No release checksum published; download not verified", style("!").yellow());
+                }
+                Err(e) => println!(
+                    "    {} Could not check the release checksum ({}); download not verified",
+                    style("!").yellow(),
+                    scrub_token(&format!("{:#}", e))
+                ),
+            }
+        }
+        (true, None, _) => {
+            println!("    {} Installed from a clone; release checksums only cover release archives, so the source is not verified", style("!").yellow());
+        }
+        _ => {}
+    }
+
     // Check if already installed
     println!("  {} Checking existing installation...", style("[3/4]").bold().dim());
 
@@ -1010,77 +3296,233 @@ fn install_plugin(repo: &str, force: bool) -> Result<()> {
     // Copy plugin to plugins directory
     println!("  {} Installing plugin...", style("[4/4]").bold().dim());
 
-    copy_dir_recursive(&plugin_source_dir, &target_dir)?;
+    let bar = ProgressBar::new(copy_size(&plugin_source_dir));
+    bar.set_style(
+        ProgressStyle::default_bar()
+            .template("    [{bar:30.cyan/dim}] {bytes}/{total_bytes}")
+            .unwrap()
+            .progress_chars("=> ")
+    );
+    let copied = copy_dir_recursive(&plugin_source_dir, &target_dir, &|done, total| {
+        bar.set_length(total);
+        bar.set_position(done);
+    });
+    bar.finish_and_clear();
+    copied?;
 
     // Cleanup temp directory
     let _ = fs::remove_dir_all(&temp_dir);
 
+    // Record where the plugin came from, pinned to the resolved commit or release tag.
+    // The entry stays disabled until the plugin's first successful build.
+    let source = format!("github:{}@{}", repo_slug, resolved_ref);
+    update_config_for_plugin(
+        plugin_id,
+        remote_info.has_backend,
+        remote_info.has_frontend,
+        Vec::new(),
+        None,
+        &configured_plugin_features(plugin_id)?,
+        &[],
+        false,
+        None,
+        Some(&source),
+        false,
+    )?;
+
     println!("    {} Plugin installed to {}", style("✓").green(), target_dir.display());
 
+    // The plugin stays installed if its build fails; the error says how to retry
+    if build {
+        println!();
+        build_plugin(plugin_id, &BuildOptions { force: true, ..Default::default() }).with_context(|| {
+            format!("'{}' was installed but failed to build; fix it and run 'webarcade build {}'", plugin_id, plugin_id)
+        })?;
+    }
+
     println!();
     println!("{}", style("╔══════════════════════════════════════════╗").green());
-    println!("{}", style("║         Plugin Installed!                ║").green());
+    if build {
+        println!("{}", style("║         Installed and built!             ║").green());
+    } else {
+        println!("{}", style("║         Installed (not built)            ║").green());
+    }
     println!("{}", style("╚══════════════════════════════════════════╝").green());
     println!();
     println!("  Next steps:");
     println!();
-    println!("    {} {}", style("webarcade build").cyan(), plugin_id);
-    println!("    {} {}", style("webarcade run").cyan(), "");
+    if !build {
+        println!("    {} {}", style("webarcade build").cyan(), plugin_id);
+    }
+    println!("    {}", style("webarcade run").cyan());
     println!();
 
     Ok(())
 }
 
+fn remove_plugin(plugin_id: &str, force: bool) -> Result<()> {
+    let plugin_dir = get_plugins_dir()?.join(plugin_id);
+    let dist_plugins_dir = get_dist_plugins_dir()?;
+    let config_path = get_config_path()?;
+    let mut config = WebArcadeConfig::load_or_create(&config_path)?;
+    let entry = config.plugins.get(plugin_id).cloned();
+
+    if !plugin_dir.exists() && entry.is_none() {
+        anyhow::bail!("Plugin '{}' not found", plugin_id);
+    }
+
+    if !force {
+        let prompt = match entry.as_ref().and_then(|e| e.source.as_deref()) {
+            Some(source) => format!("Remove plugin '{}' (installed from {})?", plugin_id, source),
+            None => format!("Remove plugin '{}'?", plugin_id),
+        };
+        let confirmed = Confirm::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
+            .default(false)
+            .interact()?;
+        if !confirmed {
+            println!("{}", style("Removal cancelled.").yellow());
+            return Ok(());
+        }
+    }
+
+    if plugin_dir.exists() {
+        fs::remove_dir_all(&plugin_dir)?;
+        println!("  {} Removed {}", style("✓").green(), plugin_dir.display());
+    }
+
+    // Built artifacts for any platform, plus a custom path recorded in config
+    let mut artifacts = vec![
+        dist_plugins_dir.join(format!("{}.dll", plugin_id)),
+        dist_plugins_dir.join(format!("lib{}.so", plugin_id)),
+        dist_plugins_dir.join(format!("lib{}.dylib", plugin_id)),
+        dist_plugins_dir.join(format!("{}.js", plugin_id)),
+        dist_plugins_dir.join(format!("{}.wasm", plugin_id)),
+        dist_plugins_dir.join(format!("{}.cwasm", plugin_id)),
+        dist_plugins_dir.join(format!("{}.exports.json", plugin_id)),
+        dist_plugins_dir.join(format!("{}_bg.wasm", plugin_id)),
+    ];
+    if let Some(entry) = &entry {
+        artifacts.push(dist_plugins_dir.join(&entry.path));
+    }
+    for artifact in artifacts {
+        if artifact.is_file() {
+            fs::remove_file(&artifact)?;
+            println!("  {} Removed {}", style("✓").green(), artifact.display());
+        }
+    }
+
+    if entry.is_some() {
+        config.remove_plugin(plugin_id);
+        config.save(&config_path)?;
+        println!("  {} Removed config entry", style("✓").green());
+    }
+
+    Ok(())
+}
+
+/// How many directory levels below a repository root to look for a plugin
+const PLUGIN_SEARCH_DEPTH: usize = 3;
+
+fn looks_like_plugin(dir: &Path) -> bool {
+    let has_backend = dir.join("mod.rs").exists() && dir.join("Cargo.toml").exists();
+    let has_frontend = dir.join("index.jsx").exists() || dir.join("index.js").exists();
+    dir.join("plugin.toml").exists() || has_backend || has_frontend
+}
+
 /// Find the plugin directory within a cloned repo
-/// The plugin could be at the repo root or in a subdirectory
-fn find_plugin_in_dir(dir: &Path) -> Result<PathBuf> {
+/// The plugin could be at the repo root or in a subdirectory up to `max_depth` levels deep
+/// (e.g. `packages/plugins/my-plugin/` in a monorepo). Directories with a plugin.toml
+/// win over the rest; if several candidates remain the user picks one.
+fn find_plugin_in_dir(dir: &Path, max_depth: usize) -> Result<PathBuf> {
     // Check if root is a plugin
-    let has_backend_root = dir.join("mod.rs").exists() && dir.join("Cargo.toml").exists();
-    let has_frontend_root = dir.join("index.jsx").exists() || dir.join("index.js").exists();
-
-    if has_backend_root || has_frontend_root {
+    if looks_like_plugin(dir) {
         return Ok(dir.to_path_buf());
     }
 
-    // Check common subdirectory names
-    for subdir_name in &["plugin", "src", "plugin_src"] {
-        let subdir = dir.join(subdir_name);
-        if subdir.exists() && subdir.is_dir() {
-            let has_backend = subdir.join("mod.rs").exists() && subdir.join("Cargo.toml").exists();
-            let has_frontend = subdir.join("index.jsx").exists() || subdir.join("index.js").exists();
-            if has_backend || has_frontend {
-                return Ok(subdir);
-            }
+    let mut candidates: Vec<PathBuf> = Vec::new();
+    let mut walker = WalkDir::new(dir).min_depth(1).max_depth(max_depth).sort_by_file_name().into_iter();
+    while let Some(entry) = walker.next() {
+        let Ok(entry) = entry else { continue };
+        if !entry.file_type().is_dir() {
+            continue;
+        }
+
+        // Skip hidden directories and common non-plugin dirs
+        let name = entry.file_name().to_string_lossy();
+        if name.starts_with('.') || name == "node_modules" || name == "target" {
+            walker.skip_current_dir();
+            continue;
+        }
+
+        if looks_like_plugin(entry.path()) {
+            candidates.push(entry.path().to_path_buf());
+            // A plugin's own subdirectories (components/, assets/) aren't separate plugins
+            walker.skip_current_dir();
         }
     }
 
-    // Check for any subdirectory that looks like a plugin
-    for entry in fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_dir() {
-            // Skip hidden directories and common non-plugin dirs
-            let name = entry.file_name().to_string_lossy().to_string();
-            if name.starts_with('.') || name == "node_modules" || name == "target" {
-                continue;
-            }
+    if candidates.iter().any(|c| c.join("plugin.toml").exists()) {
+        candidates.retain(|c| c.join("plugin.toml").exists());
+    }
 
-            let has_backend = path.join("mod.rs").exists() && path.join("Cargo.toml").exists();
-            let has_frontend = path.join("index.jsx").exists() || path.join("index.js").exists();
-            if has_backend || has_frontend {
-                return Ok(path);
+    match candidates.len() {
+        0 => anyhow::bail!(
+            "Could not find a valid plugin in the repository (searched {} levels deep). \
+            Expected mod.rs + Cargo.toml (for backend) or index.jsx/index.js (for frontend).",
+            max_depth
+        ),
+        1 => Ok(candidates.remove(0)),
+        _ => {
+            let labels: Vec<String> = candidates
+                .iter()
+                .map(|c| c.strip_prefix(dir).unwrap_or(c).display().to_string())
+                .collect();
+            if !console::Term::stdout().is_term() {
+                anyhow::bail!("The repository contains several plugins: {}", labels.join(", "));
             }
+
+            let selection = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("Several plugins found, which one?")
+                .items(&labels)
+                .default(0)
+                .interact()?;
+            Ok(candidates.remove(selection))
         }
     }
+}
 
-    anyhow::bail!(
-        "Could not find a valid plugin in the repository. \
-        Expected mod.rs + Cargo.toml (for backend) or index.jsx/index.js (for frontend)."
-    )
+/// Skip .git directory and other common non-essential directories when copying
+fn is_copy_excluded(name: &std::ffi::OsStr) -> bool {
+    matches!(name.to_str(), Some(".git" | "node_modules" | "target"))
 }
 
-/// Recursively copy a directory
-fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
+/// Total size in bytes of the files `copy_dir_recursive` would copy from `src`
+fn copy_size(src: &Path) -> u64 {
+    WalkDir::new(src)
+        .into_iter()
+        .filter_entry(|e| e.depth() == 0 || !is_copy_excluded(e.file_name()))
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_file())
+        .filter_map(|e| fs::metadata(e.path()).ok())
+        .map(|m| m.len())
+        .sum()
+}
+
+/// Recursively copy a directory, calling `progress(bytes_copied, total_bytes)` after each file
+fn copy_dir_recursive(src: &Path, dst: &Path, progress: &dyn Fn(u64, u64)) -> Result<()> {
+    let total = copy_size(src);
+    let mut copied = 0;
+    copy_dir_with_progress(src, dst, &mut copied, total, progress)
+}
+
+fn copy_dir_with_progress(
+    src: &Path,
+    dst: &Path,
+    copied: &mut u64,
+    total: u64,
+    progress: &dyn Fn(u64, u64),
+) -> Result<()> {
     fs::create_dir_all(dst)?;
 
     for entry in fs::read_dir(src)? {
@@ -1088,22 +3530,37 @@ fn copy_dir_recursive(src: &Path, dst: &Path) -> Result<()> {
         let src_path = entry.path();
         let dst_path = dst.join(entry.file_name());
 
-        // Skip .git directory and other common non-essential directories
-        let name = entry.file_name().to_string_lossy().to_string();
-        if name == ".git" || name == "node_modules" || name == "target" {
+        if is_copy_excluded(&entry.file_name()) {
             continue;
         }
 
         if src_path.is_dir() {
-            copy_dir_recursive(&src_path, &dst_path)?;
+            copy_dir_with_progress(&src_path, &dst_path, copied, total, progress)?;
         } else {
-            fs::copy(&src_path, &dst_path)?;
+            *copied += fs::copy(&src_path, &dst_path)?;
+            progress(*copied, total);
         }
     }
 
     Ok(())
 }
 
+/// Copy a directory tree as-is, including the .git, node_modules and target directories
+/// `copy_dir_recursive` leaves out
+fn copy_dir_all(src: &Path, dst: &Path) -> Result<()> {
+    for entry in WalkDir::new(src) {
+        let entry = entry?;
+        let dst_path = dst.join(entry.path().strip_prefix(src)?);
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&dst_path)?;
+        } else {
+            fs::copy(entry.path(), &dst_path)
+                .with_context(|| format!("Failed to copy {}", entry.path().display()))?;
+        }
+    }
+    Ok(())
+}
+
 fn print_banner() {
     println!();
     println!("{}", style(r#"
@@ -1127,37 +3584,171 @@ fn clear_screen() {
     std::io::stdout().flush().unwrap();
 }
 
-fn interactive_menu() -> Result<()> {
-    let theme = ColorfulTheme::default();
+/// An entry in a `select_menu`
+struct MenuItem {
+    label: String,
+    /// Key that picks this item directly (shown as a hint)
+    shortcut: Option<char>,
+    /// Extra text the `/` filter matches, e.g. a plugin ID
+    keywords: String,
+}
+
+impl MenuItem {
+    fn new(label: impl Into<String>) -> Self {
+        Self { label: label.into(), shortcut: None, keywords: String::new() }
+    }
+
+    fn shortcut(mut self, key: char) -> Self {
+        self.shortcut = Some(key.to_ascii_lowercase());
+        self
+    }
+
+    fn keywords(mut self, keywords: impl Into<String>) -> Self {
+        self.keywords = keywords.into();
+        self
+    }
+
+    /// Label with the shortcut letter bracketed, e.g. `[P]ackage App`
+    fn display(&self) -> String {
+        let Some(key) = self.shortcut else {
+            return self.label.clone();
+        };
+        match self.label.char_indices().find(|(_, c)| c.to_ascii_lowercase() == key) {
+            Some((i, c)) => format!("{}[{}]{}", &self.label[..i], c, &self.label[i + c.len_utf8()..]),
+            None => format!("{} [{}]", self.label, key.to_ascii_uppercase()),
+        }
+    }
+
+    /// fzf-style match: the query's characters appear in order in the label or keywords
+    fn matches(&self, query: &str) -> bool {
+        let is_subsequence = |haystack: &str| {
+            let mut chars = haystack.chars().flat_map(char::to_lowercase);
+            query.chars().flat_map(char::to_lowercase).all(|q| chars.any(|c| c == q))
+        };
+        is_subsequence(&self.label) || is_subsequence(&self.keywords)
+    }
+}
+
+/// Menu driven by single keys: arrows and Enter, an item's shortcut key to pick it
+/// directly, and `/` to filter the items by typing. Returns None on Escape.
+/// Falls back to a plain `Select` when stdout isn't a terminal.
+fn select_menu(prompt: &str, items: &[MenuItem]) -> Result<Option<usize>> {
+    let term = Term::stdout();
+    if !term.is_term() {
+        let labels: Vec<String> = items.iter().map(|i| i.label.clone()).collect();
+        return Ok(Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(prompt)
+            .items(&labels)
+            .default(0)
+            .interact_opt()?);
+    }
+
+    let mut cursor = 0;
+    let mut filter: Option<String> = None;
+    let mut drawn = 0;
+    term.hide_cursor()?;
+
+    let result = loop {
+        let visible: Vec<usize> = (0..items.len())
+            .filter(|&i| filter.as_deref().map(|q| items[i].matches(q)).unwrap_or(true))
+            .collect();
+        cursor = cursor.min(visible.len().saturating_sub(1));
+
+        term.clear_last_lines(drawn)?;
+        let mut lines = vec![format!(
+            "{} {} {}",
+            style("?").yellow(),
+            style(prompt).bold(),
+            style(if filter.is_some() { "(Esc to clear filter)" } else { "(/ to filter)" }).dim()
+        )];
+        if let Some(query) = &filter {
+            lines.push(format!("  {} {}", style("/").cyan(), query));
+        }
+        if visible.is_empty() {
+            lines.push(format!("  {}", style("(no matches)").dim()));
+        }
+        for (row, &i) in visible.iter().enumerate() {
+            if row == cursor {
+                lines.push(format!("{} {}", style("❯").cyan(), style(items[i].display()).cyan()));
+            } else {
+                lines.push(format!("  {}", items[i].display()));
+            }
+        }
+        for line in &lines {
+            term.write_line(line)?;
+        }
+        drawn = lines.len();
+
+        match term.read_key()? {
+            Key::ArrowUp => cursor = cursor.saturating_sub(1),
+            Key::ArrowDown => cursor = (cursor + 1).min(visible.len().saturating_sub(1)),
+            Key::Enter => {
+                if let Some(&i) = visible.get(cursor) {
+                    break Some(i);
+                }
+            }
+            Key::Escape => match filter {
+                Some(_) => filter = None,
+                None => break None,
+            },
+            Key::Backspace => {
+                if let Some(query) = &mut filter {
+                    query.pop();
+                }
+            }
+            Key::Char('/') if filter.is_none() => {
+                filter = Some(String::new());
+                cursor = 0;
+            }
+            Key::Char(c) => match &mut filter {
+                Some(query) => {
+                    query.push(c);
+                    cursor = 0;
+                }
+                None => {
+                    let key = c.to_ascii_lowercase();
+                    if let Some(i) = items.iter().position(|item| item.shortcut == Some(key)) {
+                        break Some(i);
+                    }
+                }
+            },
+            _ => {}
+        }
+    };
+
+    term.clear_last_lines(drawn)?;
+    term.show_cursor()?;
+    if let Some(i) = result {
+        term.write_line(&format!("{} {} {}", style("✔").green(), style(prompt).bold(), style(&items[i].label).green()))?;
+    }
+    Ok(result)
+}
 
+fn interactive_menu() -> Result<()> {
     clear_screen();
     print_banner();
 
     loop {
-        let menu_items = vec![
-            "📦 Package App        - Build and create installer",
-            "🔨 Build Plugin       - Compile a plugin",
-            "✨ Create Plugin      - Create a new plugin project",
-            "📥 Install Plugin     - Install from GitHub",
-            "📋 List Plugins       - Show available plugins",
-            "🚪 Exit",
+        let menu_items = [
+            MenuItem::new("📦 Package App        - Build and create installer").shortcut('p'),
+            MenuItem::new("🔨 Build Plugin       - Compile a plugin").shortcut('b'),
+            MenuItem::new("✨ Create Plugin      - Create a new plugin project").shortcut('c'),
+            MenuItem::new("📥 Install Plugin     - Install from GitHub").shortcut('i'),
+            MenuItem::new("📋 List Plugins       - Show available plugins").shortcut('l'),
+            MenuItem::new("🚪 Exit").shortcut('q'),
         ];
 
-        let selection = Select::with_theme(&theme)
-            .with_prompt("What would you like to do?")
-            .items(&menu_items)
-            .default(0)
-            .interact()?;
+        let selection = select_menu("What would you like to do?", &menu_items)?;
 
         println!();
 
         let result = match selection {
-            0 => package_app(false, false, false, false, None, None, None, None),
-            1 => interactive_build_plugin(),
-            2 => interactive_create_plugin(),
-            3 => interactive_install_plugin(),
-            4 => list_plugins(),
-            5 => {
+            Some(0) => package_app(PackageOptions::default()),
+            Some(1) => interactive_build_plugin(),
+            Some(2) => interactive_create_plugin(),
+            Some(3) => interactive_install_plugin(),
+            Some(4) => list_plugins(false),
+            Some(5) | None => {
                 println!("{}", style("👋 Goodbye! Happy coding!").cyan());
                 println!();
                 return Ok(());
@@ -1175,7 +3766,7 @@ fn interactive_menu() -> Result<()> {
     }
 }
 
-fn init_project(project_name: &str, branch: &str) -> Result<()> {
+fn init_project(project_name: &str, display_name: Option<&str>, branch: &str, example_plugin: Option<&str>) -> Result<()> {
     let current_dir = std::env::current_dir()?;
     let project_dir = current_dir.join(project_name);
 
@@ -1183,6 +3774,10 @@ fn init_project(project_name: &str, branch: &str) -> Result<()> {
     if project_dir.exists() {
         anyhow::bail!("Directory '{}' already exists", project_name);
     }
+    let display_name = display_name.map(str::trim);
+    if display_name == Some("") {
+        anyhow::bail!("--name must not be empty");
+    }
 
     println!();
     println!("{}", style("Initializing WebArcade project...").cyan().bold());
@@ -1190,28 +3785,9 @@ fn init_project(project_name: &str, branch: &str) -> Result<()> {
 
     // Clone the repository
     println!("  {} Cloning repository...", style("[1/3]").bold().dim());
-    let clone_status = Command::new("git")
-        .args([
-            "clone",
-            "--depth", "1",
-            "--branch", branch,
-            "https://github.com/warcade/core.git",
-            project_name,
-        ])
-        .status()
-        .context("Failed to run git clone. Is git installed?")?;
-
-    if !clone_status.success() {
-        anyhow::bail!("Failed to clone repository");
-    }
+    clone_template(branch, &project_dir)?;
     println!("    {} Repository cloned", style("✓").green());
 
-    // Remove .git directory to start fresh
-    let git_dir = project_dir.join(".git");
-    if git_dir.exists() {
-        fs::remove_dir_all(&git_dir)?;
-    }
-
     // Initialize new git repo
     let _ = Command::new("git")
         .current_dir(&project_dir)
@@ -1220,30 +3796,20 @@ fn init_project(project_name: &str, branch: &str) -> Result<()> {
 
     // Install npm dependencies
     println!("  {} Installing dependencies...", style("[2/3]").bold().dim());
-
-    let install_status = if Command::new("bun").arg("--version").output().is_ok() {
-        Command::new("bun")
-            .current_dir(&project_dir)
-            .arg("install")
-            .status()
-            .context("Failed to run bun install")?
-    } else if Command::new("npm").arg("--version").output().is_ok() {
-        Command::new("npm")
-            .current_dir(&project_dir)
-            .arg("install")
-            .status()
-            .context("Failed to run npm install")?
-    } else {
-        anyhow::bail!("Neither bun nor npm found. Please install bun (https://bun.sh) or npm.");
-    };
-
-    if !install_status.success() {
-        println!("    {} Failed to install dependencies (you can run 'bun install' manually)", style("!").yellow());
-    } else {
-        println!("    {} Dependencies installed", style("✓").green());
-    }
+    install_js_dependencies(&project_dir)?;
 
     println!("  {} Setting up project...", style("[3/3]").bold().dim());
+    if let Some(name) = display_name {
+        set_app_display_name(&project_dir, name)?;
+        println!("    {} App name set to {}", style("✓").green(), style(name).green());
+    }
+    if let Some(example_id) = example_plugin {
+        // Full-stack scaffold: GET /hello route + SolidJS viewport calling it through the bridge
+        println!();
+        create_plugin_in(&plugins_dir_in(&project_dir), example_id, NewPluginOptions::default())?;
+        println!();
+        println!("    {} Example plugin '{}' created", style("✓").green(), example_id);
+    }
     println!("    {} Project ready", style("✓").green());
 
     println!();
@@ -1254,14 +3820,214 @@ fn init_project(project_name: &str, branch: &str) -> Result<()> {
     println!("  Next steps:");
     println!();
     println!("    {} {}", style("cd").cyan(), project_name);
-    println!("    {} {}", style("webarcade new").cyan(), "my-plugin");
-    println!("    {} {}", style("webarcade build").cyan(), "my-plugin");
-    println!("    {} {}", style("webarcade run").cyan(), "");
+    match example_plugin {
+        Some(example_id) => println!("    {} {}", style("webarcade build").cyan(), example_id),
+        None => {
+            println!("    {} my-plugin", style("webarcade new").cyan());
+            println!("    {} my-plugin", style("webarcade build").cyan());
+        }
+    }
+    println!("    {}", style("webarcade run").cyan());
+    println!();
+
+    Ok(())
+}
+
+/// Set the app's human-readable name (spaces allowed) as the packager `product-name` in
+/// app/Cargo.toml and as `name` in webarcade.config.json. The cargo package name is left alone
+/// until `webarcade package` applies the full app config.
+fn set_app_display_name(project_dir: &Path, name: &str) -> Result<()> {
+    let cargo_toml_path = project_dir.join("app").join("Cargo.toml");
+    let mut doc: toml_edit::DocumentMut = fs::read_to_string(&cargo_toml_path)
+        .with_context(|| format!("Failed to read {}", cargo_toml_path.display()))?
+        .parse()
+        .with_context(|| format!("Failed to parse {}", cargo_toml_path.display()))?;
+    if doc["package"].get("metadata").is_none() {
+        doc["package"]["metadata"] = toml_edit::table();
+    }
+    if doc["package"]["metadata"].get("packager").is_none() {
+        doc["package"]["metadata"]["packager"] = toml_edit::table();
+    }
+    doc["package"]["metadata"]["packager"]["product-name"] = toml_edit::value(name);
+    fs::write(&cargo_toml_path, doc.to_string())?;
+
+    let config_path = project_dir.join("webarcade.config.json");
+    let mut config = WebArcadeConfig::load_or_create(&config_path)?;
+    config.name = name.to_string();
+    config.save(&config_path)?;
+    Ok(())
+}
+
+/// Clone the project template into `dest`, without its git history
+fn clone_template(branch: &str, dest: &Path) -> Result<()> {
+    let clone_status = Command::new("git")
+        .args(["clone", "--depth", "1", "--branch", branch, "https://github.com/warcade/core.git"])
+        .arg(dest)
+        .status()
+        .context("Failed to run git clone. Is git installed?")?;
+
+    if !clone_status.success() {
+        anyhow::bail!("Failed to clone repository");
+    }
+
+    // Remove .git directory to start fresh
+    let git_dir = dest.join(".git");
+    if git_dir.exists() {
+        fs::remove_dir_all(&git_dir)?;
+    }
+    Ok(())
+}
+
+/// Run `bun install` (or `npm install`) in `dir`. A failed install only warns.
+fn install_js_dependencies(dir: &Path) -> Result<()> {
+    let install_status = if Command::new("bun").arg("--version").output().is_ok() {
+        Command::new("bun")
+            .current_dir(dir)
+            .arg("install")
+            .status()
+            .context("Failed to run bun install")?
+    } else if Command::new("npm").arg("--version").output().is_ok() {
+        Command::new("npm")
+            .current_dir(dir)
+            .arg("install")
+            .status()
+            .context("Failed to run npm install")?
+    } else {
+        anyhow::bail!("Neither bun nor npm found. Please install bun (https://bun.sh) or npm.");
+    };
+
+    if !install_status.success() {
+        println!("    {} Failed to install dependencies (you can run 'bun install' manually)", style("!").yellow());
+    } else {
+        println!("    {} Dependencies installed", style("✓").green());
+    }
+    Ok(())
+}
+
+/// Reset an existing project to the latest template. The template is cloned next to the
+/// project and prepared there; the project is only touched by the final rename, and put
+/// back if that fails. Plugins, compiled plugins, `.git`, `.env` and a customized
+/// webarcade.config.json are carried over. With `plugins_only`, just `app/` is replaced.
+fn reinit_project(project_name: &str, branch: &str, plugins_only: bool) -> Result<()> {
+    let project_dir = std::env::current_dir()?.join(project_name);
+    if !project_dir.is_dir() {
+        anyhow::bail!("Directory '{}' does not exist (run without --overwrite to create it)", project_name);
+    }
+
+    let sibling = |suffix: &str| {
+        let name = project_dir.file_name().unwrap_or_default().to_string_lossy();
+        project_dir.with_file_name(format!(".{}.{}", name, suffix))
+    };
+    let staging_dir = sibling("webarcade-init");
+    let backup_dir = sibling("webarcade-backup");
+    for dir in [&staging_dir, &backup_dir] {
+        if dir.exists() {
+            anyhow::bail!("{} exists from an earlier interrupted run; move or delete it first", dir.display());
+        }
+    }
+
+    println!();
+    println!("{}", style("Re-initializing WebArcade project...").cyan().bold());
+    println!();
+
+    println!("  {} Cloning repository...", style("[1/3]").bold().dim());
+    clone_template(branch, &staging_dir)?;
+    println!("    {} Repository cloned", style("✓").green());
+
+    let result = prepare_reinit(&project_dir, &staging_dir, plugins_only)
+        .and_then(|_| swap_in_template(&project_dir, &staging_dir, &backup_dir, plugins_only));
+    if staging_dir.exists() {
+        let _ = fs::remove_dir_all(&staging_dir);
+    }
+    result?;
+
+    println!();
+    println!("{}", style("╔══════════════════════════════════════════╗").green());
+    println!("{}", style("║        Project re-initialized!           ║").green());
+    println!("{}", style("╚══════════════════════════════════════════╝").green());
+    println!();
+    println!("  Rebuild your plugins with {}", style("webarcade build --all").cyan());
     println!();
 
     Ok(())
 }
 
+/// Copy what the project keeps into the freshly cloned template and install its dependencies
+fn prepare_reinit(project_dir: &Path, staging_dir: &Path, plugins_only: bool) -> Result<()> {
+    println!("  {} Restoring project files...", style("[2/3]").bold().dim());
+    let compiled_plugins = project_dir.join("app").join("plugins");
+    if compiled_plugins.is_dir() {
+        copy_dir_recursive(&compiled_plugins, &staging_dir.join("app").join("plugins"), &|_, _| {})?;
+    }
+
+    if !plugins_only {
+        for plugins_name in ["plugins", "plugins_src"] {
+            let plugins_dir = project_dir.join(plugins_name);
+            if plugins_dir.is_dir() {
+                let staged = staging_dir.join(plugins_name);
+                if staged.exists() {
+                    fs::remove_dir_all(&staged)?;
+                }
+                // Plugin sources keep their git checkouts and build state
+                copy_dir_all(&plugins_dir, &staged)?;
+                println!("    {} {}/ restored", style("✓").green(), plugins_name);
+            }
+        }
+
+        let env_file = project_dir.join(".env");
+        if env_file.exists() {
+            fs::copy(&env_file, staging_dir.join(".env"))?;
+        }
+
+        let config_path = project_dir.join("webarcade.config.json");
+        if let Ok(current) = fs::read_to_string(&config_path) {
+            let template = fs::read_to_string(staging_dir.join("webarcade.config.json")).unwrap_or_default();
+            if current != template {
+                fs::write(staging_dir.join("webarcade.config.json"), current)?;
+                println!("    {} webarcade.config.json restored", style("✓").green());
+            }
+        }
+    }
+
+    println!("  {} Installing dependencies...", style("[3/3]").bold().dim());
+    // With --plugins-only only app/ moves over, so install where it will end up being used
+    install_js_dependencies(if plugins_only { project_dir } else { staging_dir })
+}
+
+/// Move the prepared template into place, restoring the backup if a rename fails
+fn swap_in_template(project_dir: &Path, staging_dir: &Path, backup_dir: &Path, plugins_only: bool) -> Result<()> {
+    let (current, fresh) = if plugins_only {
+        (project_dir.join("app"), staging_dir.join("app"))
+    } else {
+        (project_dir.to_path_buf(), staging_dir.to_path_buf())
+    };
+
+    if current.exists() {
+        fs::rename(&current, backup_dir)
+            .with_context(|| format!("Failed to move {} aside", current.display()))?;
+    }
+    if let Err(e) = fs::rename(&fresh, &current) {
+        if backup_dir.exists() {
+            fs::rename(backup_dir, &current)
+                .with_context(|| format!("Failed to restore {} from {}", current.display(), backup_dir.display()))?;
+        }
+        return Err(e).with_context(|| format!("Failed to move the new template into {}", current.display()));
+    }
+
+    // Keep the project's git history
+    let old_git = backup_dir.join(".git");
+    if !plugins_only && old_git.exists() {
+        fs::rename(&old_git, project_dir.join(".git"))?;
+    } else if !plugins_only {
+        let _ = Command::new("git").current_dir(project_dir).args(["init"]).status();
+    }
+
+    if backup_dir.exists() {
+        fs::remove_dir_all(backup_dir)?;
+    }
+    Ok(())
+}
+
 fn sync_project(branch: &str, dry_run: bool) -> Result<()> {
     let repo_root = get_repo_root()?;
     let app_src_dir = repo_root.join("app").join("src");
@@ -1408,10 +4174,117 @@ fn sync_directory(
     Ok(())
 }
 
-fn dev_app() -> Result<()> {
+/// Parse a dotenv-style file into KEY=VALUE pairs.
+/// Blank lines and `#` comments are ignored, an optional `export ` prefix is allowed,
+/// double-quoted values support escapes and single-quoted values are taken literally.
+fn parse_env_file(content: &str) -> Result<Vec<(String, String)>> {
+    let mut vars = Vec::new();
+
+    for (index, raw_line) in content.lines().enumerate() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+
+        let (key, value) = line.split_once('=')
+            .with_context(|| format!("Line {}: expected KEY=VALUE", index + 1))?;
+        let key = key.trim();
+        if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            anyhow::bail!("Line {}: invalid variable name '{}'", index + 1, key);
+        }
+
+        let value = value.trim();
+        let value = if let Some(rest) = value.strip_prefix('"') {
+            let mut parsed = String::new();
+            let mut chars = rest.chars();
+            let mut closed = false;
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => {
+                        closed = true;
+                        break;
+                    }
+                    '\\' => match chars.next() {
+                        Some('n') => parsed.push('\n'),
+                        Some('r') => parsed.push('\r'),
+                        Some('t') => parsed.push('\t'),
+                        Some(other) => parsed.push(other),
+                        None => parsed.push('\\'),
+                    },
+                    other => parsed.push(other),
+                }
+            }
+            if !closed {
+                anyhow::bail!("Line {}: unterminated quoted value for '{}'", index + 1, key);
+            }
+            parsed
+        } else if let Some(rest) = value.strip_prefix('\'') {
+            match rest.find('\'') {
+                Some(end) => rest[..end].to_string(),
+                None => anyhow::bail!("Line {}: unterminated quoted value for '{}'", index + 1, key),
+            }
+        } else {
+            // Unquoted values end at an inline comment
+            match value.find(" #") {
+                Some(pos) => value[..pos].trim_end().to_string(),
+                None => value.to_string(),
+            }
+        };
+
+        vars.push((key.to_string(), value));
+    }
+
+    Ok(vars)
+}
+
+fn dev_app(args: &DevArgs) -> Result<()> {
     let repo_root = get_repo_root()?;
     let app_dir = repo_root.join("app");
 
+    // Load environment variables for the app (explicit file must exist, default .env is optional)
+    let env_path = match &args.env_file {
+        Some(path) => {
+            if !path.exists() {
+                anyhow::bail!("Env file not found: {}", path.display());
+            }
+            Some(path.clone())
+        }
+        None => Some(repo_root.join(".env")).filter(|p| p.exists()),
+    };
+    let env_vars = match &env_path {
+        Some(path) => {
+            let content = fs::read_to_string(path)?;
+            parse_env_file(&content)
+                .with_context(|| format!("Failed to parse {}", path.display()))?
+        }
+        None => Vec::new(),
+    };
+
+    // A fresh clone has nothing in app/plugins/, and the app would start with a blank screen.
+    // With --mock-api the backends are stubbed, so there's nothing to compile.
+    if args.mock_api.is_none() && !has_compiled_plugins(&get_dist_plugins_dir()?) && get_plugins_dir()?.is_dir() {
+        let build = if args.auto_build {
+            true
+        } else if console::user_attended() {
+            Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt("No compiled plugins found. Build all plugins now?")
+                .default(true)
+                .interact()?
+        } else {
+            println!(
+                "  {} No compiled plugins found; run {} or pass {}",
+                style("!").yellow(),
+                style("webarcade build --all").bold(),
+                style("--auto-build").bold()
+            );
+            false
+        };
+        if build {
+            build_all_plugins(&BuildOptions::default())?;
+        }
+    }
+
     println!();
     println!("{}", style("Running WebArcade in dev mode...").cyan().bold());
     println!();
@@ -1425,17 +4298,17 @@ fn dev_app() -> Result<()> {
         ("npm", "run")
     };
 
-    let mut dev_server = Command::new(pkg_manager)
+    let mut dev_server = KillOnDrop(Command::new(pkg_manager)
         .current_dir(&repo_root)
         .args([run_arg, "dev"])
         .stdout(std::process::Stdio::piped())
         .stderr(std::process::Stdio::piped())
         .spawn()
-        .context("Failed to start dev server")?;
+        .context("Failed to start dev server")?);
 
     // Wait for initial build to complete (look for "Dev server ready" message)
-    let stdout = dev_server.stdout.take().unwrap();
-    let stderr = dev_server.stderr.take().unwrap();
+    let stdout = dev_server.0.stdout.take().unwrap();
+    let stderr = dev_server.0.stderr.take().unwrap();
 
     // Spawn thread to forward stderr
     let stderr_handle = std::thread::spawn(move || {
@@ -1455,7 +4328,7 @@ fn dev_app() -> Result<()> {
             println!("    {}", line);
             if !ready && (line.contains("Dev server ready") || line.contains("watching for changes")) {
                 ready = true;
-                println!("    {} Dev server running (hot reload enabled)", "\x1b[32m✓\x1b[0m");
+                println!("    \x1b[32m✓\x1b[0m Dev server running (hot reload enabled)");
             }
         }
         ready
@@ -1464,20 +4337,73 @@ fn dev_app() -> Result<()> {
     // Give it a moment to start
     std::thread::sleep(std::time::Duration::from_millis(500));
 
+    // Everything started from here on is stopped by its guard, also when a later step fails
+    // Start sidecar dev servers for plugins that declare a devPort
+    let plugin_servers = start_plugin_dev_servers()?;
+
+    // Start docker compose services plugins need
+    let services = ComposeServices(if args.start_services {
+        start_plugin_services()?
+    } else {
+        Vec::new()
+    });
+
+    let mock_api = match &args.mock_api {
+        Some(spec) => {
+            let server = MockApiServer::start(spec, args.mock_port)?;
+            println!("    {} Mock API from {} on {}", style("✓").green(), spec.display(), server.url);
+            Some(server)
+        }
+        None => None,
+    };
+
+    // Watch backend plugins for changes while the app runs
+    let watcher = if args.hot_reload {
+        Some(PluginHotReloader::start()?)
+    } else {
+        None
+    };
+
     // Run the app with cargo run
     println!("  {} Starting app...", style("[2/2]").bold().dim());
+    if let Some(path) = &env_path {
+        println!("    {} Loaded {} variable(s) from {}", style("✓").green(), env_vars.len(), path.display());
+    }
+    let dev_config = if args.exclude.is_empty() {
+        None
+    } else {
+        let path = write_dev_config(&args.exclude)?;
+        println!("    {} Excluded for this run: {}", style("✓").green(), args.exclude.join(", "));
+        Some(path)
+    };
     println!();
 
-    let status = Command::new("cargo")
-        .current_dir(&app_dir)
+    let mut app = Command::new("cargo");
+    app.current_dir(&app_dir)
         .args(["run", "--release"])
-        .status()
-        .context("Failed to run cargo")?;
+        .envs(env_vars.iter().map(|(k, v)| (k, v)));
+    if let Some(path) = &dev_config {
+        app.env("WEBARCADE_CONFIG", path);
+    }
+    if let Some(server) = &mock_api {
+        app.env("WEBARCADE_MOCK_API_URL", &server.url);
+    }
+    let status = app.status().context("Failed to run cargo");
+    if let Some(path) = &dev_config {
+        let _ = fs::remove_file(path);
+    }
+    let status = status?;
 
-    // Clean up dev server when app exits
-    let _ = dev_server.kill();
+    // Clean up dev servers and watchers when app exits
+    drop(dev_server);
+    drop(plugin_servers);
     let _ = stdout_handle.join();
     let _ = stderr_handle.join();
+    if let Some(watcher) = watcher {
+        watcher.stop();
+    }
+    drop(mock_api);
+    drop(services);
 
     if !status.success() {
         anyhow::bail!("App failed to run");
@@ -1486,98 +4412,504 @@ fn dev_app() -> Result<()> {
     Ok(())
 }
 
-fn build_app(locked: bool) -> Result<()> {
-    let repo_root = get_repo_root()?;
-    let app_dir = repo_root.join("app");
+/// One stub from a `dev --mock-api` spec file
+struct MockRoute {
+    method: String,
+    /// Path segments; `:name` matches any one segment, a trailing `*` matches the rest
+    segments: Vec<String>,
+    status: u16,
+    body: Option<serde_json::Value>,
+    headers: Vec<(String, String)>,
+}
 
-    println!();
-    if locked {
-        println!("{}", style("Building locked app (plugins embedded)...").cyan().bold());
-    } else {
-        println!("{}", style("Building production app...").cyan().bold());
+impl MockRoute {
+    fn matches(&self, method: &str, path: &str) -> bool {
+        if !self.method.eq_ignore_ascii_case(method) {
+            return false;
+        }
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        for (i, pattern) in self.segments.iter().enumerate() {
+            if pattern == "*" && i == self.segments.len() - 1 {
+                return true;
+            }
+            match segments.get(i) {
+                Some(segment) if pattern.starts_with(':') || pattern == segment => {}
+                _ => return false,
+            }
+        }
+        segments.len() == self.segments.len()
     }
-    println!();
-
-    // Kill any running app processes before building
-    kill_running_app_processes()?;
-
-    // Build production frontend
-    println!("  {} Building frontend (production)...", style("[1/3]").bold().dim());
-    let build_status = run_bun_or_npm(&repo_root, &["run", "build:prod"])?;
+}
 
-    if !build_status.success() {
-        anyhow::bail!("Frontend build failed");
+/// Parse a mock spec: `{ "GET /users/:id": { "status": 200, "body": {...}, "headers": {...} } }`
+fn read_mock_spec(path: &Path) -> Result<Vec<MockRoute>> {
+    let content = fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let spec: serde_json::Map<String, serde_json::Value> = serde_json::from_str(&content)
+        .with_context(|| format!("{} must be a JSON object of \"METHOD /path\" keys", path.display()))?;
+    let mut routes = Vec::new();
+    for (route, stub) in spec {
+        let Some((method, route_path)) = route.split_once(' ') else {
+            anyhow::bail!("{}: '{}' should look like \"GET /hello\"", path.display(), route);
+        };
+        let status = stub.get("status").and_then(|s| s.as_u64()).unwrap_or(200);
+        let status = u16::try_from(status)
+            .ok()
+            .filter(|s| (100..=599).contains(s))
+            .with_context(|| format!("{}: '{}' has an invalid status {}", path.display(), route, status))?;
+        let headers = stub
+            .get("headers")
+            .and_then(|h| h.as_object())
+            .map(|h| h.iter().map(|(k, v)| (k.clone(), v.as_str().map(String::from).unwrap_or_else(|| v.to_string()))).collect())
+            .unwrap_or_default();
+        routes.push(MockRoute {
+            method: method.trim().to_uppercase(),
+            segments: route_path.trim().split('/').filter(|s| !s.is_empty()).map(String::from).collect(),
+            status,
+            body: stub.get("body").cloned(),
+            headers,
+        });
     }
-    println!("    {} Frontend built", style("✓").green());
+    Ok(routes)
+}
 
-    // Build Rust app
-    println!("  {} Building app...", style("[2/3]").bold().dim());
-    let cargo_args = if locked {
-        vec!["build", "--release", "--features", "locked-plugins"]
+/// The stub for a request, with CORS headers so the app's webview can call the mock directly
+fn mock_response(routes: &[MockRoute], method: &str, url: &str) -> tiny_http::Response<std::io::Cursor<Vec<u8>>> {
+    let path = url.split(['?', '#']).next().unwrap_or(url);
+    let (status, body, content_type, extra_headers) = if method.eq_ignore_ascii_case("OPTIONS") {
+        (204, Vec::new(), None, Vec::new())
     } else {
-        vec!["build", "--release"]
+        match routes.iter().find(|route| route.matches(method, path)) {
+            Some(route) => match &route.body {
+                Some(serde_json::Value::String(text)) => (route.status, text.clone().into_bytes(), Some("text/plain; charset=utf-8"), route.headers.clone()),
+                Some(body) => (route.status, body.to_string().into_bytes(), Some("application/json"), route.headers.clone()),
+                None => (route.status, Vec::new(), None, route.headers.clone()),
+            },
+            None => {
+                let error = serde_json::json!({ "error": format!("No mock for {} {}", method, path) });
+                (404, error.to_string().into_bytes(), Some("application/json"), Vec::new())
+            }
+        }
     };
 
-    let status = Command::new("cargo")
-        .current_dir(&app_dir)
-        .args(&cargo_args)
-        .status()
-        .context("Failed to run cargo build")?;
-
-    if !status.success() {
-        anyhow::bail!("Cargo build failed");
+    let mut response = tiny_http::Response::from_data(body).with_status_code(status);
+    let mut headers: Vec<(String, String)> = vec![
+        ("Access-Control-Allow-Origin".to_string(), "*".to_string()),
+        ("Access-Control-Allow-Methods".to_string(), "GET, POST, PUT, PATCH, DELETE, OPTIONS".to_string()),
+        ("Access-Control-Allow-Headers".to_string(), "*".to_string()),
+    ];
+    if let Some(content_type) = content_type {
+        headers.push(("Content-Type".to_string(), content_type.to_string()));
     }
-    println!("    {} App built", style("✓").green());
-
-    // Package with cargo-packager
-    println!("  {} Packaging installer...", style("[3/3]").bold().dim());
-    let status = Command::new("cargo")
-        .current_dir(&app_dir)
-        .args(["packager", "--release"])
-        .status()
-        .context("Failed to run cargo packager")?;
-
-    if !status.success() {
-        anyhow::bail!("Packaging failed");
+    headers.extend(extra_headers);
+    for (name, value) in headers {
+        if let Ok(header) = tiny_http::Header::from_bytes(name.as_bytes(), value.as_bytes()) {
+            response.add_header(header);
+        }
     }
-    println!("    {} Installer created", style("✓").green());
-
-    println!();
-    println!("{}", style("Build complete!").green().bold());
-    println!("  Output: {}", app_dir.join("target/release").display());
-    println!();
+    response
+}
 
-    Ok(())
+/// HTTP server for `dev --mock-api`. The spec is re-read on every request, so stubs can be
+/// edited while the app runs; a spec that no longer parses keeps the last good one.
+struct MockApiServer {
+    url: String,
+    server: std::sync::Arc<tiny_http::Server>,
+    handle: Option<std::thread::JoinHandle<()>>,
 }
 
-fn run_bun_or_npm(dir: &Path, args: &[&str]) -> Result<std::process::ExitStatus> {
-    if Command::new("bun").arg("--version").output().is_ok() {
-        Command::new("bun")
-            .current_dir(dir)
-            .args(args)
-            .status()
-            .context("Failed to run bun")
-    } else {
-        Command::new("npm")
-            .current_dir(dir)
-            .args(args)
-            .status()
-            .context("Failed to run npm")
+impl MockApiServer {
+    fn start(spec_path: &Path, port: u16) -> Result<Self> {
+        let mut routes = read_mock_spec(spec_path)?;
+        let server = tiny_http::Server::http(("127.0.0.1", port))
+            .map_err(|e| anyhow::anyhow!("Failed to start the mock API on port {}: {}", port, e))?;
+        let server = std::sync::Arc::new(server);
+        let spec_path = spec_path.to_path_buf();
+        let handle = std::thread::spawn({
+            let server = server.clone();
+            move || {
+                for request in server.incoming_requests() {
+                    if let Ok(latest) = read_mock_spec(&spec_path) {
+                        routes = latest;
+                    }
+                    let method = request.method().as_str().to_string();
+                    let response = mock_response(&routes, &method, request.url());
+                    println!("    {} {} {} {}", style("mock").dim(), method, request.url(), style(response.status_code().0).dim());
+                    let _ = request.respond(response);
+                }
+            }
+        });
+        Ok(Self { url: format!("http://127.0.0.1:{}", port), server, handle: Some(handle) })
     }
 }
 
-fn interactive_build_plugin() -> Result<()> {
-    let theme = ColorfulTheme::default();
-    let plugins_dir = get_plugins_dir()?;
+impl Drop for MockApiServer {
+    fn drop(&mut self) {
+        self.server.unblock();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
 
-    // Get list of plugin directories
-    let mut plugins: Vec<String> = Vec::new();
-    if plugins_dir.exists() {
-        for entry in fs::read_dir(&plugins_dir)? {
-            let entry = entry?;
-            if entry.path().is_dir() {
-                plugins.push(entry.file_name().to_string_lossy().to_string());
-            }
+/// Write `webarcade.dev-config.json`: the project config with `excluded` plugins disabled,
+/// for `dev --exclude`. The app is pointed at it with WEBARCADE_CONFIG.
+fn write_dev_config(excluded: &[String]) -> Result<PathBuf> {
+    let config_path = get_config_path()?;
+    let content = fs::read_to_string(&config_path)
+        .with_context(|| format!("--exclude needs {}", config_path.display()))?;
+    let mut config: serde_json::Value = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", config_path.display()))?;
+
+    for plugin_id in excluded {
+        match config.get_mut("plugins").and_then(|p| p.get_mut(plugin_id.as_str())) {
+            Some(entry) => entry["enabled"] = serde_json::Value::Bool(false),
+            None => println!("    {} --exclude {}: no such plugin in {}", style("!").yellow(), plugin_id, config_path.display()),
+        }
+    }
+
+    let dev_config_path = config_path.with_file_name("webarcade.dev-config.json");
+    write_atomic(&dev_config_path, &serde_json::to_string_pretty(&config)?)?;
+    Ok(dev_config_path)
+}
+
+/// Start `node dev-server.js` for every plugin whose package.json or plugin.toml has a `devPort`.
+/// Servers are spawned in parallel; their output goes straight to the terminal.
+fn start_plugin_dev_servers() -> Result<Vec<KillOnDrop>> {
+    let plugins_dir = get_plugins_dir()?;
+    if !plugins_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut servers: Vec<(String, PathBuf, u64)> = Vec::new();
+    for entry in fs::read_dir(&plugins_dir)?.filter_map(|e| e.ok()) {
+        let plugin_dir = entry.path();
+        let package_json_port = fs::read_to_string(plugin_dir.join("package.json")).ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|package_json| package_json.get("devPort")?.as_u64());
+        let port = package_json_port.or_else(|| {
+            let manifest = read_plugin_manifest(&plugin_dir).ok()??;
+            manifest.get("devPort")?.as_integer().and_then(|p| u64::try_from(p).ok())
+        });
+        if let Some(port) = port {
+            if plugin_dir.join("dev-server.js").exists() {
+                servers.push((entry.file_name().to_string_lossy().to_string(), plugin_dir, port));
+            }
+        }
+    }
+    servers.sort();
+
+    let handles: Vec<_> = servers
+        .into_iter()
+        .map(|(plugin_id, plugin_dir, port)| {
+            std::thread::spawn(move || {
+                let child = Command::new("node")
+                    .current_dir(&plugin_dir)
+                    .arg("dev-server.js")
+                    .env("PORT", port.to_string())
+                    .spawn();
+                (plugin_id, port, child)
+            })
+        })
+        .collect();
+
+    let mut children = Vec::new();
+    for handle in handles {
+        let (plugin_id, port, child) = handle
+            .join()
+            .map_err(|_| anyhow::anyhow!("Plugin dev server thread panicked"))?;
+        match child {
+            Ok(child) => {
+                println!("    {} {} dev server on port {}", style("✓").green(), plugin_id, port);
+                children.push(KillOnDrop(child));
+            }
+            Err(e) => println!("    {} Failed to start {} dev server: {}", style("!").yellow(), plugin_id, e),
+        }
+    }
+
+    Ok(children)
+}
+
+/// Run `docker compose up -d` for every plugin with a docker-compose.yml, limited to the
+/// plugin.toml `services` when it lists them. Returns the directories to bring down on exit.
+fn start_plugin_services() -> Result<Vec<PathBuf>> {
+    let plugins_dir = get_plugins_dir()?;
+    if !plugins_dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut plugins: Vec<(String, PathBuf)> = fs::read_dir(&plugins_dir)?
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().join("docker-compose.yml").exists())
+        .map(|e| (e.file_name().to_string_lossy().to_string(), e.path()))
+        .collect();
+    plugins.sort();
+
+    let mut started = Vec::new();
+    for (plugin_id, plugin_dir) in plugins {
+        let services = read_plugin_services(&plugin_dir)?;
+        if services.as_ref().is_some_and(|s| s.is_empty()) {
+            println!("    {} {}: no services listed in plugin.toml, skipping", style("!").yellow(), plugin_id);
+            continue;
+        }
+
+        let output = Command::new("docker")
+            .current_dir(&plugin_dir)
+            .args(["compose", "up", "-d"])
+            .args(services.iter().flatten())
+            .output();
+        match output {
+            Ok(output) if output.status.success() => {
+                let names = services.map(|s| s.join(", ")).unwrap_or_else(|| "all services".to_string());
+                println!("    {} {} services started ({})", style("✓").green(), plugin_id, names);
+                started.push(plugin_dir);
+            }
+            Ok(output) => println!(
+                "    {} Failed to start {} services: {}",
+                style("✗").red(), plugin_id, String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            Err(e) => println!("    {} Failed to run docker compose for {}: {}", style("✗").red(), plugin_id, e),
+        }
+    }
+
+    Ok(started)
+}
+
+/// A child process `dev` started, killed when the guard goes out of scope
+struct KillOnDrop(std::process::Child);
+
+impl Drop for KillOnDrop {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+/// Directories whose compose services `dev --start-services` brought up, brought down on drop
+struct ComposeServices(Vec<PathBuf>);
+
+impl Drop for ComposeServices {
+    fn drop(&mut self) {
+        stop_plugin_services(&self.0);
+    }
+}
+
+/// Run `docker compose down` in each directory `start_plugin_services` started
+fn stop_plugin_services(dirs: &[PathBuf]) {
+    for dir in dirs {
+        let status = Command::new("docker")
+            .current_dir(dir)
+            .args(["compose", "down"])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+        if !status.is_ok_and(|s| s.success()) {
+            println!("  {} docker compose down failed in {}", style("!").yellow(), dir.display());
+        }
+    }
+}
+
+/// How often hot-reload watchers check plugin sources for changes
+const HOT_RELOAD_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Background watchers for `webarcade dev --hot-reload`.
+///
+/// Reload protocol: after a changed plugin is rebuilt and its DLL reinstalled, the CLI
+/// writes `<plugin-id>.reload` (containing the build timestamp) next to the DLL in
+/// `app/plugins/`. The app is expected to watch for these sentinels, unload and reload
+/// the named plugin, then delete the sentinel. Any sentinels left over are removed
+/// when `dev` exits.
+struct PluginHotReloader {
+    stop: std::sync::Arc<std::sync::atomic::AtomicBool>,
+    handles: Vec<std::thread::JoinHandle<()>>,
+    dist_plugins_dir: PathBuf,
+}
+
+impl PluginHotReloader {
+    /// Spawn one watcher thread per enabled backend plugin
+    fn start() -> Result<Self> {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::{Arc, Mutex};
+
+        let plugins_dir = get_plugins_dir()?;
+        let dist_plugins_dir = get_dist_plugins_dir()?;
+        let config = WebArcadeConfig::load_or_create(&get_config_path()?)?;
+
+        let mut plugin_ids: Vec<String> = config.plugins.iter()
+            .filter(|(id, entry)| entry.enabled && entry.has_backend && plugins_dir.join(id).is_dir())
+            .map(|(id, _)| id.clone())
+            .collect();
+        plugin_ids.sort();
+
+        let stop = Arc::new(AtomicBool::new(false));
+        // Builds share the cache and config files, so only one runs at a time
+        let build_lock = Arc::new(Mutex::new(()));
+        let mut handles = Vec::new();
+
+        for plugin_id in &plugin_ids {
+            let plugin_id = plugin_id.clone();
+            let plugin_dir = plugins_dir.join(&plugin_id);
+            let dist_plugins_dir = dist_plugins_dir.clone();
+            let stop = Arc::clone(&stop);
+            let build_lock = Arc::clone(&build_lock);
+
+            handles.push(std::thread::spawn(move || {
+                let mut last_hash = calculate_plugin_hash(&plugin_dir, &[], &HashedFlags::default()).ok();
+
+                while !stop.load(Ordering::Relaxed) {
+                    std::thread::sleep(HOT_RELOAD_POLL_INTERVAL);
+
+                    let hash = match calculate_plugin_hash(&plugin_dir, &[], &HashedFlags::default()) {
+                        Ok(h) => h,
+                        Err(_) => continue,
+                    };
+                    if last_hash.as_deref() == Some(hash.as_str()) {
+                        continue;
+                    }
+                    last_hash = Some(hash);
+
+                    println!("    {} {} changed, rebuilding...", style("↻").cyan(), plugin_id);
+                    let _guard = build_lock.lock().unwrap_or_else(|e| e.into_inner());
+                    match build_plugin_internal(&plugin_id, &BuildOptions::default()) {
+                        Ok(_) => {
+                            let timestamp = std::time::SystemTime::now()
+                                .duration_since(std::time::UNIX_EPOCH)
+                                .map(|d| d.as_secs())
+                                .unwrap_or(0);
+                            let sentinel = dist_plugins_dir.join(format!("{}.reload", plugin_id));
+                            match fs::write(&sentinel, timestamp.to_string()) {
+                                Ok(_) => println!("    {} {} rebuilt, reload signalled", style("✓").green(), plugin_id),
+                                Err(e) => println!("    {} {}: failed to write {}: {}", style("✗").red(), plugin_id, sentinel.display(), e),
+                            }
+                        }
+                        Err(e) => {
+                            println!("    {} {} failed to build: {}", style("✗").red(), plugin_id, e);
+                        }
+                    }
+                }
+            }));
+        }
+
+        if plugin_ids.is_empty() {
+            println!("    {} No enabled backend plugins to watch", style("!").yellow());
+        } else {
+            println!("    {} Watching {} backend plugin(s) for changes", style("✓").green(), plugin_ids.len());
+        }
+
+        Ok(Self { stop, handles, dist_plugins_dir })
+    }
+
+    /// Stop all watchers and remove leftover reload sentinels
+    fn stop(self) {
+        self.stop.store(true, std::sync::atomic::Ordering::Relaxed);
+        for handle in self.handles {
+            let _ = handle.join();
+        }
+
+        if let Ok(entries) = fs::read_dir(&self.dist_plugins_dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().map(|e| e == "reload").unwrap_or(false) {
+                    let _ = fs::remove_file(&path);
+                }
+            }
+        }
+    }
+}
+
+fn build_app(locked: bool, no_kill: bool) -> Result<()> {
+    let repo_root = get_repo_root()?;
+    let app_dir = repo_root.join("app");
+
+    println!();
+    if locked {
+        println!("{}", style("Building locked app (plugins embedded)...").cyan().bold());
+    } else {
+        println!("{}", style("Building production app...").cyan().bold());
+    }
+    println!();
+
+    // Kill any running app processes before building
+    if !no_kill {
+        kill_running_app_processes()?;
+    }
+
+    // Build production frontend
+    println!("  {} Building frontend (production)...", style("[1/3]").bold().dim());
+    let build_status = run_bun_or_npm(&repo_root, &["run", "build:prod"])?;
+
+    if !build_status.success() {
+        anyhow::bail!("Frontend build failed");
+    }
+    println!("    {} Frontend built", style("✓").green());
+
+    // Build Rust app
+    println!("  {} Building app...", style("[2/3]").bold().dim());
+    let cargo_args = if locked {
+        vec!["build", "--release", "--features", "locked-plugins"]
+    } else {
+        vec!["build", "--release"]
+    };
+
+    let status = Command::new("cargo")
+        .current_dir(&app_dir)
+        .args(&cargo_args)
+        .status()
+        .context("Failed to run cargo build")?;
+
+    if !status.success() {
+        anyhow::bail!("Cargo build failed");
+    }
+    println!("    {} App built", style("✓").green());
+
+    // Package with cargo-packager
+    println!("  {} Packaging installer...", style("[3/3]").bold().dim());
+    let status = Command::new("cargo")
+        .current_dir(&app_dir)
+        .args(["packager", "--release"])
+        .status()
+        .context("Failed to run cargo packager")?;
+
+    if !status.success() {
+        anyhow::bail!("Packaging failed");
+    }
+    println!("    {} Installer created", style("✓").green());
+
+    println!();
+    println!("{}", style("Build complete!").green().bold());
+    println!("  Output: {}", app_dir.join("target/release").display());
+    println!();
+
+    Ok(())
+}
+
+fn run_bun_or_npm(dir: &Path, args: &[&str]) -> Result<std::process::ExitStatus> {
+    if Command::new("bun").arg("--version").output().is_ok() {
+        Command::new("bun")
+            .current_dir(dir)
+            .args(args)
+            .status()
+            .context("Failed to run bun")
+    } else {
+        Command::new("npm")
+            .current_dir(dir)
+            .args(args)
+            .status()
+            .context("Failed to run npm")
+    }
+}
+
+fn interactive_build_plugin() -> Result<()> {
+    let theme = ColorfulTheme::default();
+    let plugins_dir = get_plugins_dir()?;
+
+    // Get list of plugin directories
+    let mut plugins: Vec<String> = Vec::new();
+    if plugins_dir.exists() {
+        for entry in fs::read_dir(&plugins_dir)? {
+            let entry = entry?;
+            if entry.path().is_dir() {
+                plugins.push(entry.file_name().to_string_lossy().to_string());
+            }
         }
     }
 
@@ -1586,28 +4918,52 @@ fn interactive_build_plugin() -> Result<()> {
         return Ok(());
     }
 
-    // Add "Build All" option
-    let mut options = vec!["🔨 Build All Plugins".to_string()];
+    // Add "Build All" option; plugins show their display name and match on their ID
+    let config = WebArcadeConfig::load_or_create(&get_config_path()?)?;
+    let mut options = vec![MenuItem::new("🔨 Build All Plugins").shortcut('a')];
     for plugin in &plugins {
-        options.push(format!("   {}", plugin));
+        let label = match config.plugins.get(plugin) {
+            Some(entry) if entry.name != *plugin => format!("   {} ({})", plugin, entry.name),
+            _ => format!("   {}", plugin),
+        };
+        options.push(MenuItem::new(label).keywords(plugin.as_str()));
     }
-    options.push("← Back".to_string());
+    options.push(MenuItem::new("← Back"));
 
-    let selection = Select::with_theme(&theme)
-        .with_prompt("Select a plugin to build")
-        .items(&options)
-        .default(0)
-        .interact()?;
+    let selection = select_menu("Select a plugin to build", &options)?;
 
     println!();
 
+    let selection = match selection {
+        Some(i) if i + 1 < options.len() => i,
+        _ => return Ok(()), // Back to menu
+    };
     if selection == 0 {
-        build_all_plugins(false, None)
-    } else if selection == options.len() - 1 {
-        Ok(()) // Back to menu
+        build_all_plugins(&BuildOptions::default())
     } else {
         let plugin_id = &plugins[selection - 1];
-        build_plugin(plugin_id, false, None)
+        let mut build_options = BuildOptions::default();
+
+        // Let the developer toggle the plugin's Cargo features before building
+        let available = available_plugin_features(&plugins_dir.join(plugin_id));
+        if !available.is_empty() {
+            let mut configured = configured_plugin_features(plugin_id)?;
+            configured.sort();
+            let defaults: Vec<bool> = available.iter().map(|f| configured.contains(f)).collect();
+            let chosen = MultiSelect::with_theme(&theme)
+                .with_prompt("Features to enable (space to toggle)")
+                .items(&available)
+                .defaults(&defaults)
+                .interact()?;
+            let selected: Vec<String> = chosen.into_iter().map(|i| available[i].clone()).collect();
+
+            // A different feature set produces a different binary, so skip the cache
+            build_options.force = selected != configured;
+            build_options.features = Some(selected);
+            println!();
+        }
+
+        build_plugin(plugin_id, &build_options)
     }
 }
 
@@ -1629,7 +4985,7 @@ fn interactive_create_plugin() -> Result<()> {
 
     let display_name: String = Input::with_theme(&theme)
         .with_prompt("Display name")
-        .default(plugin_id.split(|c| c == '-' || c == '_')
+        .default(plugin_id.split(['-', '_'])
             .map(|s| {
                 let mut chars = s.chars();
                 match chars.next() {
@@ -1646,6 +5002,26 @@ fn interactive_create_plugin() -> Result<()> {
         .default("WebArcade".to_string())
         .interact_text()?;
 
+    let mut licenses: Vec<&str> = LICENSE_TEXTS.iter().map(|(id, _, _)| *id).collect();
+    licenses.push("Other (enter manually)");
+    licenses.push("None");
+    let license_selection = Select::with_theme(&theme)
+        .with_prompt("License")
+        .items(&licenses)
+        .default(licenses.len() - 1)
+        .interact()?;
+    let license = match license_selection {
+        i if i < LICENSE_TEXTS.len() => Some(LICENSE_TEXTS[i].0.to_string()),
+        i if i == LICENSE_TEXTS.len() => Some(
+            Input::<String>::with_theme(&theme)
+                .with_prompt("SPDX license identifier")
+                .interact_text()?
+                .trim()
+                .to_string(),
+        ).filter(|l| !l.is_empty()),
+        _ => None,
+    };
+
     let plugin_types = vec![
         "Full-stack (frontend + Rust backend)",
         "Frontend-only (just JavaScript)",
@@ -1658,9 +5034,23 @@ fn interactive_create_plugin() -> Result<()> {
 
     let frontend_only = type_selection == 1;
 
+    let frameworks = ["solid", "react", "preact"];
+    let framework_selection = Select::with_theme(&theme)
+        .with_prompt("Frontend framework")
+        .items(&["SolidJS", "React", "Preact"])
+        .default(0)
+        .interact()?;
+
     println!();
 
-    create_plugin(&plugin_id, Some(display_name), Some(author), frontend_only)
+    create_plugin(&plugin_id, NewPluginOptions {
+        name: Some(display_name),
+        author: Some(author),
+        frontend_only,
+        framework: Some(frameworks[framework_selection].to_string()),
+        license,
+        ..Default::default()
+    })
 }
 
 fn interactive_install_plugin() -> Result<()> {
@@ -1682,11 +5072,32 @@ fn interactive_install_plugin() -> Result<()> {
 
     println!();
 
-    install_plugin(&repo, false)
+    install_plugin(&repo, false, None, None, true, true)
+}
+
+/// Repo root given with --project-dir, set once before any command runs
+static PROJECT_DIR: std::sync::OnceLock<PathBuf> = std::sync::OnceLock::new();
+
+/// Check and record the --project-dir override for `get_repo_root`
+fn set_project_dir(dir: Option<&Path>) -> Result<()> {
+    let Some(dir) = dir else {
+        return Ok(());
+    };
+    let dir = fs::canonicalize(dir).with_context(|| format!("--project-dir {} does not exist", dir.display()))?;
+    let has_plugins = dir.join("plugins_src").exists() || dir.join("plugins").exists();
+    if !has_plugins || !dir.join("app").exists() {
+        anyhow::bail!("--project-dir {} is not a WebArcade project (no plugins/ and app/ directories)", dir.display());
+    }
+    let _ = PROJECT_DIR.set(dir);
+    Ok(())
 }
 
 /// Get the repo root directory (where plugins and app folders are)
 fn get_repo_root() -> Result<PathBuf> {
+    if let Some(dir) = PROJECT_DIR.get() {
+        return Ok(dir.clone());
+    }
+
     let mut current = std::env::current_dir()?;
 
     // Check if we're already at repo root
@@ -1721,12 +5132,16 @@ fn get_repo_root() -> Result<PathBuf> {
 }
 
 fn get_plugins_dir() -> Result<PathBuf> {
-    let root = get_repo_root()?;
+    Ok(plugins_dir_in(&get_repo_root()?))
+}
+
+/// Plugin source directory within a project root
+fn plugins_dir_in(root: &Path) -> PathBuf {
     // Support both "plugins_src" (old) and "plugins" (new) naming conventions
     if root.join("plugins_src").exists() {
-        Ok(root.join("plugins_src"))
+        root.join("plugins_src")
     } else {
-        Ok(root.join("plugins"))
+        root.join("plugins")
     }
 }
 
@@ -1738,8 +5153,42 @@ fn get_dist_plugins_dir() -> Result<PathBuf> {
     Ok(get_repo_root()?.join("app").join("plugins"))
 }
 
-fn create_plugin(plugin_id: &str, name: Option<String>, author: Option<String>, frontend_only: bool) -> Result<()> {
+/// Options for scaffolding a new plugin
+#[derive(Default)]
+struct NewPluginOptions {
+    /// Display name (derived from the plugin ID when unset)
+    name: Option<String>,
+    author: Option<String>,
+    /// Skip the Rust backend
+    frontend_only: bool,
+    /// Environment variables to document in .env.example
+    env_keys: Vec<String>,
+    no_readme: bool,
+    /// Port for a dev-server.js sidecar started by `webarcade dev`
+    port: Option<u16>,
+    /// Panels only: no viewport.jsx, and an `/init` route instead of `/hello`
+    no_viewport: bool,
+    /// Scaffold docker-compose.yml and a plugin.toml `services` list
+    with_docker_compose: bool,
+    /// "solid", "react" or "preact"; SolidJS when unset
+    framework: Option<String>,
+    /// `rust-version` for the backend's Cargo.toml
+    msrv: Option<String>,
+    /// SPDX license for Cargo.toml, plugin.toml and LICENSE
+    license: Option<String>,
+}
+
+fn create_plugin(plugin_id: &str, options: NewPluginOptions) -> Result<()> {
     let plugins_dir = get_plugins_dir()?;
+    create_plugin_in(&plugins_dir, plugin_id, options)
+}
+
+/// Scaffold a plugin inside a specific plugins directory
+fn create_plugin_in(plugins_dir: &Path, plugin_id: &str, options: NewPluginOptions) -> Result<()> {
+    let NewPluginOptions { name, author, frontend_only, env_keys, no_readme, port, no_viewport, with_docker_compose, framework, msrv, license } = options;
+    let framework_name = framework.as_deref().unwrap_or("solid");
+    // React wants className; SolidJS and Preact take class
+    let class_attr = if framework_name == "react" { "className" } else { "class" };
     let plugin_dir = plugins_dir.join(plugin_id);
 
     // Validate plugin ID
@@ -1757,7 +5206,7 @@ fn create_plugin(plugin_id: &str, name: Option<String>, author: Option<String>,
     let display_name = name.unwrap_or_else(|| {
         // Convert plugin-id to "Plugin Id"
         plugin_id
-            .split(|c| c == '-' || c == '_')
+            .split(['-', '_'])
             .map(|s| {
                 let mut chars = s.chars();
                 match chars.next() {
@@ -1769,11 +5218,16 @@ fn create_plugin(plugin_id: &str, name: Option<String>, author: Option<String>,
             .join(" ")
     });
 
-    let author_name = author.unwrap_or_else(|| "WebArcade".to_string());
+    let author_name = author
+        .or_else(|| {
+            let config_path = plugins_dir.parent()?.join("webarcade.config.json");
+            WebArcadeConfig::load_or_create(&config_path).ok()?.default_author
+        })
+        .unwrap_or_else(|| "WebArcade".to_string());
 
     // Generate struct name from plugin_id (my-plugin -> MyPlugin)
     let struct_name = plugin_id
-        .split(|c| c == '-' || c == '_')
+        .split(['-', '_'])
         .map(|s| {
             let mut chars = s.chars();
             match chars.next() {
@@ -1787,7 +5241,14 @@ fn create_plugin(plugin_id: &str, name: Option<String>, author: Option<String>,
     println!("  Location: {}", plugin_dir.display());
     println!("  Name: {}", display_name);
     println!("  Author: {}", author_name);
-    println!("  Type: {}", if frontend_only { "frontend-only" } else { "full-stack" });
+    println!("  Framework: {}", framework_name);
+    println!("  Type: {}", if frontend_only {
+        "frontend-only"
+    } else if no_viewport {
+        "full-stack (panels only)"
+    } else {
+        "full-stack"
+    });
     println!();
 
     // Create index.jsx (always required)
@@ -1814,8 +5275,57 @@ export default plugin({{
             id: 'main',
             label: '{display_name}',
             component: () => (
-                <div class="flex items-center justify-center h-full">
-                    <h1 class="text-4xl font-bold">{display_name}</h1>
+                <div {class_attr}="flex items-center justify-center h-full">
+                    <h1 {class_attr}="text-4xl font-bold">{display_name}</h1>
+                </div>
+            ),
+        }});
+    }},
+
+    active(api) {{
+        console.log('[{display_name}] Activated');
+    }},
+
+    inactive(api) {{
+        console.log('[{display_name}] Deactivated');
+    }},
+
+    stop(api) {{
+        console.log('[{display_name}] Stopped');
+    }}
+}});
+"#)
+    } else if no_viewport {
+        format!(r#"import {{ plugin }} from 'webarcade/plugin';
+
+export default plugin({{
+    id: '{plugin_id}',
+    name: '{display_name}',
+    version: '1.0.0',
+    description: '{display_name} plugin',
+    author: '{author_name}',
+
+    start(api) {{
+        // Register left panel tab
+        api.add({{
+            panel: 'left',
+            id: 'explorer',
+            label: 'Explorer',
+            component: () => (
+                <div {class_attr}="p-4">
+                    <h2 {class_attr}="font-bold">{display_name}</h2>
+                </div>
+            ),
+        }});
+
+        // Register bottom panel tab
+        api.add({{
+            panel: 'bottom',
+            id: 'console',
+            label: 'Console',
+            component: () => (
+                <div {class_attr}="p-4 font-mono text-sm text-base-content/70">
+                    {display_name} ready
                 </div>
             ),
         }});
@@ -1894,43 +5404,31 @@ export default plugin({{
     fs::write(plugin_dir.join("index.jsx"), index_jsx)?;
     println!("  Created index.jsx");
 
-    if !frontend_only {
+    if !frontend_only && !no_viewport {
         // Create viewport.jsx
-        let viewport_jsx = format!(r#"import {{ createSignal, onMount }} from 'solid-js';
-import {{ api }} from 'webarcade/bridge';
+        let viewport_jsx = viewport_template(framework_name, plugin_id, &display_name);
+        fs::write(plugin_dir.join("viewport.jsx"), viewport_jsx)?;
+        println!("  Created viewport.jsx");
+    }
 
-export default function Viewport() {{
-    const [message, setMessage] = createSignal('Loading...');
-
-    onMount(async () => {{
-        try {{
-            const response = await api('{plugin_id}/hello');
-            const data = await response.json();
-            setMessage(data.message);
-        }} catch (error) {{
-            setMessage('Error: ' + error.message);
-        }}
-    }});
-
-    return (
-        <div class="p-4">
-            <h1 class="text-xl font-bold mb-4">{display_name}</h1>
-            <p class="text-base-content/70">{{message()}}</p>
-        </div>
-    );
-}}
-"#);
-        fs::write(plugin_dir.join("viewport.jsx"), viewport_jsx)?;
-        println!("  Created viewport.jsx");
+    if !frontend_only {
+        // Panels-only plugins get an /init stub instead of the viewport's /hello
+        let (route, handler) = if no_viewport { ("/init", "handle_init") } else { ("/hello", "handle_hello") };
 
         // Create Cargo.toml
+        let rust_version = msrv.map(|v| format!("rust-version = \"{}\"\n", v)).unwrap_or_default();
+        let license_line = license.as_ref().map(|l| format!("license = {}\n", toml_edit::Value::from(l.as_str()))).unwrap_or_default();
         let cargo_toml = format!(r#"[package]
 name = "{plugin_id}"
 version = "1.0.0"
 edition = "2021"
-
+{rust_version}{license_line}
 [routes]
-"GET /hello" = "handle_hello"
+"GET {route}" = "{handler}"
+
+# Build customization (read by webarcade build, not by cargo):
+# [build]
+# linker = "arm-linux-gnueabihf-gcc"  # cross-linker for --target, must be on PATH
 
 [profile.release]
 opt-level = "z"
@@ -1965,1880 +5463,7665 @@ impl Plugin for {struct_name} {{
         println!("  Created mod.rs");
 
         // Create router.rs
-        let router_rs = format!(r#"use api::{{HttpRequest, HttpResponse, json, json_response}};
+        let router_rs = if no_viewport {
+            r#"use api::{HttpRequest, HttpResponse, json, json_response};
+
+pub async fn handle_init(_req: HttpRequest) -> HttpResponse {
+    json_response(&json!({
+        "ok": true
+    }))
+}
+"#.to_string()
+        } else {
+            format!(r#"use api::{{HttpRequest, HttpResponse, json, json_response}};
 
 pub async fn handle_hello(_req: HttpRequest) -> HttpResponse {{
     json_response(&json!({{
         "message": "Hello from {display_name}!"
     }}))
 }}
-"#);
+"#)
+        };
         fs::write(plugin_dir.join("router.rs"), router_rs)?;
         println!("  Created router.rs");
     }
 
-    println!();
-    println!("Plugin created successfully!");
-    println!();
-    println!("Next steps:");
-    println!("  1. Edit the plugin files in: {}", plugin_dir.display());
-    println!("  2. Build with: bun run plugin:build {}", plugin_id);
-    println!("  3. Run the app: bun run dev");
-
-    Ok(())
-}
+    // Create CHANGELOG.md
+    fs::write(plugin_dir.join("CHANGELOG.md"), changelog_template("1.0.0", &today_iso_date()))?;
+    println!("  Created CHANGELOG.md");
 
-fn list_plugins() -> Result<()> {
-    let plugins_dir = get_plugins_dir()?;
+    // Create .env.example documenting required environment variables
+    if !env_keys.is_empty() {
+        let mut env_example = String::from("# Environment variables required by this plugin.\n# Copy to .env at the repo root and fill in the values.\n");
+        for key in &env_keys {
+            env_example.push_str(&format!("{}=\n", key.trim()));
+        }
+        fs::write(plugin_dir.join(".env.example"), env_example)?;
+        println!("  Created .env.example");
+    }
 
-    if !plugins_dir.exists() {
-        println!("No plugins directory found at: {}", plugins_dir.display());
-        return Ok(());
+    // Create package.json when a framework is chosen (as a peer dependency) or for a dev port
+    if framework.is_some() || port.is_some() {
+        let mut package_json = serde_json::json!({
+            "name": plugin_id,
+            "version": "1.0.0",
+            "private": true,
+        });
+        if let Some(port) = port {
+            package_json["devPort"] = serde_json::json!(port);
+            package_json["scripts"] = serde_json::json!({ "dev": "node dev-server.js" });
+        }
+        let (package, version) = match framework_name {
+            "react" => ("react", "^18.0.0"),
+            "preact" => ("preact", "^10.0.0"),
+            _ => ("solid-js", "^1.8.0"),
+        };
+        package_json["peerDependencies"] = serde_json::json!({ package: version });
+        fs::write(plugin_dir.join("package.json"), serde_json::to_string_pretty(&package_json)? + "\n")?;
+        println!("  Created package.json");
     }
 
-    println!("Plugins in {}:", plugins_dir.display());
-    println!();
+    // Create dev-server.js for a plugin with its own dev port
+    if let Some(port) = port {
 
-    let mut sources = Vec::new();
-    let mut compiled = Vec::new();
+        let dev_server_js = format!(r#"// Development sidecar for {display_name}.
+// Started automatically by `webarcade dev` (or run `npm run dev` in this directory).
+const http = require('http');
 
-    for entry in fs::read_dir(&plugins_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        let name = entry.file_name();
-        let name_str = name.to_string_lossy();
+const PORT = {port};
 
-        if path.is_dir() {
-            // Source directory
-            let has_backend = path.join("mod.rs").exists() || path.join("Cargo.toml").exists();
-            let has_frontend = path.join("index.jsx").exists() || path.join("index.js").exists();
+const server = http.createServer((req, res) => {{
+    res.writeHead(200, {{ 'Content-Type': 'application/json' }});
+    res.end(JSON.stringify({{ plugin: '{plugin_id}', path: req.url }}));
+}});
 
-            let type_str = match (has_backend, has_frontend) {
-                (true, true) => "full-stack",
-                (true, false) => "backend-only",
-                (false, true) => "frontend-only",
-                (false, false) => "empty",
-            };
+server.listen(PORT, () => {{
+    console.log('[{plugin_id}] Dev server listening on http://localhost:' + PORT);
+}});
+"#);
+        fs::write(plugin_dir.join("dev-server.js"), dev_server_js)?;
+        println!("  Created dev-server.js");
+    }
 
-            sources.push((name_str.to_string(), type_str));
-        } else if path.extension().map(|e| e == "dll" || e == "so" || e == "dylib").unwrap_or(false) {
-            // Compiled plugin
-            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
-            // Remove "lib" prefix on Linux/macOS
-            let plugin_name = stem.strip_prefix("lib").unwrap_or(&stem).to_string();
-            compiled.push(plugin_name);
-        }
+    // Create docker-compose.yml with service stubs, and plugin.toml to list the ones in use
+    let mut plugin_toml = String::new();
+    if let Some(ref license) = license {
+        plugin_toml.push_str(&format!("license = {}\n", toml_edit::Value::from(license.as_str())));
     }
+    if with_docker_compose {
+        fs::write(plugin_dir.join("docker-compose.yml"), docker_compose_template(plugin_id, &display_name))?;
+        println!("  Created docker-compose.yml");
 
-    if !sources.is_empty() {
-        println!("  Source (directories):");
-        for (name, type_str) in &sources {
-            let is_built = compiled.iter().any(|c| c == name);
-            let status = if is_built { "built" } else { "not built" };
-            println!("    {} ({}, {})", name, type_str, status);
+        if !plugin_toml.is_empty() {
+            plugin_toml.push('\n');
         }
+        plugin_toml.push_str(r#"# Services from docker-compose.yml this plugin needs during development.
+# `webarcade dev --start-services` starts exactly these, e.g. ["postgres", "redis"].
+services = []
+"#);
+    }
+    if !plugin_toml.is_empty() {
+        fs::write(plugin_dir.join("plugin.toml"), plugin_toml)?;
+        println!("  Created plugin.toml");
     }
 
-    if !compiled.is_empty() {
-        println!();
-        println!("  Compiled (.dll files):");
-        for name in &compiled {
-            println!("    {}", name);
+    // Create LICENSE from the embedded text, with this year and the author filled in
+    if let Some(ref license) = license {
+        match license_text(license, &today_iso_date()[..4], &author_name) {
+            Some(text) => {
+                fs::write(plugin_dir.join("LICENSE"), text)?;
+                println!("  Created LICENSE");
+            }
+            None => println!(
+                "  {} No bundled text for {}; add a LICENSE file yourself",
+                style("!").yellow(),
+                license
+            ),
         }
     }
 
-    if sources.is_empty() && compiled.is_empty() {
-        println!("  (no plugins found)");
+    // Create README.md
+    if !no_readme {
+        let routes = read_plugin_routes(&plugin_dir);
+        fs::write(plugin_dir.join("README.md"), readme_template(plugin_id, &display_name, &routes))?;
+        println!("  Created README.md");
     }
 
+    println!();
+    println!("Plugin created successfully!");
+    println!();
+    println!("Next steps:");
+    println!("  1. Edit the plugin files in: {}", plugin_dir.display());
+    println!("  2. Build with: bun run plugin:build {}", plugin_id);
+    println!("  3. Run the app: bun run dev");
+
     Ok(())
 }
 
-// ============================================================================
-// BUILD CACHE - Track plugin source changes to skip unnecessary rebuilds
-// ============================================================================
+/// License texts `new --license` writes to LICENSE, by SPDX identifier, with the copyright
+/// line whose year and holder get filled in. The GPL and MPL have none: their `<year>`
+/// placeholders are in the instructions, which are part of the license text.
+const LICENSE_TEXTS: &[(&str, &str, Option<&str>)] = &[
+    ("MIT", include_str!("licenses/MIT.txt"), Some("Copyright (c) <year> <copyright holders>")),
+    ("Apache-2.0", include_str!("licenses/Apache-2.0.txt"), Some("Copyright [yyyy] [name of copyright owner]")),
+    ("GPL-3.0-only", include_str!("licenses/GPL-3.0.txt"), None),
+    ("BSD-3-Clause", include_str!("licenses/BSD-3-Clause.txt"), Some("Copyright (c) <year>, <copyright holders>")),
+    ("MPL-2.0", include_str!("licenses/MPL-2.0.txt"), None),
+];
+
+/// Bundled license text for an SPDX identifier, with `year` and `holder` in its copyright
+/// line. The GPL's deprecated `GPL-3.0` and its `-or-later` variant share the `GPL-3.0-only` text.
+fn license_text(spdx: &str, year: &str, holder: &str) -> Option<String> {
+    let spdx = match spdx {
+        "GPL-3.0" | "GPL-3.0-or-later" => "GPL-3.0-only",
+        other => other,
+    };
+    let (_, text, copyright) = LICENSE_TEXTS.iter().find(|(id, _, _)| id.eq_ignore_ascii_case(spdx))?;
+    Some(match copyright {
+        Some(line) => {
+            let filled = line
+                .replace("<year>", year)
+                .replace("[yyyy]", year)
+                .replace("<copyright holders>", holder)
+                .replace("[name of copyright owner]", holder);
+            text.replacen(line, &filled, 1)
+        }
+        None => text.to_string(),
+    })
+}
 
-/// Cache entry for a single plugin
-#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
-struct PluginCacheEntry {
-    /// Hash of all source files
-    source_hash: String,
-    /// Timestamp of last successful build
-    built_at: u64,
+/// docker-compose.yml for `new --with-docker-compose`: every service is a commented-out stub
+fn docker_compose_template(plugin_id: &str, display_name: &str) -> String {
+    let prefix = plugin_id.replace('_', "-");
+    format!(r#"# Development services for {display_name}.
+# Uncomment the services the plugin needs and list their names in plugin.toml `services`;
+# `webarcade dev --start-services` runs `docker compose up -d` for them and `down` on exit.
+services:
+  # postgres:
+  #   image: postgres:16
+  #   container_name: {prefix}-postgres
+  #   environment:
+  #     POSTGRES_USER: {plugin_id}
+  #     POSTGRES_PASSWORD: {plugin_id}
+  #     POSTGRES_DB: {plugin_id}
+  #   ports:
+  #     - "5432:5432"
+  #   volumes:
+  #     - postgres-data:/var/lib/postgresql/data
+
+  # redis:
+  #   image: redis:7
+  #   container_name: {prefix}-redis
+  #   ports:
+  #     - "6379:6379"
+
+  # mock-http:
+  #   image: mockserver/mockserver:latest
+  #   container_name: {prefix}-mock-http
+  #   ports:
+  #     - "1080:1080"
+
+# volumes:
+#   postgres-data:
+"#)
 }
 
-/// Build cache stored in build/.build_cache.json
-#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
-struct BuildCache {
-    plugins: HashMap<String, PluginCacheEntry>,
+/// A plugin's `build.json`, the JSON alternative to the `[routes]`, `[hooks]` and `[postprocess]`
+/// tables in Cargo.toml
+#[derive(Debug, Default, Deserialize)]
+struct PluginBuildJson {
+    /// "METHOD /path" -> handler
+    #[serde(default)]
+    routes: serde_json::Map<String, serde_json::Value>,
+    #[serde(default)]
+    hooks: BuildHooks,
+    #[serde(default)]
+    postprocess: PostProcess,
+    /// Cargo features always enabled for this plugin
+    #[serde(default)]
+    features: Vec<String>,
 }
 
-impl BuildCache {
-    fn cache_path() -> Result<PathBuf> {
-        Ok(get_repo_root()?.join("build").join(".build_cache.json"))
-    }
+/// Shell commands run around a plugin build
+#[derive(Debug, Default, Clone, Deserialize)]
+struct BuildHooks {
+    #[serde(default, rename = "pre-build")]
+    pre_build: Vec<String>,
+    #[serde(default, rename = "post-build")]
+    post_build: Vec<String>,
+}
 
-    fn load() -> Result<Self> {
-        let path = Self::cache_path()?;
-        if path.exists() {
-            let content = fs::read_to_string(&path)?;
-            Ok(serde_json::from_str(&content).unwrap_or_default())
-        } else {
-            Ok(Self::default())
-        }
+/// Commands that rewrite build artifacts in place: `dll` gets the installed library, `js` the
+/// bundled frontend (before it is embedded or installed)
+#[derive(Debug, Default, Clone, Deserialize)]
+struct PostProcess {
+    dll: Option<String>,
+    js: Option<String>,
+}
+
+/// Parse `build.json` in a plugin directory, if there is one
+fn read_build_json(plugin_dir: &Path) -> Result<Option<PluginBuildJson>> {
+    let path = plugin_dir.join("build.json");
+    if !path.exists() {
+        return Ok(None);
     }
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let build_json = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    Ok(Some(build_json))
+}
 
-    fn save(&self) -> Result<()> {
-        let path = Self::cache_path()?;
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)?;
-        }
-        let content = serde_json::to_string_pretty(self)?;
-        fs::write(&path, content)?;
-        Ok(())
+/// A plugin's routes as ("METHOD /path", handler) pairs, from build.json or else Cargo.toml
+fn read_plugin_routes(plugin_dir: &Path) -> Vec<(String, String)> {
+    match read_build_json(plugin_dir) {
+        Ok(Some(build_json)) => build_json.routes.iter()
+            .filter_map(|(route, handler)| handler.as_str().map(|h| (route.clone(), h.to_string())))
+            .collect(),
+        _ => read_cargo_routes(plugin_dir),
     }
+}
 
-    fn get(&self, plugin_id: &str) -> Option<&PluginCacheEntry> {
-        self.plugins.get(plugin_id)
+/// A plugin's build hooks, from build.json or else Cargo.toml `[hooks]`
+fn read_build_hooks(plugin_dir: &Path) -> Result<BuildHooks> {
+    if let Some(build_json) = read_build_json(plugin_dir)? {
+        return Ok(build_json.hooks);
     }
+    let hooks = fs::read_to_string(plugin_dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|content| content.parse::<toml::Value>().ok())
+        .and_then(|doc| doc.get("hooks").cloned())
+        .map(|hooks| hooks.try_into::<BuildHooks>())
+        .transpose()
+        .context("Invalid [hooks] table in Cargo.toml")?;
+    Ok(hooks.unwrap_or_default())
+}
 
-    fn set(&mut self, plugin_id: &str, source_hash: String) {
-        let timestamp = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map(|d| d.as_secs())
-            .unwrap_or(0);
-        self.plugins.insert(plugin_id.to_string(), PluginCacheEntry {
-            source_hash,
-            built_at: timestamp,
-        });
+/// A plugin's artifact post-processors, from build.json or else Cargo.toml `[postprocess]`
+fn read_postprocess(plugin_dir: &Path) -> Result<PostProcess> {
+    if let Some(build_json) = read_build_json(plugin_dir)? {
+        return Ok(build_json.postprocess);
     }
+    let postprocess = fs::read_to_string(plugin_dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|content| content.parse::<toml::Value>().ok())
+        .and_then(|doc| doc.get("postprocess").cloned())
+        .map(|table| table.try_into::<PostProcess>())
+        .transpose()
+        .context("Invalid [postprocess] table in Cargo.toml")?;
+    Ok(postprocess.unwrap_or_default())
 }
 
-/// Calculate a hash of all source files in a plugin directory
-fn calculate_plugin_hash(plugin_dir: &Path) -> Result<String> {
-    let mut hasher = Sha256::new();
-    let mut files: Vec<PathBuf> = Vec::new();
+/// `[routes]` entries from a plugin's Cargo.toml as ("METHOD /path", handler) pairs
+fn read_cargo_routes(plugin_dir: &Path) -> Vec<(String, String)> {
+    fs::read_to_string(plugin_dir.join("Cargo.toml"))
+        .ok()
+        .and_then(|content| content.parse::<toml::Value>().ok())
+        .and_then(|doc| doc.get("routes").and_then(|r| r.as_table()).cloned())
+        .map(|table| table.iter()
+            .filter_map(|(route, handler)| handler.as_str().map(|h| (route.clone(), h.to_string())))
+            .collect())
+        .unwrap_or_default()
+}
 
-    // Collect all relevant source files
-    for entry in WalkDir::new(plugin_dir)
-        .into_iter()
-        .filter_map(|e| e.ok())
-    {
-        let path = entry.path();
-        if path.is_file() {
-            let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
-            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+/// viewport.jsx for a new plugin, fetching `/hello` with the framework's state and mount hooks
+fn viewport_template(framework: &str, plugin_id: &str, display_name: &str) -> String {
+    if framework == "solid" {
+        return format!(r#"import {{ createSignal, onMount }} from 'solid-js';
+import {{ api }} from 'webarcade/bridge';
 
-            // Include source files but skip build artifacts
-            let is_source = matches!(ext, "rs" | "jsx" | "js" | "ts" | "tsx" | "json" | "toml" | "css" | "scss");
-            let is_build_artifact = path.components().any(|c| {
-                let s = c.as_os_str().to_string_lossy();
-                s == "target" || s == "node_modules" || s == ".git"
-            });
+export default function Viewport() {{
+    const [message, setMessage] = createSignal('Loading...');
 
-            // Skip lock files as they shouldn't trigger rebuilds
-            let is_lock_file = name == "package-lock.json" || name == "bun.lockb" || name == "Cargo.lock";
+    onMount(async () => {{
+        try {{
+            const response = await api('{plugin_id}/hello');
+            const data = await response.json();
+            setMessage(data.message);
+        }} catch (error) {{
+            setMessage('Error: ' + error.message);
+        }}
+    }});
 
-            if is_source && !is_build_artifact && !is_lock_file {
-                files.push(path.to_path_buf());
-            }
-        }
+    return (
+        <div class="p-4">
+            <h1 class="text-xl font-bold mb-4">{display_name}</h1>
+            <p class="text-base-content/70">{{message()}}</p>
+        </div>
+    );
+}}
+"#);
     }
 
-    // Sort for consistent ordering
-    files.sort();
+    // React and Preact share the hooks API; only the import path and class attribute differ
+    let (hooks_module, class_attr) = if framework == "react" { ("react", "className") } else { ("preact/hooks", "class") };
+    format!(r#"import {{ useState, useEffect }} from '{hooks_module}';
+import {{ api }} from 'webarcade/bridge';
 
-    // Hash each file's path and content
-    for file in files {
-        // Include relative path in hash so file renames are detected
-        if let Ok(rel_path) = file.strip_prefix(plugin_dir) {
-            hasher.update(rel_path.to_string_lossy().as_bytes());
-        }
-        if let Ok(content) = fs::read(&file) {
-            hasher.update(&content);
-        }
-    }
+export default function Viewport() {{
+    const [message, setMessage] = useState('Loading...');
+
+    useEffect(() => {{
+        (async () => {{
+            try {{
+                const response = await api('{plugin_id}/hello');
+                const data = await response.json();
+                setMessage(data.message);
+            }} catch (error) {{
+                setMessage('Error: ' + error.message);
+            }}
+        }})();
+    }}, []);
 
-    let result = hasher.finalize();
-    Ok(format!("{:x}", result))
+    return (
+        <div {class_attr}="p-4">
+            <h1 {class_attr}="text-xl font-bold mb-4">{display_name}</h1>
+            <p {class_attr}="text-base-content/70">{{message}}</p>
+        </div>
+    );
+}}
+"#)
 }
 
-/// Check if a plugin needs to be rebuilt
-fn plugin_needs_rebuild(plugin_id: &str, plugin_dir: &Path, dist_plugins_dir: &Path) -> Result<bool> {
-    // Check if output file exists
-    let lib_name = if cfg!(target_os = "windows") {
-        format!("{}.dll", plugin_id)
-    } else if cfg!(target_os = "macos") {
-        format!("lib{}.dylib", plugin_id)
+/// Initial README.md contents for a newly created plugin
+fn readme_template(plugin_id: &str, display_name: &str, routes: &[(String, String)]) -> String {
+    let routes_section = if routes.is_empty() {
+        "This plugin has no backend routes.\n".to_string()
     } else {
-        format!("lib{}.so", plugin_id)
+        let mut table = String::from("| Method | Path | Handler |\n|--------|------|---------|\n");
+        for (route, handler) in routes {
+            let (method, path) = route.split_once(' ').unwrap_or((route.as_str(), ""));
+            table.push_str(&format!("| `{}` | `{}` | `{}` |\n", method, path, handler));
+        }
+        table
     };
 
-    let has_backend = plugin_dir.join("mod.rs").exists() && plugin_dir.join("Cargo.toml").exists();
-    let output_path = if has_backend {
-        dist_plugins_dir.join(&lib_name)
-    } else {
-        dist_plugins_dir.join(format!("{}.js", plugin_id))
-    };
+    format!(r#"# {display_name}
 
-    // If output doesn't exist, definitely need to build
-    if !output_path.exists() {
-        return Ok(true);
-    }
+{display_name} plugin for WebArcade.
 
-    // Check hash against cache
-    let cache = BuildCache::load()?;
-    let current_hash = calculate_plugin_hash(plugin_dir)?;
+## Installation
 
-    if let Some(entry) = cache.get(plugin_id) {
-        // Rebuild if hash changed
-        Ok(entry.source_hash != current_hash)
-    } else {
-        // No cache entry, need to build
-        Ok(true)
-    }
-}
+```bash
+webarcade install <user/repo>
+webarcade build {plugin_id}
+```
 
-/// Update the build cache after a successful build
-fn update_build_cache(plugin_id: &str, plugin_dir: &Path) -> Result<()> {
-    let mut cache = BuildCache::load()?;
-    let hash = calculate_plugin_hash(plugin_dir)?;
-    cache.set(plugin_id, hash);
-    cache.save()
+## Configuration
+
+| Option | Type | Default | Description |
+|--------|------|---------|-------------|
+| | | | |
+
+## Routes
+
+{routes_section}
+## Development
+
+```bash
+webarcade build {plugin_id}
+webarcade run
+```
+"#)
 }
 
-// ============================================================================
-// PROCESS MANAGEMENT - Kill running processes before building
-// ============================================================================
+/// Initial CHANGELOG.md contents for a newly created plugin
+fn changelog_template(version: &str, date: &str) -> String {
+    format!(r#"# Changelog
 
-/// Kill any running processes that might lock build artifacts
-fn kill_running_app_processes() -> Result<()> {
-    let repo_root = get_repo_root()?;
-    let app_dir = repo_root.join("app");
+All notable changes to this plugin will be documented in this file.
 
-    // Get the app name from Cargo.toml
-    let cargo_toml_path = app_dir.join("Cargo.toml");
-    let app_name = if cargo_toml_path.exists() {
-        let content = fs::read_to_string(&cargo_toml_path)?;
-        if let Ok(doc) = content.parse::<toml::Value>() {
-            doc.get("package")
-                .and_then(|p| p.get("name"))
-                .and_then(|n| n.as_str())
-                .unwrap_or("webarcade")
-                .to_string()
-        } else {
-            "webarcade".to_string()
-        }
-    } else {
-        "webarcade".to_string()
-    };
+## [{version}] - {date}
 
-    let mut sys = System::new();
-    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+### Added
 
-    let mut killed = Vec::new();
-    let exe_name = format!("{}.exe", app_name.to_lowercase());
-    let exe_name_no_ext = app_name.to_lowercase();
+- Initial plugin scaffold
 
-    // Also check for processes running from target directory
-    let target_release_dir = app_dir.join("target").join("release");
-    let target_debug_dir = app_dir.join("target").join("debug");
+### Changed
 
-    for (pid, process) in sys.processes() {
-        let name = process.name().to_string_lossy().to_lowercase();
-        let exe_path = process.exe().map(|p| p.to_path_buf());
+### Fixed
+"#)
+}
 
-        let mut should_kill = false;
+/// A route handler parsed from router.rs
+struct HandlerDoc {
+    description: String,
+    example_request: Option<String>,
+    example_response: Option<String>,
+}
 
-        // Check by process name
-        if name == exe_name || name == exe_name_no_ext {
-            should_kill = true;
-        }
+/// Length of the char literal (`'}'`, `'\''`, `'\u{7d}'`) starting `text`, None for a lifetime
+fn char_literal_len(text: &str) -> Option<usize> {
+    let mut chars = text.char_indices().skip(1);
+    let (_, first) = chars.next()?;
+    if first == '\\' {
+        // Escapes are short; the closing quote is the first one after the escaped char
+        let (escaped_at, escaped) = chars.next()?;
+        let rest = &text[escaped_at + escaped.len_utf8()..];
+        return rest.find('\'').filter(|&end| end <= 8).map(|end| escaped_at + escaped.len_utf8() + end + 1);
+    }
+    match chars.next()? {
+        (quote_at, '\'') => Some(quote_at + 1),
+        _ => None,
+    }
+}
 
-        // Check by executable path (more reliable)
-        if let Some(ref path) = exe_path {
-            let path_str = path.to_string_lossy().to_lowercase();
-            if path_str.contains(&app_name.to_lowercase()) {
-                // Check if it's running from our target directory
-                if path.starts_with(&target_release_dir) || path.starts_with(&target_debug_dir) {
-                    should_kill = true;
-                }
-                // Or if the exe name matches
-                if let Some(file_name) = path.file_name() {
-                    let file_name_str = file_name.to_string_lossy().to_lowercase();
-                    if file_name_str == exe_name || file_name_str == exe_name_no_ext {
-                        should_kill = true;
-                    }
-                }
+/// Index just past the delimiter that closes the one at `open_index`, skipping string and
+/// char literals
+fn find_closing(text: &str, open_index: usize, open: char, close: char) -> Option<usize> {
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut skip_to = 0;
+    for (i, c) in text[open_index..].char_indices() {
+        if i < skip_to {
+            continue;
+        }
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
             }
+            continue;
         }
-
-        if should_kill {
-            let display_name = exe_path
-                .as_ref()
-                .and_then(|p| p.file_name())
-                .map(|n| n.to_string_lossy().to_string())
-                .unwrap_or_else(|| name.clone());
-
-            if process.kill() {
-                killed.push(format!("{} (PID: {})", display_name, pid));
+        if c == '"' {
+            in_string = true;
+        } else if c == '\'' {
+            if let Some(len) = char_literal_len(&text[open_index + i..]) {
+                skip_to = i + len;
+            }
+        } else if c == open {
+            depth += 1;
+        } else if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(open_index + i + c.len_utf8());
             }
         }
     }
+    None
+}
 
-    if !killed.is_empty() {
-        println!("  {} Terminated running processes:", style("!").yellow());
-        for proc in &killed {
-            println!("    - {}", proc);
+/// Doc comments and `json!` examples for each handler function in router.rs.
+/// A doc line starting with `Request:` is used as the example request body.
+fn parse_router_docs(router_rs: &str) -> HashMap<String, HandlerDoc> {
+    let fn_re = regex::Regex::new(r"(?m)((?:^[ \t]*///.*\n)*)^[ \t]*(?:pub\s+)?async\s+fn\s+([A-Za-z_][A-Za-z0-9_]*)\s*\(").unwrap();
+    let compact = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    let mut docs = HashMap::new();
+    for caps in fn_re.captures_iter(router_rs) {
+        let mut description = Vec::new();
+        let mut example_request = None;
+        for line in caps[1].lines() {
+            let text = line.trim().trim_start_matches("///").trim();
+            if let Some(request) = text.strip_prefix("Request:") {
+                example_request = Some(request.trim().to_string());
+            } else if !text.is_empty() {
+                description.push(text);
+            }
         }
 
-        // Wait for processes to fully terminate and release file handles
-        // Windows can be slow to release handles, so we wait a bit longer
-        std::thread::sleep(std::time::Duration::from_millis(1000));
+        // The first json!(...) literal in the handler body is the example response
+        let fn_start = caps.get(0).map(|m| m.end()).unwrap_or(0);
+        let example_response = router_rs[fn_start..].find('{')
+            .and_then(|brace| {
+                let body_start = fn_start + brace;
+                let body_end = find_closing(router_rs, body_start, '{', '}')?;
+                let body = &router_rs[body_start..body_end];
+                let json_start = body.find("json!(")? + "json!".len();
+                let json_end = find_closing(body, json_start, '(', ')')?;
+                Some(compact(&body[json_start + 1..json_end - 1]))
+            });
 
-        // Refresh and verify processes are gone
-        sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
-        let still_running: Vec<_> = sys.processes()
-            .iter()
-            .filter(|(_, p)| {
-                let name = p.name().to_string_lossy().to_lowercase();
-                name == exe_name || name == exe_name_no_ext
-            })
-            .collect();
+        docs.insert(caps[2].to_string(), HandlerDoc {
+            description: description.join(" "),
+            example_request,
+            example_response,
+        });
+    }
+    docs
+}
 
-        if !still_running.is_empty() {
-            // Try one more time with SIGKILL equivalent
-            for (_, process) in still_running {
-                process.kill();
-            }
-            std::thread::sleep(std::time::Duration::from_millis(500));
-        }
+/// Generate API.md in the plugin directory from `[routes]` and router.rs doc comments
+fn write_api_docs(plugin_dir: &Path, plugin_id: &str) -> Result<()> {
+    let routes = read_plugin_routes(plugin_dir);
+    if routes.is_empty() {
+        return Ok(());
     }
+    let router_rs = fs::read_to_string(plugin_dir.join("router.rs")).unwrap_or_default();
+    let docs = parse_router_docs(&router_rs);
 
-    Ok(())
-}
+    let cell = |s: &str| s.replace('|', "\\|");
+    let code = |s: &Option<String>| match s {
+        Some(s) if !s.is_empty() => format!("`{}`", cell(s)),
+        _ => "—".to_string(),
+    };
 
-fn build_all_plugins(force: bool, target: Option<&str>) -> Result<()> {
-    let plugins_dir = get_plugins_dir()?;
-    let dist_plugins_dir = get_dist_plugins_dir()?;
+    let mut routes = routes;
+    routes.sort_by(|a, b| {
+        let (am, ap) = a.0.split_once(' ').unwrap_or((a.0.as_str(), ""));
+        let (bm, bp) = b.0.split_once(' ').unwrap_or((b.0.as_str(), ""));
+        (ap, am).cmp(&(bp, bm))
+    });
 
-    if !plugins_dir.exists() {
-        anyhow::bail!("Plugins directory not found: {}", plugins_dir.display());
+    let mut api_md = format!(
+        "# {} API\n\n<!-- Generated by webarcade build --emit-docs. Do not edit by hand. -->\n\n\
+         | Method | Path | Handler | Description | Example Request | Example Response |\n\
+         |--------|------|---------|-------------|-----------------|------------------|\n",
+        plugin_id
+    );
+    for (route, handler) in &routes {
+        let (method, path) = route.split_once(' ').unwrap_or((route.as_str(), ""));
+        let doc = docs.get(handler);
+        api_md.push_str(&format!(
+            "| `{}` | `{}` | `{}` | {} | {} | {} |\n",
+            method,
+            path,
+            handler,
+            doc.map(|d| cell(&d.description)).filter(|d| !d.is_empty()).unwrap_or_else(|| "—".to_string()),
+            code(&doc.and_then(|d| d.example_request.clone())),
+            code(&doc.and_then(|d| d.example_response.clone())),
+        ));
     }
 
-    let mut plugins = Vec::new();
-    for entry in fs::read_dir(&plugins_dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        // Only build source directories, not .dll files
-        if path.is_dir() {
-            plugins.push(entry.file_name().to_string_lossy().to_string());
-        }
-    }
+    fs::write(plugin_dir.join("API.md"), api_md)?;
+    Ok(())
+}
 
-    if plugins.is_empty() {
-        println!("No plugin source directories found in {}", plugins_dir.display());
-        return Ok(());
+/// Today's date (UTC) formatted as YYYY-MM-DD
+fn today_iso_date() -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    // Convert days since the Unix epoch to a civil date (Howard Hinnant's algorithm)
+    let days = (secs / 86_400) as i64 + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+/// Add an entry to a plugin's CHANGELOG.md under its current version
+fn changelog_add(plugin_id: &str, message: &str) -> Result<()> {
+    let plugin_dir = get_plugins_dir()?.join(plugin_id);
+    if !plugin_dir.exists() {
+        anyhow::bail!("Plugin '{}' not found at {}", plugin_id, plugin_dir.display());
     }
 
-    // Check which plugins need rebuilding
-    let mut to_build = Vec::new();
-    let mut skipped = Vec::new();
+    let info = PluginInfo::from_dir(&plugin_dir)?;
+    let changelog_path = plugin_dir.join("CHANGELOG.md");
+    let content = if changelog_path.exists() {
+        fs::read_to_string(&changelog_path)?
+    } else {
+        "# Changelog\n\nAll notable changes to this plugin will be documented in this file.\n".to_string()
+    };
 
-    for plugin_id in &plugins {
-        let plugin_dir = plugins_dir.join(plugin_id);
-        if force {
-            to_build.push(plugin_id.clone());
-        } else {
-            match plugin_needs_rebuild(plugin_id, &plugin_dir, &dist_plugins_dir) {
-                Ok(true) => to_build.push(plugin_id.clone()),
-                Ok(false) => skipped.push(plugin_id.clone()),
-                Err(_) => to_build.push(plugin_id.clone()), // Build on error
+    let entry = format!("- {}", message.trim());
+    let version_heading = format!("## [{}]", info.version);
+    let mut lines: Vec<String> = content.lines().map(String::from).collect();
+
+    if let Some(section_start) = lines.iter().position(|l| l.starts_with(&version_heading)) {
+        // Existing section for this version: add to the top of its "### Added" list
+        let section_end = lines.iter()
+            .skip(section_start + 1)
+            .position(|l| l.starts_with("## "))
+            .map(|i| section_start + 1 + i)
+            .unwrap_or(lines.len());
+
+        let added = lines[section_start..section_end].iter()
+            .position(|l| l.trim() == "### Added")
+            .map(|i| section_start + i);
+
+        match added {
+            Some(heading) => {
+                lines.insert(heading + 1, String::new());
+                lines.insert(heading + 2, entry);
+                // Drop the blank line that previously followed the heading
+                if lines.get(heading + 3).map(|l| l.trim().is_empty()).unwrap_or(false) {
+                    lines.remove(heading + 3);
+                }
+            }
+            None => {
+                lines.splice(section_start + 1..section_start + 1, [
+                    String::new(),
+                    "### Added".to_string(),
+                    String::new(),
+                    entry,
+                ]);
             }
         }
+    } else {
+        // New section for this version, placed above any older releases
+        let insert_at = lines.iter()
+            .position(|l| l.starts_with("## "))
+            .unwrap_or(lines.len());
+        let section = [
+            format!("{} - {}", version_heading, today_iso_date()),
+            String::new(),
+            "### Added".to_string(),
+            String::new(),
+            entry,
+            String::new(),
+        ];
+        if insert_at == lines.len() && lines.last().map(|l| !l.trim().is_empty()).unwrap_or(false) {
+            lines.push(String::new());
+        }
+        lines.splice(insert_at..insert_at, section);
     }
 
-    if to_build.is_empty() {
-        println!();
-        println!("  {} {}", style("✓").green().bold(), style("All plugins are up to date!").green());
-        println!();
-        return Ok(());
-    }
-
-    // Sort build order based on dependencies (dependencies first)
-    let config_path = get_config_path()?;
-    let config = WebArcadeConfig::load_or_create(&config_path)?;
-    let to_build = match config.get_build_order(&to_build) {
-        Ok(order) => order,
-        Err(e) => {
-            println!("  {} {}", style("⚠").yellow(), style(format!("Dependency resolution warning: {}", e)).yellow());
-            to_build // Fall back to original order
-        }
-    };
+    let mut updated = lines.join("\n");
+    updated.push('\n');
+    fs::write(&changelog_path, updated)?;
 
-    // Create progress display
-    let mut progress = BuildProgress::new(&to_build, &skipped);
-    progress.render();
+    println!("{} Added to {} v{}: {}",
+        style("✓").green(),
+        style(plugin_id).cyan(),
+        info.version,
+        message.trim()
+    );
 
-    // Set global progress for PluginBuilder to use
-    set_build_progress(Some(&mut progress));
+    Ok(())
+}
 
-    let mut errors: Vec<(String, String)> = Vec::new();
+fn show_plugin_info(plugin_id: &str, readme: bool) -> Result<()> {
+    let plugin_dir = get_plugins_dir()?.join(plugin_id);
+    if !plugin_dir.exists() {
+        anyhow::bail!("Plugin '{}' not found at {}", plugin_id, plugin_dir.display());
+    }
 
-    for plugin_id in &to_build {
-        progress.start_plugin(plugin_id);
+    let info = PluginInfo::from_dir(&plugin_dir)?;
+    let config = WebArcadeConfig::load_or_create(&get_config_path()?)?;
+    let readme_path = plugin_dir.join("README.md");
 
-        match build_plugin_internal(plugin_id, target) {
-            Ok(_) => {
-                progress.complete_plugin(plugin_id, true);
-            }
-            Err(e) => {
-                progress.complete_plugin(plugin_id, false);
-                errors.push((plugin_id.clone(), e.to_string()));
-            }
+    if readme {
+        if !readme_path.exists() {
+            anyhow::bail!("Plugin '{}' has no README.md", plugin_id);
         }
+        println!();
+        print_markdown(&fs::read_to_string(&readme_path)?);
+        println!();
+        return Ok(());
     }
 
-    // Clear global progress
-    set_build_progress(None);
-
-    progress.finish();
+    let plugin_type = match (info.has_backend, info.has_frontend) {
+        (true, true) => "full-stack",
+        (true, false) => "backend-only",
+        (false, true) => "frontend-only",
+        (false, false) => "unknown",
+    };
 
-    // Show errors at the end
-    if !errors.is_empty() {
-        println!("  {}", style("Errors:").red().bold());
-        for (plugin_id, error) in &errors {
-            println!("    {} {}: {}", style("✗").red(), plugin_id, style(error).dim());
-        }
-        println!();
-        anyhow::bail!("Some plugins failed to build");
+    println!();
+    println!("  {}  {}", style("▶").cyan().bold(), style(info.name.as_deref().unwrap_or(plugin_id)).cyan().bold());
+    println!("  {}", style("─".repeat(50)).dim());
+    println!("  ID:          {}", style(&info.id).cyan());
+    println!("  Version:     {}", style(&info.version).cyan());
+    println!("  Type:        {}", plugin_type);
+    if let Some(author) = &info.author {
+        println!("  Author:      {}", author);
+    }
+    if let Some(description) = &info.description {
+        println!("  Description: {}", description);
+    }
+    PluginLinks::read(&plugin_dir)?.print("  ");
+    if let Some(source) = config.plugins.get(plugin_id).and_then(|e| e.source.as_deref()) {
+        println!("  Source:      {}", source);
     }
 
-    // Recalculate priorities based on dependency graph and save
-    let mut config = WebArcadeConfig::load_or_create(&config_path)?;
-    if let Err(e) = config.recalculate_priorities() {
-        println!("  {} {}", style("⚠").yellow(), style(format!("Priority calculation warning: {}", e)).yellow());
+    let routes = read_cargo_routes(&plugin_dir);
+    if !routes.is_empty() {
+        println!("  Routes:");
+        for (route, handler) in &routes {
+            println!("    {} {}", route, style(format!("→ {}", handler)).dim());
+        }
     }
-    config.save(&config_path)?;
 
-    // Validate dependencies and warn about missing ones
-    let missing = config.validate_dependencies()?;
-    if !missing.is_empty() {
+    if readme_path.exists() {
         println!();
-        println!("  {} {}", style("⚠").yellow().bold(), style("Missing dependencies:").yellow());
-        for msg in &missing {
-            println!("    {} {}", style("→").dim(), msg);
-        }
+        println!("  {} README.md available (webarcade info {} --readme)", style("→").dim(), plugin_id);
     }
+    println!();
 
     Ok(())
 }
 
-fn build_plugin(plugin_id: &str, force: bool, target: Option<&str>) -> Result<()> {
-    let plugins_dir = get_plugins_dir()?;
-    let dist_plugins_dir = get_dist_plugins_dir()?;
-    let plugin_dir = plugins_dir.join(plugin_id);
+/// Print markdown to the terminal with basic formatting for headings, lists and code blocks
+fn print_markdown(content: &str) {
+    let mut in_code_block = false;
 
-    // Check if rebuild is needed (unless forced)
-    if !force {
-        match plugin_needs_rebuild(plugin_id, &plugin_dir, &dist_plugins_dir) {
-            Ok(false) => {
-                println!("{} Plugin '{}' is up to date (use -f to force rebuild)",
-                    style("→").dim(), plugin_id);
-                return Ok(());
+    for line in content.lines() {
+        if line.trim_start().starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            println!("    {}", style(line).yellow());
+        } else if let Some(heading) = line.strip_prefix("# ") {
+            println!("{}", style(heading).cyan().bold().underlined());
+        } else if let Some(heading) = line.strip_prefix("## ").or_else(|| line.strip_prefix("### ")) {
+            println!("{}", style(heading).cyan().bold());
+        } else if let Some(item) = line.trim_start().strip_prefix("- ").or_else(|| line.trim_start().strip_prefix("* ")) {
+            println!("  {} {}", style("•").dim(), item.replace('`', ""));
+        } else if line.trim_start().starts_with('|') {
+            // Skip table separator rows, keep the rest as-is
+            if !line.chars().all(|c| matches!(c, '|' | '-' | ':' | ' ')) {
+                println!("  {}", line.replace('`', ""));
             }
-            _ => {} // Build if needs rebuild or on error
+        } else {
+            println!("{}", line.replace('`', ""));
         }
     }
-
-    build_plugin_internal(plugin_id, target)?;
-
-    // Recalculate priorities after building
-    let config_path = get_config_path()?;
-    let mut config = WebArcadeConfig::load_or_create(&config_path)?;
-    config.recalculate_priorities()?;
-    config.save(&config_path)?;
-
-    Ok(())
 }
 
-fn build_plugin_internal(plugin_id: &str, target: Option<&str>) -> Result<()> {
-    let builder = PluginBuilder::new(plugin_id, target)?;
-    let build_info = builder.build()?;
-
-    // Update cache on successful build
+fn list_plugins(verbose: bool) -> Result<()> {
     let plugins_dir = get_plugins_dir()?;
-    let plugin_dir = plugins_dir.join(plugin_id);
-    update_build_cache(plugin_id, &plugin_dir)?;
 
-    // Update webarcade.config.json with plugin info
-    update_config_for_plugin(
-        plugin_id,
-        build_info.has_backend,
-        build_info.has_frontend,
-        build_info.routes,
-    )?;
+    if !plugins_dir.exists() {
+        println!("No plugins directory found at: {}", plugins_dir.display());
+        return Ok(());
+    }
 
-    Ok(())
-}
+    println!("Plugins in {}:", plugins_dir.display());
+    println!();
 
-// ============================================================================
-// Build Progress Display
-// ============================================================================
+    let config = WebArcadeConfig::load_or_create(&get_config_path()?)?;
+    let repo_root = get_repo_root()?;
+    let mut sources = Vec::new();
+    let mut compiled = Vec::new();
 
-#[derive(Clone, Copy, PartialEq)]
-enum PluginStatus {
-    Pending,
-    Building,
-    Success,
-    Failed,
-    Skipped,
-}
+    for entry in fs::read_dir(&plugins_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        let name = entry.file_name();
+        let name_str = name.to_string_lossy();
 
-#[derive(Clone)]
-struct PluginState {
-    id: String,
-    status: PluginStatus,
-}
-
-struct BuildProgress {
-    term: Term,
-    plugins: Vec<PluginState>,
-    current_plugin: Option<String>,
-    current_step: Option<String>,
-    spinner: ProgressBar,
-    // Cargo compilation progress
-    cargo_current: usize,
-    cargo_total: usize,
-    cargo_crate_name: Option<String>,
-}
-
-impl BuildProgress {
-    fn new(to_build: &[String], skipped: &[String]) -> Self {
-        let term = Term::stdout();
+        if path.is_dir() {
+            // Source directory
+            let has_backend = path.join("mod.rs").exists() || path.join("Cargo.toml").exists();
+            let has_frontend = path.join("index.jsx").exists() || path.join("index.js").exists();
 
-        // Create plugin states
-        let mut plugins: Vec<PluginState> = to_build
-            .iter()
-            .map(|id| PluginState {
-                id: id.clone(),
-                status: PluginStatus::Pending,
-            })
-            .collect();
+            let type_str = match (has_backend, has_frontend) {
+                (true, true) => "full-stack",
+                (true, false) => "backend-only",
+                (false, true) => "frontend-only",
+                (false, false) => "empty",
+            };
 
-        // Add skipped plugins
-        for id in skipped {
-            plugins.push(PluginState {
-                id: id.clone(),
-                status: PluginStatus::Skipped,
-            });
+            let submodule = plugin_submodule_path(&repo_root, &path).is_some();
+            sources.push((name_str.to_string(), type_str, submodule));
+        } else if path.extension().map(|e| e == "dll" || e == "so" || e == "dylib").unwrap_or(false) {
+            // Compiled plugin
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            // Remove "lib" prefix on Linux/macOS
+            let plugin_name = stem.strip_prefix("lib").unwrap_or(&stem).to_string();
+            compiled.push(plugin_name);
         }
+    }
 
-        // Sort plugins alphabetically for consistent display
-        plugins.sort_by(|a, b| a.id.cmp(&b.id));
-
-        // Create spinner for current action
-        let spinner = ProgressBar::new_spinner();
-        spinner.set_style(
-            ProgressStyle::default_spinner()
-                .template("  {spinner:.cyan} {msg}")
-                .unwrap()
-        );
-
-        Self {
-            term,
-            plugins,
-            current_plugin: None,
-            current_step: None,
-            spinner,
-            cargo_current: 0,
-            cargo_total: 0,
-            cargo_crate_name: None,
+    if !sources.is_empty() {
+        println!("  Source (directories):");
+        for (name, type_str, submodule) in &sources {
+            let is_built = compiled.iter().any(|c| c == name);
+            let status = if is_built { "built" } else { "not built" };
+            let mut tag = if *submodule { format!(" {}", style("[submodule]").cyan()) } else { String::new() };
+            let required = config.plugins.get(name).map(|e| required_cpu_features(&e.target_features)).unwrap_or_default();
+            for feature in required {
+                tag.push_str(&format!(" {}", style(format!("[{}]", feature)).magenta()));
+            }
+            match config.plugins.get(name).and_then(|e| e.source.as_deref()) {
+                Some(source) => println!("    {} ({}, {}){} {}", name, type_str, status, tag, style(source).dim()),
+                None => println!("    {} ({}, {}){}", name, type_str, status, tag),
+            }
+            if verbose {
+                let plugin_dir = plugins_dir.join(name);
+                if let Ok(info) = PluginInfo::from_dir(&plugin_dir) {
+                    println!("      Version:     {}", info.version);
+                    if let Some(description) = &info.description {
+                        println!("      Description: {}", description);
+                    }
+                }
+                PluginLinks::read(&plugin_dir)?.print("      ");
+            }
         }
     }
 
-    fn render(&self) {
-        // Hide cursor and clear screen completely
-        let _ = self.term.hide_cursor();
-        let _ = self.term.clear_screen();
-        let _ = self.term.move_cursor_to(0, 0);
-        // Also clear scrollback buffer on supported terminals
-        print!("\x1B[3J");
-        let _ = std::io::stdout().flush();
-
-        // Header
-        println!();
-        println!("  {}  {}", style("▶").cyan().bold(), style("Building Plugins").cyan().bold());
-        println!("  {}", style("─".repeat(50)).dim());
+    if !compiled.is_empty() {
         println!();
+        println!("  Compiled (.dll files):");
+        for name in &compiled {
+            println!("    {}", name);
+        }
+    }
 
-        // Plugin grid (3 columns)
-        let cols = 3;
-        let col_width = 18;
+    if sources.is_empty() && compiled.is_empty() {
+        println!("  (no plugins found)");
+    }
 
-        for (i, plugin) in self.plugins.iter().enumerate() {
-            if i % cols == 0 && i > 0 {
-                println!();
-            }
+    Ok(())
+}
 
-            let icon = match plugin.status {
-                PluginStatus::Pending => style("○").dim(),
-                PluginStatus::Building => style("●").cyan().bold(),
-                PluginStatus::Success => style("✓").green().bold(),
-                PluginStatus::Failed => style("✗").red().bold(),
-                PluginStatus::Skipped => style("◦").dim(),
-            };
+/// `list --tree`: each plugin's frontend entry, backend sources and routes
+fn list_plugins_tree() -> Result<()> {
+    let plugins_dir = get_plugins_dir()?;
 
-            let name = if plugin.id.len() > col_width - 4 {
-                format!("{}…", &plugin.id[..col_width - 5])
-            } else {
-                plugin.id.clone()
-            };
+    if !plugins_dir.exists() {
+        println!("No plugins directory found at: {}", plugins_dir.display());
+        return Ok(());
+    }
 
-            let name_styled = match plugin.status {
-                PluginStatus::Pending => style(format!("{:<width$}", name, width = col_width - 3)).dim(),
-                PluginStatus::Building => style(format!("{:<width$}", name, width = col_width - 3)).cyan(),
-                PluginStatus::Success => style(format!("{:<width$}", name, width = col_width - 3)).green(),
-                PluginStatus::Failed => style(format!("{:<width$}", name, width = col_width - 3)).red(),
-                PluginStatus::Skipped => style(format!("{:<width$}", name, width = col_width - 3)).dim(),
-            };
+    let config = WebArcadeConfig::load_or_create(&get_config_path()?)?;
+    let mut plugin_dirs: Vec<PathBuf> = fs::read_dir(&plugins_dir)?
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.is_dir())
+        .collect();
+    plugin_dirs.sort();
 
-            print!("  {} {}", icon, name_styled);
-        }
-        println!();
-        println!();
+    println!("Plugins in {}:", plugins_dir.display());
+    println!();
+    if plugin_dirs.is_empty() {
+        println!("  (no plugins found)");
+        return Ok(());
+    }
 
-        // Current action
-        if let (Some(plugin), Some(step)) = (&self.current_plugin, &self.current_step) {
-            println!("  {} {}: {}", style("→").cyan(), style(plugin).bold(), style(step).dim());
+    for plugin_dir in &plugin_dirs {
+        let plugin_id = plugin_dir.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let info = PluginInfo::from_dir(plugin_dir)?;
+        println!("  {} {}", style(&plugin_id).cyan().bold(), style(format!("v{}", info.version)).dim());
 
-            // Show cargo compilation progress if compiling
-            if step.contains("Compiling") && self.cargo_total > 0 {
-                let cargo_bar_width = 30;
-                let cargo_filled = if self.cargo_total > 0 {
-                    (self.cargo_current * cargo_bar_width) / self.cargo_total
-                } else { 0 };
-                let cargo_empty = cargo_bar_width - cargo_filled;
+        let mut children: Vec<String> = Vec::new();
 
-                let cargo_bar = format!("{}{}",
-                    style("=".repeat(cargo_filled)).cyan(),
-                    style(" ".repeat(cargo_empty)).dim()
-                );
+        if let Some(entry) = ["index.jsx", "index.js"].iter().find(|f| plugin_dir.join(f).exists()) {
+            let size = fs::metadata(plugin_dir.join(entry)).map(|m| m.len()).unwrap_or(0);
+            children.push(format!("Frontend: {} ({} KB)", entry, size.div_ceil(1024)));
+        }
 
-                let crate_display = self.cargo_crate_name.as_deref().unwrap_or("");
-                println!("    {} [{}] {}/{}: {}",
-                    style("Building").dim(),
-                    cargo_bar,
-                    self.cargo_current,
-                    self.cargo_total,
-                    style(crate_display).yellow()
-                );
-            }
+        let mut backend_files: Vec<String> = fs::read_dir(plugin_dir)?
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| p.is_file() && p.extension().map(|e| e == "rs").unwrap_or(false))
+            .map(|p| p.file_name().unwrap_or_default().to_string_lossy().to_string())
+            .collect();
+        // mod.rs is the entry point, so it goes first
+        backend_files.sort_by_key(|f| (f != "mod.rs", f.clone()));
+        if !backend_files.is_empty() {
+            children.push(format!("Backend: {}", backend_files.join(", ")));
         }
 
-        // Progress bar
-        let done = self.plugins.iter().filter(|p| p.status == PluginStatus::Success || p.status == PluginStatus::Failed).count();
-        let total = self.plugins.iter().filter(|p| p.status != PluginStatus::Skipped).count();
-        let skipped = self.plugins.iter().filter(|p| p.status == PluginStatus::Skipped).count();
+        // Routes recorded by the last build, or the [routes] table for plugins not built yet
+        let routes: Vec<String> = match config.plugins.get(&plugin_id).filter(|e| !e.routes.is_empty()) {
+            Some(entry) => entry.routes.iter()
+                .map(|route| format!(
+                    "{} {} ({})",
+                    route["method"].as_str().unwrap_or("?"),
+                    route["path"].as_str().unwrap_or("?"),
+                    route["handler"].as_str().unwrap_or("?")
+                ))
+                .collect(),
+            None => read_plugin_routes(plugin_dir).into_iter()
+                .map(|(route, handler)| format!("{} ({})", route, handler))
+                .collect(),
+        };
+        if !routes.is_empty() {
+            children.push(format!("Routes: {}", routes.join(", ")));
+        }
 
+        if children.is_empty() {
+            children.push(style("(empty)").dim().to_string());
+        }
+        for (i, child) in children.iter().enumerate() {
+            let branch = if i + 1 == children.len() { "└──" } else { "├──" };
+            println!("  {} {}", style(branch).dim(), child);
+        }
         println!();
-        let bar_width = 40;
-        let filled = if total > 0 { (done * bar_width) / total } else { 0 };
-        let empty = bar_width - filled;
+    }
 
-        let bar = format!("{}{}",
-            style("━".repeat(filled)).cyan(),
-            style("─".repeat(empty)).dim()
-        );
+    Ok(())
+}
 
-        let percent = if total > 0 { (done * 100) / total } else { 0 };
-        let progress_text = if skipped > 0 {
-            format!("{}% ({}/{}, {} skipped)", percent, done, total, skipped)
-        } else {
-            format!("{}% ({}/{})", percent, done, total)
-        };
+/// GitHub API quota from a response's X-RateLimit-* headers
+#[derive(Debug)]
+struct RateLimit {
+    remaining: u64,
+    limit: u64,
+    /// Unix time the quota resets
+    reset: u64,
+}
 
-        println!("  {} {}", bar, style(progress_text).dim());
-        println!();
+impl RateLimit {
+    fn from_response(response: &ureq::Response) -> Option<Self> {
+        let header = |name: &str| response.header(name).and_then(|v| v.trim().parse().ok());
+        Some(RateLimit {
+            remaining: header("X-RateLimit-Remaining")?,
+            limit: header("X-RateLimit-Limit")?,
+            reset: header("X-RateLimit-Reset")?,
+        })
     }
+}
 
-    fn start_plugin(&mut self, plugin_id: &str) {
-        if let Some(plugin) = self.plugins.iter_mut().find(|p| p.id == plugin_id) {
-            plugin.status = PluginStatus::Building;
-        }
-        self.current_plugin = Some(plugin_id.to_string());
-        self.current_step = Some("Starting...".to_string());
-        self.render();
-    }
+/// GitHub answered 403 because the API quota is used up
+#[derive(Debug)]
+struct RateLimitExceeded(Option<RateLimit>);
 
-    fn set_step(&mut self, plugin_id: &str, step: &str) {
-        self.current_plugin = Some(plugin_id.to_string());
-        self.current_step = Some(step.to_string());
-        // Reset cargo progress when step changes (unless it's still compiling)
-        if !step.contains("Compiling") {
-            self.cargo_current = 0;
-            self.cargo_total = 0;
-            self.cargo_crate_name = None;
-        }
-        self.render();
+impl std::fmt::Display for RateLimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "GitHub API rate limit exceeded")
     }
+}
 
-    fn update_cargo_progress(&mut self, current: usize, total: usize, crate_name: Option<String>) {
-        self.cargo_current = current;
-        self.cargo_total = total;
-        self.cargo_crate_name = crate_name;
-        self.render();
-    }
+impl std::error::Error for RateLimitExceeded {}
 
-    fn complete_plugin(&mut self, plugin_id: &str, success: bool) {
-        if let Some(plugin) = self.plugins.iter_mut().find(|p| p.id == plugin_id) {
-            plugin.status = if success { PluginStatus::Success } else { PluginStatus::Failed };
+/// Version of `repo`'s latest GitHub release (its tag without a leading `v`), or None when
+/// the repo has no releases, plus the API quota the response reported
+fn latest_release_version(repo: &str) -> Result<(Option<String>, Option<RateLimit>)> {
+    let url = format!("https://api.github.com/repos/{}/releases/latest", repo);
+    match github_api_get(&url).call() {
+        Ok(response) => {
+            let rate_limit = RateLimit::from_response(&response);
+            let release: serde_json::Value = serde_json::from_str(&response.into_string()?)
+                .context("Failed to parse the GitHub release")?;
+            let tag = release["tag_name"].as_str().context("GitHub release has no tag")?;
+            Ok((Some(tag.strip_prefix('v').unwrap_or(tag).to_string()), rate_limit))
         }
-        self.current_plugin = None;
-        self.current_step = None;
-        self.render();
+        Err(ureq::Error::Status(404, response)) => Ok((None, RateLimit::from_response(&response))),
+        Err(ureq::Error::Status(403, response)) if response.header("X-RateLimit-Remaining") == Some("0") => {
+            Err(RateLimitExceeded(RateLimit::from_response(&response)).into())
+        }
+        Err(e) => Err(e.into()),
     }
+}
 
-    fn finish(&self) {
-        self.spinner.finish_and_clear();
+/// Compare plugins installed from GitHub against the latest version on their default branch,
+/// or with `via_api` against their latest GitHub release
+fn list_outdated_plugins(check_only: bool, via_api: bool) -> Result<()> {
+    let plugins_dir = get_plugins_dir()?;
+    let config = WebArcadeConfig::load_or_create(&get_config_path()?)?;
 
-        // Final render - show cursor and clear screen
-        let _ = self.term.show_cursor();
-        let _ = self.term.clear_screen();
-        let _ = self.term.move_cursor_to(0, 0);
-        // Clear scrollback buffer
-        print!("\x1B[3J");
-        let _ = std::io::stdout().flush();
+    let mut installed: Vec<(&String, &str)> = config.plugins.iter()
+        .filter_map(|(id, entry)| {
+            let repo = entry.source.as_deref()?.strip_prefix("github:")?;
+            // Drop the pinned @sha; we want the newest version
+            Some((id, repo.split('@').next().unwrap_or(repo)))
+        })
+        .collect();
+    installed.sort();
 
+    if installed.is_empty() {
+        println!("No plugins installed from GitHub.");
+        return Ok(());
+    }
+
+    println!();
+    println!("  {}  {}", style("▶").cyan().bold(), style("Checking for plugin updates").cyan().bold());
+    println!("  {}", style("─".repeat(50)).dim());
+    println!();
+    if via_api && github_token().is_none() {
+        println!(
+            "  {} Unauthenticated GitHub API (60 requests/hour); set WEBARCADE_GITHUB_TOKEN for 5000",
+            style("→").dim()
+        );
         println!();
-        println!("  {}  {}", style("✓").green().bold(), style("Build Complete").green().bold());
-        println!("  {}", style("─".repeat(50)).dim());
-        println!();
+    }
 
-        // Final plugin grid
-        let cols = 3;
-        let col_width = 18;
+    let total = installed.len();
+    let mut rate_limit: Option<RateLimit> = None;
+    let mut stopped_early = false;
+    let mut outdated: Vec<(String, String, String)> = Vec::new();
+    for (checked, (plugin_id, repo)) in installed.into_iter().enumerate() {
+        // Leave some quota for other tools sharing the token or IP
+        if let Some(limit) = rate_limit.as_ref().filter(|l| l.remaining < 10) {
+            println!(
+                "  {} Rate limit nearly exhausted ({} left); only {}/{} plugins checked.",
+                style("!").yellow(),
+                limit.remaining,
+                checked,
+                total
+            );
+            stopped_early = true;
+            break;
+        }
 
-        for (i, plugin) in self.plugins.iter().enumerate() {
-            if i % cols == 0 && i > 0 {
-                println!();
+        let local_version = match PluginInfo::from_dir(&plugins_dir.join(plugin_id)) {
+            Ok(info) => info.version,
+            Err(_) => {
+                println!("  {} {}: not found in plugins/", style("!").yellow(), plugin_id);
+                continue;
             }
+        };
 
-            let icon = match plugin.status {
-                PluginStatus::Success => style("✓").green().bold(),
-                PluginStatus::Failed => style("✗").red().bold(),
-                PluginStatus::Skipped => style("◦").dim(),
-                _ => style("○").dim(),
-            };
-
-            let name = if plugin.id.len() > col_width - 4 {
-                format!("{}…", &plugin.id[..col_width - 5])
-            } else {
-                plugin.id.clone()
-            };
-
-            let name_styled = match plugin.status {
-                PluginStatus::Success => style(format!("{:<width$}", name, width = col_width - 3)).green(),
-                PluginStatus::Failed => style(format!("{:<width$}", name, width = col_width - 3)).red(),
-                PluginStatus::Skipped => style(format!("{:<width$}", name, width = col_width - 3)).dim(),
-                _ => style(format!("{:<width$}", name, width = col_width - 3)).dim(),
-            };
+        if via_api {
+            match latest_release_version(repo) {
+                Ok((version, limit)) => {
+                    rate_limit = limit.or(rate_limit);
+                    match version {
+                        Some(remote_version) if compare_versions(&remote_version, &local_version) == Some(std::cmp::Ordering::Greater) => {
+                            outdated.push((plugin_id.clone(), local_version, remote_version));
+                        }
+                        Some(_) => println!("  {} {} {}", style("✓").green(), plugin_id, style(&local_version).dim()),
+                        None => println!(
+                            "  {} {}: {} has no releases (check it with --outdated)",
+                            style("!").yellow(),
+                            plugin_id,
+                            repo
+                        ),
+                    }
+                }
+                Err(e) => match e.downcast::<RateLimitExceeded>() {
+                    Ok(RateLimitExceeded(limit)) => {
+                        rate_limit = limit.or(rate_limit);
+                        println!(
+                            "  {} Rate limit exhausted; only {}/{} plugins checked.",
+                            style("!").yellow(),
+                            checked,
+                            total
+                        );
+                        stopped_early = true;
+                        break;
+                    }
+                    Err(e) => println!("  {} {}: could not check {} ({})", style("!").yellow(), plugin_id, repo, scrub_token(&e.to_string())),
+                },
+            }
+            continue;
+        }
 
-            print!("  {} {}", icon, name_styled);
+        let temp_dir = std::env::temp_dir().join(format!("webarcade-outdated-{}", plugin_id));
+        if temp_dir.exists() {
+            fs::remove_dir_all(&temp_dir)?;
         }
+        let url = github_clone_url(repo);
+        let remote_version = clone_repo_at(&url, &temp_dir, None)
+            .and_then(|_| find_plugin_in_dir(&temp_dir, PLUGIN_SEARCH_DEPTH))
+            .and_then(|dir| PluginInfo::from_dir(&dir))
+            .map(|info| info.version);
+        let _ = fs::remove_dir_all(&temp_dir);
+
+        match remote_version {
+            Ok(remote_version) => {
+                if compare_versions(&remote_version, &local_version) == Some(std::cmp::Ordering::Greater) {
+                    outdated.push((plugin_id.clone(), local_version, remote_version));
+                } else {
+                    println!("  {} {} {}", style("✓").green(), plugin_id, style(&local_version).dim());
+                }
+            }
+            Err(e) => println!("  {} {}: could not check {} ({})", style("!").yellow(), plugin_id, repo, scrub_token(&e.to_string())),
+        }
+    }
+
+    if let Some(limit) = &rate_limit {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
         println!();
+        println!(
+            "  {} GitHub API: {}/{} requests left, resets in {} min",
+            style("→").dim(),
+            limit.remaining,
+            limit.limit,
+            limit.reset.saturating_sub(now).div_ceil(60)
+        );
+    }
+
+    println!();
+    if outdated.is_empty() {
+        let scope = if stopped_early { "All checked plugins are" } else { "All plugins are" };
+        println!("  {} {} up to date", style("✓").green(), scope);
         println!();
+        return Ok(());
+    }
 
-        // Summary
-        let success_count = self.plugins.iter().filter(|p| p.status == PluginStatus::Success).count();
-        let failed_count = self.plugins.iter().filter(|p| p.status == PluginStatus::Failed).count();
-        let skipped_count = self.plugins.iter().filter(|p| p.status == PluginStatus::Skipped).count();
+    println!("    {:<24} {:>12} {:>12}", style("Plugin").dim(), style("Current").dim(), style("Available").dim());
+    for (plugin_id, current, available) in &outdated {
+        println!("    {:<24} {:>12} {:>12}", plugin_id, style(current).red(), style(available).green());
+    }
+    println!();
+    println!("  Update with: webarcade install <user/repo> --force");
+    println!();
 
-        if failed_count > 0 {
-            println!("  {} built, {} failed{}",
-                style(success_count).green().bold(),
-                style(failed_count).red().bold(),
-                if skipped_count > 0 { format!(", {} skipped", skipped_count) } else { String::new() }
-            );
-        } else {
-            println!("  {} All {} plugins built successfully{}",
-                style("✓").green().bold(),
-                style(success_count).green().bold(),
-                if skipped_count > 0 { format!(" ({} skipped)", skipped_count) } else { String::new() }
-            );
-        }
-        println!();
+    if check_only {
+        anyhow::bail!("{} plugin(s) outdated", outdated.len());
     }
-}
 
-// Shared progress state for use in PluginBuilder
-thread_local! {
-    static BUILD_PROGRESS: std::cell::RefCell<Option<*mut BuildProgress>> = const { std::cell::RefCell::new(None) };
+    Ok(())
 }
 
-fn set_build_progress(progress: Option<&mut BuildProgress>) {
-    BUILD_PROGRESS.with(|p| {
-        *p.borrow_mut() = progress.map(|p| p as *mut BuildProgress);
-    });
+// ============================================================================
+// BUILD CACHE - Track plugin source changes to skip unnecessary rebuilds
+// ============================================================================
+
+/// Cache entry for a single plugin
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct PluginCacheEntry {
+    /// Hash of all source files
+    source_hash: String,
+    /// Timestamp of last successful build
+    built_at: u64,
+    /// Size of the compiled DLL/JS from the last successful build
+    #[serde(default)]
+    artifact_size_bytes: u64,
+    /// SHA-256 of the compiled DLL/JS, to compare rebuilds of the same sources (--reproducible)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    artifact_sha256: Option<String>,
 }
 
-fn with_build_progress<F>(f: F)
-where
-    F: FnOnce(&mut BuildProgress),
-{
-    BUILD_PROGRESS.with(|p| {
-        if let Some(ptr) = *p.borrow() {
-            // Safety: We ensure the pointer is valid during the build process
-            unsafe {
-                f(&mut *ptr);
-            }
-        }
-    });
+/// Namespace given with `build --cache-key-prefix` (or `config reset-cache --prefix`)
+static CACHE_KEY_PREFIX: std::sync::OnceLock<String> = std::sync::OnceLock::new();
+
+/// Record the cache namespace for `BuildCache::cache_path`
+fn set_cache_key_prefix(prefix: Option<String>) {
+    if let Some(prefix) = prefix {
+        let _ = CACHE_KEY_PREFIX.set(prefix);
+    }
 }
 
-/// Information about a completed plugin build
-struct PluginBuildInfo {
-    has_backend: bool,
-    has_frontend: bool,
-    routes: Vec<serde_json::Value>,
+/// Parse `--cache-key-prefix`; it becomes part of a file name
+fn parse_cache_key_prefix(value: &str) -> Result<String, String> {
+    if value.is_empty() || !value.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(format!("'{}' is not a valid cache prefix (use letters, digits, '-' and '_')", value));
+    }
+    Ok(value.to_string())
 }
 
-struct PluginBuilder {
-    plugin_id: String,
-    plugin_dir: PathBuf,
-    build_dir: PathBuf,
-    dist_plugins_dir: PathBuf,
-    repo_root: PathBuf,
-    target: Option<String>,
+/// Build cache stored in build/.build_cache.json, or build/.build_cache_<prefix>.json
+/// with --cache-key-prefix
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct BuildCache {
+    plugins: HashMap<String, PluginCacheEntry>,
 }
 
-impl PluginBuilder {
-    fn new(plugin_id: &str, target: Option<&str>) -> Result<Self> {
-        let repo_root = get_repo_root()?;
-        let plugins_dir = get_plugins_dir()?;
-        let plugin_dir = plugins_dir.join(plugin_id);
+impl BuildCache {
+    fn cache_path() -> Result<PathBuf> {
+        let file_name = match CACHE_KEY_PREFIX.get() {
+            Some(prefix) => format!(".build_cache_{}.json", prefix),
+            None => ".build_cache.json".to_string(),
+        };
+        Ok(get_repo_root()?.join("build").join(file_name))
+    }
 
-        if !plugin_dir.exists() {
-            anyhow::bail!("Plugin source not found: {}", plugin_dir.display());
-        }
+    /// Every cache file in build/, with its prefix (None for the default cache)
+    fn all_cache_paths() -> Result<Vec<(Option<String>, PathBuf)>> {
+        let build_dir = get_repo_root()?.join("build");
+        let mut caches: Vec<(Option<String>, PathBuf)> = fs::read_dir(&build_dir)
+            .map(|entries| entries.filter_map(|e| e.ok()).map(|e| e.path()).collect::<Vec<_>>())
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|path| {
+                let name = path.file_name()?.to_str()?;
+                if name == ".build_cache.json" {
+                    return Some((None, path));
+                }
+                let prefix = name.strip_prefix(".build_cache_")?.strip_suffix(".json")?;
+                Some((Some(prefix.to_string()), path))
+            })
+            .collect();
+        caches.sort();
+        Ok(caches)
+    }
 
-        if !plugin_dir.is_dir() {
-            anyhow::bail!("Plugin source must be a directory: {}", plugin_dir.display());
+    fn load() -> Result<Self> {
+        let path = Self::cache_path()?;
+        if path.exists() {
+            let content = fs::read_to_string(&path)?;
+            Ok(serde_json::from_str(&content).unwrap_or_default())
+        } else {
+            Ok(Self::default())
         }
+    }
 
-        let build_dir = get_build_dir()?.join(plugin_id);
-        fs::create_dir_all(&build_dir)?;
+    fn save(&self) -> Result<()> {
+        let path = Self::cache_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self)?;
+        write_atomic(&path, &content)
+    }
 
-        let dist_plugins_dir = get_dist_plugins_dir()?;
-        fs::create_dir_all(&dist_plugins_dir)?;
+    fn get(&self, plugin_id: &str) -> Option<&PluginCacheEntry> {
+        self.plugins.get(plugin_id)
+    }
 
-        Ok(Self {
-            plugin_id: plugin_id.to_string(),
-            plugin_dir,
-            build_dir,
-            dist_plugins_dir,
-            repo_root,
-            target: target.map(|s| s.to_string()),
-        })
+    fn set(&mut self, plugin_id: &str, source_hash: String, artifact_size_bytes: u64, artifact_sha256: Option<String>) {
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        self.plugins.insert(plugin_id.to_string(), PluginCacheEntry {
+            source_hash,
+            built_at: timestamp,
+            artifact_size_bytes,
+            artifact_sha256,
+        });
     }
+}
 
-    /// Get the native library filename for the target platform
-    /// Rust converts hyphens to underscores in crate/library names
-    fn lib_name(&self) -> String {
-        let crate_name = self.plugin_id.replace('-', "_");
-        let is_windows;
-        let is_macos;
-        if let Some(ref target) = self.target {
-            is_windows = target.contains("windows");
-            is_macos = target.contains("apple") || target.contains("darwin");
-        } else {
-            is_windows = cfg!(target_os = "windows");
-            is_macos = cfg!(target_os = "macos");
-        }
-        if is_windows {
-            format!("{}.dll", crate_name)
-        } else if is_macos {
-            format!("lib{}.dylib", crate_name)
-        } else {
-            format!("lib{}.so", crate_name)
-        }
+/// Parse `--target-feature +avx2,+sse4.1` into its entries; each must start with + or -
+fn parse_target_features(value: &str) -> Result<Vec<String>, String> {
+    let features: Vec<String> = value
+        .split(',')
+        .map(|f| f.trim().to_string())
+        .filter(|f| !f.is_empty())
+        .collect();
+    if features.is_empty() {
+        return Err("expected features like +avx2,+sse4.1".to_string());
+    }
+    if let Some(bad) = features.iter().find(|f| !f.starts_with('+') && !f.starts_with('-')) {
+        return Err(format!("'{}' must start with + (enable) or - (disable)", bad));
     }
+    Ok(features)
+}
 
-    fn build(&self) -> Result<PluginBuildInfo> {
-        let has_backend = self.plugin_dir.join("mod.rs").exists()
-            && self.plugin_dir.join("Cargo.toml").exists();
-        let has_frontend = self.plugin_dir.join("index.jsx").exists()
-            || self.plugin_dir.join("index.js").exists();
+/// Enabled target features as CPU feature names for messages and badges (+avx2 -> AVX2)
+fn required_cpu_features(target_features: &[String]) -> Vec<String> {
+    target_features
+        .iter()
+        .filter_map(|f| f.strip_prefix('+'))
+        .map(|f| f.to_uppercase())
+        .collect()
+}
 
-        // Check if plugin has routes (needs bridge feature)
-        let has_routes = self.has_routes();
+/// Calculate a hash of all source files in a plugin directory.
+/// `--define` constants are included for plugins with a frontend, since they change the bundle,
+/// and the other flags in `flags` for the side of the plugin they affect.
+fn calculate_plugin_hash(plugin_dir: &Path, defines: &[(String, String)], flags: &HashedFlags) -> Result<String> {
+    let mut hasher = Sha256::new();
+    let mut files: Vec<PathBuf> = Vec::new();
 
-        // Extract routes for config
-        let routes = self.extract_routes().unwrap_or_default();
+    // Collect all relevant source files
+    for entry in WalkDir::new(plugin_dir)
+        .into_iter()
+        .filter_map(|e| e.ok())
+    {
+        let path = entry.path();
+        if path.is_file() {
+            let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+            let name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
 
-        // Report step progress
-        let plugin_id = self.plugin_id.clone();
-        let report_step = |step: &str| {
-            with_build_progress(|p| p.set_step(&plugin_id, step));
-        };
+            // Include source files but skip build artifacts
+            let is_source = matches!(ext, "rs" | "jsx" | "js" | "ts" | "tsx" | "json" | "toml" | "css" | "scss");
+            let is_build_artifact = path.components().any(|c| {
+                let s = c.as_os_str().to_string_lossy();
+                s == "target" || s == "node_modules" || s == ".git"
+            });
 
-        report_step("Preparing...");
+            // Skip lock files as they shouldn't trigger rebuilds
+            let is_lock_file = name == "package-lock.json" || name == "bun.lockb" || name == "Cargo.lock";
 
-        // Clean build directory
-        if self.build_dir.exists() {
-            fs::remove_dir_all(&self.build_dir)?;
+            if is_source && !is_build_artifact && !is_lock_file {
+                files.push(path.to_path_buf());
+            }
         }
-        fs::create_dir_all(&self.build_dir)?;
+    }
 
-        // Build frontend first
-        if has_frontend {
-            report_step("Bundling frontend...");
-            self.bundle_frontend()?;
+    // Extra inputs a build.rs declares with cargo:rerun-if-changed (assets, schemas, ...)
+    for declared in build_script_inputs(plugin_dir) {
+        if declared.is_dir() {
+            files.extend(WalkDir::new(&declared).into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.path().is_file())
+                .map(|e| e.path().to_path_buf()));
+        } else if declared.is_file() {
+            files.push(declared);
         }
+    }
 
-        // Frontend-only plugins: output JS file to app/plugins
-        if !has_backend {
-            report_step("Installing JS...");
-            let js_name = format!("{}.js", self.plugin_id);
-            let src_plugin_js = self.build_dir.join("plugin.js");
-            let dest_plugin_js = self.dist_plugins_dir.join(&js_name);
-            if src_plugin_js.exists() {
-                fs::copy(&src_plugin_js, &dest_plugin_js)?;
-            }
-
-            // Clean up build directory
-            report_step("Cleaning up...");
-            self.cleanup_build_dir()?;
+    // Sort for consistent ordering
+    files.sort();
+    files.dedup();
 
-            return Ok(PluginBuildInfo {
-                has_backend: false,
-                has_frontend,
-                routes: routes.clone(),
-            });
+    // Hash each file's path and content
+    for file in files {
+        // Include relative path in hash so file renames are detected
+        if let Ok(rel_path) = file.strip_prefix(plugin_dir) {
+            hasher.update(rel_path.to_string_lossy().as_bytes());
         }
+        if let Ok(content) = fs::read(&file) {
+            hasher.update(&content);
+        }
+    }
 
-        // Backend plugins: build DLL with embedded frontend
-        let frontend_js = if has_frontend {
-            let plugin_js_path = self.build_dir.join("plugin.js");
-            if plugin_js_path.exists() {
-                fs::read_to_string(&plugin_js_path)?
-            } else {
-                String::new()
-            }
-        } else {
-            String::new()
-        };
-
-        // Create package.json / manifest
-        report_step("Creating manifest...");
-        let manifest = self.create_manifest()?;
+    let has_frontend = plugin_dir.join("index.jsx").exists() || plugin_dir.join("index.js").exists();
+    if has_frontend && !defines.is_empty() {
+        let mut sorted: Vec<&(String, String)> = defines.iter().collect();
+        sorted.sort();
+        for (key, value) in sorted {
+            hasher.update(format!("define:{}={}\0", key, value).as_bytes());
+        }
+    }
 
-        report_step("Setting up backend...");
-        self.setup_backend_build(&frontend_js, &manifest, has_routes)?;
+    if has_frontend && flags.code_split {
+        hasher.update(b"code-split");
+    }
 
-        report_step("Compiling DLL...");
-        self.compile_backend()?;
+    let has_backend = plugin_dir.join("Cargo.toml").exists();
+    if let Some(mode) = flags.symbols.filter(|_| has_backend) {
+        hasher.update(mode.hash_tag().as_bytes());
+    }
+    if flags.static_link && has_backend {
+        hasher.update(b"static");
+    }
+    if flags.aot && has_backend {
+        hasher.update(b"aot");
+    }
+    if flags.incremental && has_backend {
+        hasher.update(b"incremental");
+    }
+    if let Some(level) = flags.opt_level.as_ref().filter(|_| has_backend) {
+        hasher.update(format!("opt-level:{}\0", level).as_bytes());
+    }
+    if flags.min_size && has_backend {
+        hasher.update(b"min-size");
+    }
+    if flags.dual_target && has_backend {
+        hasher.update(b"dual-target");
+    }
+    if flags.reproducible && has_backend {
+        hasher.update(b"reproducible");
+    }
+    if let (Some(dsn), true) = (&flags.sentry_dsn, has_backend) {
+        hasher.update(b"sentry");
+        hasher.update(dsn.as_bytes());
+        hasher.update(flags.sentry_environment.as_deref().unwrap_or("").as_bytes());
+    }
+    if let Some(workload) = flags.pgo_workload.as_ref().filter(|_| has_backend) {
+        hasher.update(format!("pgo:{}\0", workload).as_bytes());
+    }
+    if !flags.target_features.is_empty() && has_backend {
+        hasher.update(format!("target-feature:{}\0", flags.target_features.join(",")).as_bytes());
+    }
 
-        // Copy final DLL to app/plugins
-        report_step("Installing DLL...");
-        self.install_dll()?;
+    let result = hasher.finalize();
+    Ok(format!("{:x}", result))
+}
 
-        // Clean up build directory
-        report_step("Cleaning up...");
-        self.cleanup_build_dir()?;
+/// Paths from `cargo:rerun-if-changed=` directives in a plugin's build.rs, resolved
+/// against the plugin directory. Directives built with format arguments are skipped.
+fn build_script_inputs(plugin_dir: &Path) -> Vec<PathBuf> {
+    let content = match fs::read_to_string(plugin_dir.join("build.rs")) {
+        Ok(c) => c,
+        Err(_) => return Vec::new(),
+    };
+    let re = regex::Regex::new(r#"cargo::?rerun-if-changed=([^"\n]+)""#).unwrap();
+    re.captures_iter(&content)
+        .map(|c| c[1].trim().to_string())
+        .filter(|p| !p.is_empty() && !p.contains('{'))
+        .map(|p| plugin_dir.join(p))
+        .collect()
+}
 
-        Ok(PluginBuildInfo {
-            has_backend: true,
-            has_frontend,
-            routes,
-        })
+/// File name of the library built from `stem` for `target`, or for the host when there is none
+fn library_file_name(stem: &str, target: Option<&str>) -> String {
+    let (is_windows, is_macos) = match target {
+        Some(target) if target.starts_with("wasm32") => return format!("{}.wasm", stem),
+        Some(target) => (target.contains("windows"), target.contains("apple") || target.contains("darwin")),
+        None => (cfg!(target_os = "windows"), cfg!(target_os = "macos")),
+    };
+    if is_windows {
+        format!("{}.dll", stem)
+    } else if is_macos {
+        format!("lib{}.dylib", stem)
+    } else {
+        format!("lib{}.so", stem)
     }
+}
 
-    /// Clean up the build directory after successful build
-    fn cleanup_build_dir(&self) -> Result<()> {
-        if self.build_dir.exists() {
-            fs::remove_dir_all(&self.build_dir)?;
-        }
+/// Check if a plugin needs to be rebuilt
+fn plugin_needs_rebuild(
+    plugin_id: &str,
+    plugin_dir: &Path,
+    dist_plugins_dir: &Path,
+    cache_key: &str,
+    target: Option<&str>,
+    defines: &[(String, String)],
+    flags: &HashedFlags,
+) -> Result<bool> {
+    // Check if output file exists
+    let lib_name = library_file_name(plugin_id, target);
 
-        // Also remove the parent build/ directory if it's empty
-        if let Some(parent) = self.build_dir.parent() {
-            if parent.exists() {
-                if let Ok(entries) = fs::read_dir(parent) {
-                    if entries.count() == 0 {
-                        let _ = fs::remove_dir(parent);
-                    }
-                }
-            }
-        }
+    let has_backend = plugin_dir.join("mod.rs").exists() && plugin_dir.join("Cargo.toml").exists();
+    let output_path = if has_backend {
+        dist_plugins_dir.join(&lib_name)
+    } else {
+        dist_plugins_dir.join(format!("{}.js", plugin_id))
+    };
 
-        Ok(())
+    // If output doesn't exist, restore it from the artifact cache or build
+    if !output_path.exists() {
+        let current_hash = calculate_plugin_hash(plugin_dir, defines, flags)?;
+        let entry_dir = artifact_cache_entry(plugin_id, &current_hash, target, cache_key)?;
+        return Ok(!restore_cached_artifact(plugin_id, &entry_dir, &output_path)?);
     }
 
-    /// Check if the plugin has routes defined in Cargo.toml
-    fn has_routes(&self) -> bool {
-        let cargo_toml_path = self.plugin_dir.join("Cargo.toml");
-        if !cargo_toml_path.exists() {
-            return false;
-        }
+    // Check hash against cache
+    let cache = BuildCache::load()?;
+    let current_hash = calculate_plugin_hash(plugin_dir, defines, flags)?;
 
-        if let Ok(content) = fs::read_to_string(&cargo_toml_path) {
-            if let Ok(cargo_toml) = content.parse::<toml::Value>() {
-                if let Some(routes_table) = cargo_toml.get("routes").and_then(|r| r.as_table()) {
-                    return !routes_table.is_empty();
-                }
+    if let Some(entry) = cache.get(cache_key) {
+        // Rebuild if hash changed
+        Ok(entry.source_hash != current_hash)
+    } else {
+        // No cache entry, need to build
+        Ok(true)
+    }
+}
+
+/// Update the build cache after a successful build and keep a copy of the artifact
+fn update_build_cache(
+    plugin_id: &str,
+    cache_key: &str,
+    target: Option<&str>,
+    plugin_dir: &Path,
+    defines: &[(String, String)],
+    flags: &HashedFlags,
+    artifact: &Path,
+) -> Result<()> {
+    let mut cache = BuildCache::load()?;
+    let hash = calculate_plugin_hash(plugin_dir, defines, flags)?;
+    // The artifact cache only saves later rebuilds; a full disk shouldn't fail this one
+    let stored = artifact_cache_entry(plugin_id, &hash, target, cache_key)
+        .and_then(|entry_dir| store_cached_artifact(plugin_id, &entry_dir, artifact));
+    if let Err(e) = stored {
+        report_notice(format!("Couldn't store '{}' in the artifact cache: {:#}", plugin_id, e));
+    }
+    let artifact_sha256 = fs::read(artifact).ok().map(|bytes| format!("{:x}", Sha256::digest(bytes)));
+
+    // A forced --reproducible rebuild of unchanged sources must give the same library
+    if flags.reproducible {
+        let previous = cache.get(cache_key).filter(|entry| entry.source_hash == hash);
+        if let (Some(previous), Some(current)) = (previous.and_then(|e| e.artifact_sha256.clone()), &artifact_sha256) {
+            if &previous != current {
+                let notice = format!(
+                    "'{}' is not reproducible: same sources as the last build, but SHA-256 {} instead of {}",
+                    plugin_id, &current[..12], &previous[..12.min(previous.len())]
+                );
+                report_notice(notice);
             }
         }
-        false
     }
 
-    fn setup_backend_build(&self, frontend_js: &str, manifest: &str, has_routes: bool) -> Result<()> {
-        let rust_build_dir = self.build_dir.join("rust_build");
-        fs::create_dir_all(&rust_build_dir)?;
-
-        // Copy Rust source files
-        self.copy_rust_files(&self.plugin_dir, &rust_build_dir)?;
-
-        // Generate Cargo.toml
-        // API dependency from crates.io with optional bridge feature (only if plugin has routes)
-        let api_dep = if has_routes {
-            r#"api = { package = "webarcade-api", version = "0.1", features = ["bridge"] }"#.to_string()
-        } else {
-            r#"api = { package = "webarcade-api", version = "0.1" }"#.to_string()
-        };
-
-        let plugin_cargo_toml = self.plugin_dir.join("Cargo.toml");
-        let cargo_toml = if plugin_cargo_toml.exists() {
-            let mut content = fs::read_to_string(&plugin_cargo_toml)?;
-
-            // Inject API dependency with appropriate features
-            let re = regex::Regex::new(r#"api\s*=\s*\{[^}]*\}"#)?;
-            content = if re.is_match(&content) {
-                re.replace(&content, &api_dep).to_string()
-            } else {
-                let deps_re = regex::Regex::new(r"(?m)^\[dependencies\]\s*$")?;
-                if let Some(mat) = deps_re.find(&content) {
-                    let insert_pos = mat.end();
-                    let mut new_content = content.clone();
-                    new_content.insert_str(insert_pos, &format!("\n{}", api_dep));
-                    new_content
-                } else {
-                    format!("{}\n[dependencies]\n{}\n", content, api_dep)
-                }
-            };
-
-            // Ensure [lib] section
-            let lib_section_re = regex::Regex::new(r"(?m)\n?\[lib\][^\[]*")?;
-            content = lib_section_re.replace(&content, "").to_string();
-
-            let package_re = regex::Regex::new(r"(?m)(\[package\][^\[]+)")?;
-            if let Some(mat) = package_re.find(&content) {
-                let insert_pos = mat.end();
-                content.insert_str(insert_pos, "\n[lib]\ncrate-type = [\"cdylib\"]\npath = \"lib.rs\"\n");
-            }
+    cache.set(cache_key, hash, fs::metadata(artifact).map(|m| m.len()).unwrap_or(0), artifact_sha256);
+    cache.save()
+}
 
-            content
-        } else {
-            format!(
-                r#"[package]
-name = "{}"
-version = "1.0.0"
-edition = "2021"
+/// Artifact cache entries unused for this long are pruned
+const ARTIFACT_CACHE_MAX_AGE: std::time::Duration = std::time::Duration::from_secs(30 * 24 * 60 * 60);
 
-[lib]
-crate-type = ["cdylib"]
-path = "lib.rs"
+/// `.webarcade/cache/`: compiled artifacts by plugin and source hash, kept outside build/
+/// so they survive `cargo clean` and deleted outputs
+fn artifact_cache_dir() -> Result<PathBuf> {
+    Ok(get_repo_root()?.join(".webarcade").join("cache"))
+}
 
-[dependencies]
-{}
+/// `.webarcade/cache/<plugin-id>/<key>/`, where the key covers the source hash, the target
+/// triple and the build's cache key (e.g. a custom --target-dir)
+fn artifact_cache_entry(plugin_id: &str, hash: &str, target: Option<&str>, cache_key: &str) -> Result<PathBuf> {
+    let triple = match target {
+        Some(target) => target.to_string(),
+        None => host_target_triple()?,
+    };
+    let key = Sha256::digest(format!("{}\0{}\0{}", hash, triple, cache_key).as_bytes());
+    Ok(artifact_cache_dir()?.join(plugin_id).join(format!("{:x}", key)))
+}
 
-[profile.release]
-opt-level = "z"
-lto = true
-codegen-units = 1
-strip = true
-"#,
-                self.plugin_id, api_dep
-            )
-        };
+/// Files a build installs for `artifact` besides itself, relative to the plugins directory:
+/// the AOT cache, the exports sidecar and the --dual-target wasm-bindgen module and glue.
+/// --code-split chunks live in `<plugin-id>/chunks/`.
+fn artifact_sidecars(plugin_id: &str, artifact: &Path) -> Vec<PathBuf> {
+    let mut sidecars = vec![
+        PathBuf::from(artifact.with_extension("cwasm").file_name().unwrap_or_default()),
+        PathBuf::from(format!("{}.exports.json", plugin_id)),
+        PathBuf::from(format!("{}_bg.wasm", plugin_id)),
+        PathBuf::from(format!("{}.js", plugin_id)),
+    ];
+    sidecars.retain(|sidecar| Some(sidecar.as_os_str()) != artifact.file_name());
+    sidecars
+}
 
-        fs::write(rust_build_dir.join("Cargo.toml"), cargo_toml)?;
+/// Copy a freshly built artifact with its sidecars and chunks to `entry_dir`, and prune old entries
+fn store_cached_artifact(plugin_id: &str, entry_dir: &Path, artifact: &Path) -> Result<()> {
+    let (Some(file_name), Some(dist_dir)) = (artifact.file_name(), artifact.parent()) else {
+        return Ok(());
+    };
+    if entry_dir.exists() {
+        fs::remove_dir_all(entry_dir)?;
+    }
+    fs::create_dir_all(entry_dir)?;
+    let mut outputs = vec![PathBuf::from(file_name)];
+    outputs.extend(artifact_sidecars(plugin_id, artifact));
+    let chunks_dir = dist_dir.join(plugin_id).join("chunks");
+    for chunk in WalkDir::new(&chunks_dir).into_iter().filter_map(|e| e.ok()).filter(|e| e.file_type().is_file()) {
+        outputs.push(chunk.path().strip_prefix(dist_dir)?.to_path_buf());
+    }
+    for output in outputs {
+        let src = dist_dir.join(&output);
+        if !src.is_file() {
+            continue;
+        }
+        let dest = entry_dir.join(&output);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(&src, &dest).with_context(|| format!("Failed to cache {}", src.display()))?;
+    }
+    prune_artifact_cache()
+}
 
-        // Create .cargo/config.toml
-        let cargo_config_dir = rust_build_dir.join(".cargo");
-        fs::create_dir_all(&cargo_config_dir)?;
-        let cargo_config = r#"[target.x86_64-pc-windows-msvc]
-rustflags = ["-C", "link-args=/FORCE:UNRESOLVED"]
+/// Restore `output_path`, its sidecars and chunks from the artifact cache entry `entry_dir`,
+/// removing installed sidecars the entry doesn't have. Returns false if the entry has no artifact.
+fn restore_cached_artifact(plugin_id: &str, entry_dir: &Path, output_path: &Path) -> Result<bool> {
+    let (Some(file_name), Some(dist_dir)) = (output_path.file_name(), output_path.parent()) else {
+        return Ok(false);
+    };
+    if !entry_dir.join(file_name).is_file() {
+        return Ok(false);
+    }
+    fs::create_dir_all(dist_dir)?;
+    for sidecar in artifact_sidecars(plugin_id, output_path) {
+        if !entry_dir.join(&sidecar).exists() && dist_dir.join(&sidecar).is_file() {
+            fs::remove_file(dist_dir.join(&sidecar))?;
+        }
+    }
+    let chunks_dir = dist_dir.join(plugin_id).join("chunks");
+    if chunks_dir.exists() {
+        fs::remove_dir_all(&chunks_dir)?;
+    }
+    for cached in WalkDir::new(entry_dir).into_iter().filter_map(|e| e.ok()).filter(|e| e.file_type().is_file()) {
+        let dest = dist_dir.join(cached.path().strip_prefix(entry_dir)?);
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(cached.path(), &dest)
+            .with_context(|| format!("Failed to restore {} from the artifact cache", dest.display()))?;
+    }
+    report_summary(plugin_id, &format!("Restored '{}' from the artifact cache", plugin_id));
+    Ok(true)
+}
 
-[target.x86_64-unknown-linux-gnu]
-rustflags = ["-C", "link-args=-Wl,--allow-shlib-undefined"]
+/// Remove artifact cache entries older than ARTIFACT_CACHE_MAX_AGE, and plugin directories left empty
+fn prune_artifact_cache() -> Result<()> {
+    let cache_dir = artifact_cache_dir()?;
+    let Ok(plugins) = fs::read_dir(&cache_dir) else {
+        return Ok(());
+    };
+    let now = std::time::SystemTime::now();
+    for plugin in plugins.filter_map(|e| e.ok()).filter(|e| e.path().is_dir()) {
+        for entry in fs::read_dir(plugin.path())?.filter_map(|e| e.ok()) {
+            let age = entry.metadata()
+                .and_then(|m| m.modified())
+                .ok()
+                .and_then(|modified| now.duration_since(modified).ok());
+            if age.map(|age| age > ARTIFACT_CACHE_MAX_AGE).unwrap_or(false) {
+                fs::remove_dir_all(entry.path())?;
+            }
+        }
+        let _ = fs::remove_dir(plugin.path()); // only succeeds once empty
+    }
+    Ok(())
+}
 
-[target.x86_64-apple-darwin]
-rustflags = ["-C", "link-args=-undefined dynamic_lookup"]
+/// Number of builds kept per plugin in the size history
+const SIZE_HISTORY_LEN: usize = 10;
 
-[target.aarch64-apple-darwin]
-rustflags = ["-C", "link-args=-undefined dynamic_lookup"]
-"#;
-        fs::write(cargo_config_dir.join("config.toml"), cargo_config)?;
+/// Artifact sizes from recent builds, stored in build/.size_history.json
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize)]
+struct SizeHistory {
+    plugins: HashMap<String, Vec<u64>>,
+}
 
-        // Generate lib.rs with embedded assets
-        self.create_lib_rs(&rust_build_dir, frontend_js, manifest, has_routes)?;
+impl SizeHistory {
+    fn history_path() -> Result<PathBuf> {
+        Ok(get_repo_root()?.join("build").join(".size_history.json"))
+    }
 
-        Ok(())
+    fn load() -> Result<Self> {
+        let path = Self::history_path()?;
+        if path.exists() {
+            let content = fs::read_to_string(&path)?;
+            Ok(serde_json::from_str(&content).unwrap_or_default())
+        } else {
+            Ok(Self::default())
+        }
     }
 
-    fn copy_rust_files(&self, src: &Path, dst: &Path) -> Result<()> {
-        let plugin_mod_dir = dst.join("plugin_mod");
-        fs::create_dir_all(&plugin_mod_dir)?;
+    fn save(&self) -> Result<()> {
+        let path = Self::history_path()?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content = serde_json::to_string_pretty(self)?;
+        write_atomic(&path, &content)
+    }
 
-        for entry in fs::read_dir(src)? {
-            let entry = entry?;
-            let path = entry.path();
-            let file_name = entry.file_name();
-            let file_name_str = file_name.to_string_lossy();
+    /// Append a build's artifact size, keeping only the most recent builds
+    fn record(&mut self, plugin_id: &str, size: u64) {
+        let sizes = self.plugins.entry(plugin_id.to_string()).or_default();
+        sizes.push(size);
+        if sizes.len() > SIZE_HISTORY_LEN {
+            sizes.drain(..sizes.len() - SIZE_HISTORY_LEN);
+        }
+    }
+}
 
-            if path.is_file() {
-                if let Some(ext) = path.extension() {
-                    if ext == "rs" {
-                        let dest_path = plugin_mod_dir.join(&file_name);
-                        let content = fs::read_to_string(&path)?;
+/// Render sizes as a unicode sparkline
+fn sparkline(values: &[u64]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let min = values.iter().copied().min().unwrap_or(0);
+    let max = values.iter().copied().max().unwrap_or(0);
+    values.iter()
+        .map(|&v| {
+            let index = ((v - min) * 7).checked_div(max - min).unwrap_or(0);
+            BARS[index as usize]
+        })
+        .collect()
+}
 
-                        let modified_content = if file_name_str == "mod.rs" {
-                            if content.contains("pub mod router;") {
-                                content
-                            } else {
-                                content.replace("mod router;", "pub mod router;")
-                            }
-                        } else if file_name_str == "router.rs" {
-                            let re = regex::Regex::new(r"(?m)^async fn ([a-zA-Z_][a-zA-Z0-9_]*)\(([^)]*)\) -> HttpResponse")?;
-                            re.replace_all(&content, "pub async fn $1($2) -> HttpResponse").to_string()
-                        } else {
-                            content
-                        };
+/// Print a table of artifact sizes with the change since the previous build
+/// `linkers` are the --fast-linker linkers the builds actually used
+fn print_size_report(built: &[(String, u64)], linkers: &[&str]) -> Result<()> {
+    let history = SizeHistory::load()?;
 
-                        fs::write(&dest_path, modified_content)?;
-                    }
+    println!("  {}", style("Artifact sizes:").bold());
+    if !linkers.is_empty() {
+        println!("    {}", style(format!("Linked with {}", linkers.join(", "))).dim());
+    }
+    println!("    {:<20} {:>12} {:>9}  {}", style("Plugin").dim(), style("Bytes").dim(), style("Change").dim(), style("Last 5").dim());
+
+    for (plugin_id, size) in built {
+        let sizes = history.plugins.get(plugin_id).cloned().unwrap_or_default();
+        let change = match sizes.len().checked_sub(2).map(|i| sizes[i]) {
+            Some(previous) if previous > 0 => {
+                let percent = (*size as f64 - previous as f64) / previous as f64 * 100.0;
+                let text = format!("{:+.1}%", percent);
+                if percent > 0.0 {
+                    style(text).red()
+                } else if percent < 0.0 {
+                    style(text).green()
+                } else {
+                    style(text).dim()
                 }
             }
-        }
+            _ => style("new".to_string()).dim(),
+        };
+        let recent = &sizes[sizes.len().saturating_sub(5)..];
 
-        Ok(())
+        println!("    {:<20} {:>12} {:>9}  {}", plugin_id, size, change, style(sparkline(recent)).cyan());
+    }
+    println!();
+
+    Ok(())
+}
+
+// ============================================================================
+// PROCESS MANAGEMENT - Kill running processes before building
+// ============================================================================
+
+/// Kill any running processes that might lock build artifacts
+fn kill_running_app_processes() -> Result<()> {
+    let repo_root = get_repo_root()?;
+    let app_dir = repo_root.join("app");
+
+    // Get the app name from Cargo.toml
+    let cargo_toml_path = app_dir.join("Cargo.toml");
+    let app_name = if cargo_toml_path.exists() {
+        let content = fs::read_to_string(&cargo_toml_path)?;
+        if let Ok(doc) = content.parse::<toml::Value>() {
+            doc.get("package")
+                .and_then(|p| p.get("name"))
+                .and_then(|n| n.as_str())
+                .unwrap_or("webarcade")
+                .to_string()
+        } else {
+            "webarcade".to_string()
+        }
+    } else {
+        "webarcade".to_string()
+    };
+
+    let mut sys = System::new();
+    sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+
+    let mut killed = Vec::new();
+    let exe_name = format!("{}.exe", app_name.to_lowercase());
+    let exe_name_no_ext = app_name.to_lowercase();
+
+    // Also check for processes running from target directory
+    let target_release_dir = app_dir.join("target").join("release");
+    let target_debug_dir = app_dir.join("target").join("debug");
+
+    for (pid, process) in sys.processes() {
+        let name = process.name().to_string_lossy().to_lowercase();
+        let exe_path = process.exe().map(|p| p.to_path_buf());
+
+        let mut should_kill = false;
+
+        // Check by process name
+        if name == exe_name || name == exe_name_no_ext {
+            should_kill = true;
+        }
+
+        // Check by executable path (more reliable)
+        if let Some(ref path) = exe_path {
+            let path_str = path.to_string_lossy().to_lowercase();
+            if path_str.contains(&app_name.to_lowercase()) {
+                // Check if it's running from our target directory
+                if path.starts_with(&target_release_dir) || path.starts_with(&target_debug_dir) {
+                    should_kill = true;
+                }
+                // Or if the exe name matches
+                if let Some(file_name) = path.file_name() {
+                    let file_name_str = file_name.to_string_lossy().to_lowercase();
+                    if file_name_str == exe_name || file_name_str == exe_name_no_ext {
+                        should_kill = true;
+                    }
+                }
+            }
+        }
+
+        if should_kill {
+            let display_name = exe_path
+                .as_ref()
+                .and_then(|p| p.file_name())
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_else(|| name.clone());
+
+            if process.kill() {
+                killed.push(format!("{} (PID: {})", display_name, pid));
+            }
+        }
+    }
+
+    if !killed.is_empty() {
+        println!("  {} Terminated running processes:", style("!").yellow());
+        for proc in &killed {
+            println!("    - {}", proc);
+        }
+
+        // Wait for processes to fully terminate and release file handles
+        // Windows can be slow to release handles, so we wait a bit longer
+        std::thread::sleep(std::time::Duration::from_millis(1000));
+
+        // Refresh and verify processes are gone
+        sys.refresh_processes(sysinfo::ProcessesToUpdate::All, true);
+        let still_running: Vec<_> = sys.processes()
+            .iter()
+            .filter(|(_, p)| {
+                let name = p.name().to_string_lossy().to_lowercase();
+                name == exe_name || name == exe_name_no_ext
+            })
+            .collect();
+
+        if !still_running.is_empty() {
+            // Try one more time with SIGKILL equivalent
+            for (_, process) in still_running {
+                process.kill();
+            }
+            std::thread::sleep(std::time::Duration::from_millis(500));
+        }
+    }
+
+    Ok(())
+}
+
+/// Options shared by single-plugin and `--all` builds
+#[derive(Debug, Clone, Default)]
+struct BuildOptions {
+    /// Rebuild even if the source hash hasn't changed
+    force: bool,
+    /// Rust target triple to cross-compile for
+    target: Option<String>,
+    /// Where compiled plugins are placed (defaults to app/plugins)
+    target_dir: Option<PathBuf>,
+    /// Cargo features to build with, overriding the config entry's `features`
+    features: Option<Vec<String>>,
+    /// Skip sccache even when it is installed
+    no_sccache: bool,
+    /// Print artifact sizes after building
+    size_report: bool,
+    /// Incremental release profile and a persistent target dir
+    incremental: bool,
+    /// Release profile opt-level, replacing the generated "z"
+    opt_level: Option<String>,
+    /// Size-minimizing profile and export list (--min-size)
+    min_size: bool,
+    /// Fail the build if an artifact exceeds this size in KB
+    max_size_kb: Option<u64>,
+    /// Run `rustup target add` for a missing target without asking
+    auto_install_targets: bool,
+    /// Frontend compile-time constants, passed to the bundler as DEFINE_<KEY>
+    defines: Vec<(String, String)>,
+    /// Keep the bundler metafile as build/<plugin-id>/bundle-meta.json
+    bundle_analyze: bool,
+    /// Write API.md for plugins with routes
+    emit_docs: bool,
+    /// Fail on [routes] handlers that router.rs doesn't define
+    strict: bool,
+    /// Don't run `git submodule update --init` for uninitialized plugin submodules
+    no_submodule_update: bool,
+    /// Check dependency licenses against the config's allowedLicenses
+    check_licenses: bool,
+    /// Licenses allowed in addition to allowedLicenses for this run
+    allow_licenses: Vec<String>,
+    /// Run cargo-deny after compiling
+    deny: bool,
+    /// Size analysis tool for the compiled library: "twiggy", "bloaty", "both" or "auto"
+    analyze_binary: Option<String>,
+    /// Run cargo-udeps after compiling
+    check_unused_deps: bool,
+    /// Remove the dependencies cargo-udeps reports from the plugin's Cargo.toml
+    fix_unused_deps: bool,
+    /// Profile-guided optimization with this workload command
+    pgo_workload: Option<String>,
+    /// Build again with the plugin's minimum supported Rust version
+    check_msrv: bool,
+    /// Run the backend's tests before installing it
+    test_after_build: bool,
+    /// Also build a wasm-bindgen module of each backend
+    dual_target: bool,
+    /// This is the wasm-bindgen pass of a --dual-target build
+    wasm_bindgen: bool,
+    /// Reproducible build, verified with a second compile
+    reproducible: bool,
+    /// Artifact store to POST each compiled artifact to
+    upload_artifact: Option<String>,
+    /// Sentry DSN compiled into native backends
+    sentry_dsn: Option<String>,
+    /// Sentry environment tag (prod, staging or dev)
+    sentry_environment: Option<String>,
+    /// Override the release profile's symbol stripping
+    symbols: Option<SymbolMode>,
+    /// Link with mold or lld when one is installed
+    fast_linker: bool,
+    /// Have cargo write an HTML timing report
+    timings: bool,
+    /// Build backends in place as members of the repo's Cargo workspace
+    workspace_member: bool,
+    /// Build self-contained libraries with --static
+    static_link: bool,
+    /// Parallel cargo jobs from --jobs
+    jobs: Option<u32>,
+    /// wasm-opt level to run on wasm32 modules (--strip-wasm)
+    wasm_opt_level: Option<String>,
+    /// Split frontend bundles into chunks
+    code_split: bool,
+    /// Precompile wasm32 modules to .cwasm
+    aot: bool,
+    /// Extra passes over failed plugins for build --all (None: ask)
+    retry: Option<u32>,
+    /// `-C target-feature` entries from --target-feature, e.g. "+avx2"
+    target_features: Vec<String>,
+}
+
+/// Build flags that change the artifact, and so are part of the build cache hash
+#[derive(Debug, Clone, Default)]
+struct HashedFlags {
+    symbols: Option<SymbolMode>,
+    static_link: bool,
+    code_split: bool,
+    aot: bool,
+    target_features: Vec<String>,
+    incremental: bool,
+    opt_level: Option<String>,
+    min_size: bool,
+    dual_target: bool,
+    reproducible: bool,
+    sentry_dsn: Option<String>,
+    sentry_environment: Option<String>,
+    pgo_workload: Option<String>,
+}
+
+/// Symbol handling forced by `--keep-symbols` / `--strip-symbols`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SymbolMode {
+    Keep,
+    Strip,
+}
+
+impl SymbolMode {
+    /// Rewrite `[profile.release]` of a generated Cargo.toml for this mode
+    fn apply(self, cargo_toml: &str) -> Result<String> {
+        let mut doc: toml_edit::DocumentMut = cargo_toml.parse().context("Failed to parse generated Cargo.toml")?;
+        let profile = doc["profile"].or_insert(toml_edit::table());
+        if let Some(profile) = profile.as_table_mut() {
+            profile.set_implicit(true);
+        }
+        let release = profile["release"].or_insert(toml_edit::table());
+        match self {
+            SymbolMode::Keep => {
+                if let Some(release) = release.as_table_like_mut() {
+                    release.remove("strip");
+                }
+                release["debug"] = toml_edit::value(true);
+            }
+            SymbolMode::Strip => release["strip"] = toml_edit::value("symbols"),
+        }
+        Ok(doc.to_string())
+    }
+
+    fn hash_tag(self) -> &'static str {
+        match self {
+            SymbolMode::Keep => "symbols:keep",
+            SymbolMode::Strip => "symbols:strip",
+        }
+    }
+}
+
+/// Rewrite a generated Cargo.toml for `--static`: a size-optimized, panic-aborting release
+/// profile, and `staticplugin` registered as an expected cfg
+fn apply_static_profile(cargo_toml: &str) -> Result<String> {
+    let mut doc: toml_edit::DocumentMut = cargo_toml.parse().context("Failed to parse generated Cargo.toml")?;
+    let profile = doc["profile"].or_insert(toml_edit::table());
+    if let Some(profile) = profile.as_table_mut() {
+        profile.set_implicit(true);
+    }
+    let release = profile["release"].or_insert(toml_edit::table());
+    release["opt-level"] = toml_edit::value("z");
+    release["panic"] = toml_edit::value("abort");
+
+    let lints = doc["lints"].or_insert(toml_edit::table());
+    if let Some(lints) = lints.as_table_mut() {
+        lints.set_implicit(true);
+    }
+    let mut check_cfg = toml_edit::Array::new();
+    check_cfg.push("cfg(staticplugin)");
+    let mut unexpected_cfgs = toml_edit::InlineTable::new();
+    unexpected_cfgs.insert("level", "warn".into());
+    unexpected_cfgs.insert("check-cfg", toml_edit::Value::Array(check_cfg));
+    lints["rust"].or_insert(toml_edit::table())["unexpected_cfgs"] = toml_edit::value(unexpected_cfgs);
+    Ok(doc.to_string())
+}
+
+/// `--incremental`: override the release profile for fast rebuilds. LTO is turned off
+/// because rustc can't combine it with incremental compilation.
+fn apply_incremental_profile(cargo_toml: &str) -> Result<String> {
+    let mut doc: toml_edit::DocumentMut = cargo_toml.parse().context("Failed to parse generated Cargo.toml")?;
+    let profile = doc["profile"].or_insert(toml_edit::table());
+    if let Some(profile) = profile.as_table_mut() {
+        profile.set_implicit(true);
+    }
+    let release = profile["release"].or_insert(toml_edit::table());
+    release["incremental"] = toml_edit::value(true);
+    release["codegen-units"] = toml_edit::value(16);
+    release["lto"] = toml_edit::value(false);
+    Ok(doc.to_string())
+}
+
+/// Append `flags` to the `[target.<triple>] rustflags` of a generated .cargo/config.toml
+fn add_target_rustflags(cargo_config: &str, triple: &str, flags: &[String]) -> Result<String> {
+    let mut doc: toml_edit::DocumentMut = cargo_config.parse().context("Failed to parse generated .cargo/config.toml")?;
+    let targets = doc["target"].or_insert(toml_edit::table());
+    if let Some(targets) = targets.as_table_mut() {
+        targets.set_implicit(true);
+    }
+    let target_table = targets[triple].or_insert(toml_edit::table());
+    let rustflags = target_table["rustflags"].or_insert(toml_edit::value(toml_edit::Array::new()));
+    if let Some(array) = rustflags.as_array_mut() {
+        for flag in flags {
+            array.push(flag.as_str());
+        }
+    }
+    Ok(doc.to_string())
+}
+
+/// `--opt-level`: replace the release profile's opt-level. Numeric levels are integers in
+/// Cargo.toml, `s` and `z` are strings.
+fn apply_opt_level(cargo_toml: &str, level: &str) -> Result<String> {
+    let mut doc: toml_edit::DocumentMut = cargo_toml.parse().context("Failed to parse generated Cargo.toml")?;
+    let profile = doc["profile"].or_insert(toml_edit::table());
+    if let Some(profile) = profile.as_table_mut() {
+        profile.set_implicit(true);
+    }
+    let release = profile["release"].or_insert(toml_edit::table());
+    release["opt-level"] = match level.parse::<i64>() {
+        Ok(number) => toml_edit::value(number),
+        Err(_) => toml_edit::value(level),
+    };
+    Ok(doc.to_string())
+}
+
+/// Names of the `#[no_mangle] pub extern "C" fn` exports in a generated lib.rs, in order
+fn generated_exports(lib_rs: &str) -> Vec<String> {
+    let export_re = regex::Regex::new(r#"#\[no_mangle\]\s*pub (?:unsafe )?extern "C" fn ([A-Za-z_][A-Za-z0-9_]*)"#).unwrap();
+    export_re.captures_iter(lib_rs).map(|c| c[1].to_string()).collect()
+}
+
+/// `--min-size`: the smallest release profile. Panics abort instead of unwinding, so a handler
+/// panic takes the app down rather than becoming a 500 response.
+fn apply_min_size_profile(cargo_toml: &str) -> Result<String> {
+    let mut doc: toml_edit::DocumentMut = cargo_toml.parse().context("Failed to parse generated Cargo.toml")?;
+    let profile = doc["profile"].or_insert(toml_edit::table());
+    if let Some(profile) = profile.as_table_mut() {
+        profile.set_implicit(true);
+    }
+    let release = profile["release"].or_insert(toml_edit::table());
+    release["opt-level"] = toml_edit::value("z");
+    release["strip"] = toml_edit::value("symbols");
+    release["panic"] = toml_edit::value("abort");
+    Ok(doc.to_string())
+}
+
+/// Add `wasi = "0.11"` to the generated Cargo.toml for WASI targets, keeping a version the plugin pins itself
+fn add_wasi_dependency(cargo_toml: &str) -> Result<String> {
+    let mut doc: toml_edit::DocumentMut = cargo_toml.parse().context("Failed to parse generated Cargo.toml")?;
+    let dependencies = doc["dependencies"].or_insert(toml_edit::table());
+    if dependencies.get("wasi").is_none() {
+        dependencies["wasi"] = toml_edit::value("0.11");
+    }
+    Ok(doc.to_string())
+}
+
+/// Add the sentry crate for `build --sentry-dsn`. The generated `lifecycle_exports` code
+/// (`sentry::init` with `release_name!`, the `ClientInitGuard` static, sessions) is known to
+/// build and load against sentry 0.34.0; check it again before raising this requirement.
+fn add_sentry_dependency(cargo_toml: &str) -> Result<String> {
+    let mut doc: toml_edit::DocumentMut = cargo_toml.parse().context("Failed to parse generated Cargo.toml")?;
+    let dependencies = doc["dependencies"].or_insert(toml_edit::table());
+    if dependencies.get("sentry").is_none() {
+        dependencies["sentry"] = toml_edit::value("0.34");
+    }
+    Ok(doc.to_string())
+}
+
+/// `SOURCE_DATE_EPOCH` for --reproducible: the commit time of the last commit in `dir`
+fn source_date_epoch(dir: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["log", "-1", "--format=%ct"])
+        .current_dir(dir)
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    let epoch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!epoch.is_empty()).then_some(epoch)
+}
+
+/// Version of the installed wasm-bindgen CLI, which the wasm-bindgen crate must match exactly
+fn wasm_bindgen_cli_version() -> Result<String> {
+    let output = Command::new("wasm-bindgen").arg("--version").output().ok().filter(|o| o.status.success());
+    let Some(output) = output else {
+        anyhow::bail!("--dual-target needs the wasm-bindgen CLI: cargo install wasm-bindgen-cli");
+    };
+    // "wasm-bindgen 0.2.92"
+    String::from_utf8_lossy(&output.stdout)
+        .split_whitespace()
+        .nth(1)
+        .map(String::from)
+        .context("Couldn't read the wasm-bindgen CLI version")
+}
+
+/// Pin wasm-bindgen to the CLI's version and add wasm-bindgen-futures for async handlers
+fn add_wasm_bindgen_dependencies(cargo_toml: &str, cli_version: &str) -> Result<String> {
+    let mut doc: toml_edit::DocumentMut = cargo_toml.parse().context("Failed to parse generated Cargo.toml")?;
+    let dependencies = doc["dependencies"].or_insert(toml_edit::table());
+    dependencies["wasm-bindgen"] = toml_edit::value(format!("={}", cli_version));
+    if dependencies.get("wasm-bindgen-futures").is_none() {
+        dependencies["wasm-bindgen-futures"] = toml_edit::value("0.4");
+    }
+    Ok(doc.to_string())
+}
+
+/// Find the line declaring `name` in a Cargo.toml dependency table: `name = ...`,
+/// `name.workspace = true` or a `[table.name]` header. Returns the 1-based line number and the line
+fn dependency_line<'a>(content: &'a str, table: &str, name: &str) -> Option<(usize, &'a str)> {
+    let mut section = String::new();
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim();
+        if let Some(header) = trimmed.strip_prefix('[').and_then(|h| h.split(']').next()) {
+            section = header.trim().to_string();
+            if section == format!("{}.{}", table, name) {
+                return Some((index + 1, line));
+            }
+            continue;
+        }
+        if section != table {
+            continue;
+        }
+        let key = trimmed.split(['=', '.']).next().unwrap_or("").trim().trim_matches('"');
+        if key == name {
+            return Some((index + 1, line));
+        }
+    }
+    None
+}
+
+/// Repository, install hint and report columns for an --analyze-binary tool
+fn size_tool_info(tool: &str) -> (&'static str, &'static str, &'static str) {
+    match tool {
+        "twiggy" => (
+            "https://github.com/rustwasm/twiggy",
+            "cargo install twiggy",
+            "`Shallow Bytes │ Shallow % │ Item` for the largest items",
+        ),
+        _ => (
+            "https://github.com/google/bloaty",
+            "see its README for packages",
+            "`FILE SIZE │ VM SIZE │ symbols` for the largest symbols",
+        ),
+    }
+}
+
+/// One row of an --analyze-binary report
+struct SizeItem {
+    name: String,
+    bytes: u64,
+    /// Share of the whole library
+    percent: f64,
+}
+
+/// Run twiggy or bloaty in CSV mode on `library` and return the raw output plus its rows, largest first
+fn run_size_tool(tool: &str, library: &Path, total: u64) -> Result<(String, Vec<SizeItem>)> {
+    let mut command = Command::new(tool);
+    if tool == "twiggy" {
+        command.args(["top", "-n", "10", "--format", "csv"]).arg(library);
+    } else {
+        command.args(["-d", "symbols", "-n", "10", "--csv"]).arg(library);
+    }
+    let output = command.output().with_context(|| format!("Failed to run {}", tool))?;
+    if !output.status.success() {
+        anyhow::bail!("{} failed: {}", tool, String::from_utf8_lossy(&output.stderr).trim());
+    }
+    let raw = String::from_utf8_lossy(&output.stdout).to_string();
+
+    // twiggy: Name,ShallowSize,ShallowSizePercent   bloaty: symbols,vmsize,filesize
+    // Names may contain commas, so split the two numeric columns off the end
+    let mut rows: Vec<SizeItem> = raw
+        .lines()
+        .skip(1)
+        .filter_map(|line| {
+            let mut columns = line.rsplitn(3, ',');
+            let last = columns.next()?.trim();
+            let middle = columns.next()?.trim();
+            let name = columns.next()?.trim().trim_matches('"').to_string();
+            let bytes: u64 = if tool == "twiggy" { middle.parse().ok()? } else { last.parse().ok()? };
+            Some(SizeItem { name, bytes, percent: bytes as f64 / total.max(1) as f64 * 100.0 })
+        })
+        .collect();
+    rows.sort_by_key(|row| std::cmp::Reverse(row.bytes));
+    Ok((raw, rows))
+}
+
+/// Parse a `--msrv` version: `1.75` or `1.75.0`
+fn parse_rust_version(value: &str) -> Result<String, String> {
+    let parts: Vec<&str> = value.split('.').collect();
+    if !(2..=3).contains(&parts.len()) || parts.iter().any(|p| p.is_empty() || !p.chars().all(|c| c.is_ascii_digit())) {
+        return Err(format!("expected a Rust version like 1.75 or 1.75.0, got '{}'", value));
+    }
+    Ok(value.to_string())
+}
+
+/// Parse a `--define KEY=VALUE` argument
+fn parse_define(s: &str) -> std::result::Result<(String, String), String> {
+    let (key, value) = s.split_once('=').ok_or_else(|| format!("expected KEY=VALUE, got '{}'", s))?;
+    let key = key.trim();
+    if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(format!("'{}' is not a valid define name (use letters, digits and underscores)", key));
+    }
+    Ok((key.to_string(), value.to_string()))
+}
+
+/// Parallel cargo jobs and where the number came from: --jobs, then WEBARCADE_BUILD_JOBS,
+/// then CARGO_BUILD_JOBS, then the number of CPUs
+fn resolve_build_jobs(flag: Option<u32>) -> Result<(u32, &'static str)> {
+    if let Some(jobs) = flag {
+        return Ok((jobs, "--jobs"));
+    }
+    for var in ["WEBARCADE_BUILD_JOBS", "CARGO_BUILD_JOBS"] {
+        if let Some(value) = std::env::var(var).ok().filter(|v| !v.trim().is_empty()) {
+            let jobs = value.trim().parse::<u32>().ok().filter(|&n| n > 0)
+                .with_context(|| format!("{} must be a positive number, got '{}'", var, value))?;
+            return Ok((jobs, var));
+        }
+    }
+    let cpus = std::thread::available_parallelism().map(|n| n.get() as u32).unwrap_or(1);
+    Ok((cpus, "CPU count"))
+}
+
+impl BuildOptions {
+    fn hashed_flags(&self) -> HashedFlags {
+        HashedFlags {
+            symbols: self.symbols,
+            static_link: self.static_link,
+            code_split: self.code_split,
+            aot: self.aot,
+            target_features: self.target_features.clone(),
+            incremental: self.incremental,
+            opt_level: self.opt_level.clone(),
+            min_size: self.min_size,
+            dual_target: self.dual_target,
+            reproducible: self.reproducible,
+            sentry_dsn: self.sentry_dsn.clone(),
+            sentry_environment: self.sentry_environment.clone(),
+            pgo_workload: self.pgo_workload.clone(),
+        }
+    }
+
+    /// Directory compiled plugins are installed into
+    fn dist_plugins_dir(&self) -> Result<PathBuf> {
+        match &self.target_dir {
+            Some(dir) => Ok(dir.clone()),
+            None => get_dist_plugins_dir(),
+        }
+    }
+
+    /// Build cache key for a plugin. Builds to a custom target directory are
+    /// cached separately so switching directories triggers a rebuild.
+    fn cache_key(&self, plugin_id: &str) -> String {
+        match &self.target_dir {
+            Some(dir) => format!("{}@{}", plugin_id, dir.display()),
+            None => plugin_id.to_string(),
+        }
+    }
+}
+
+/// Whether app/plugins/ holds anything besides `.sha256` checksum files
+fn has_compiled_plugins(dist_plugins_dir: &Path) -> bool {
+    fs::read_dir(dist_plugins_dir)
+        .map(|entries| {
+            entries.filter_map(|entry| entry.ok()).any(|entry| {
+                let name = entry.file_name().to_string_lossy().to_string();
+                !name.ends_with(".sha256") && !name.ends_with(".exports.json")
+            })
+        })
+        .unwrap_or(false)
+}
+
+fn build_all_plugins(options: &BuildOptions) -> Result<()> {
+    let plugins_dir = get_plugins_dir()?;
+    let dist_plugins_dir = options.dist_plugins_dir()?;
+
+    if !plugins_dir.exists() {
+        anyhow::bail!("Plugins directory not found: {}", plugins_dir.display());
+    }
+
+    let mut plugins = Vec::new();
+    for entry in fs::read_dir(&plugins_dir)? {
+        let entry = entry?;
+        let path = entry.path();
+        // Only build source directories, not .dll files
+        if path.is_dir() {
+            plugins.push(entry.file_name().to_string_lossy().to_string());
+        }
+    }
+    for plugin_id in &plugins {
+        ensure_plugin_submodule(plugin_id, !options.no_submodule_update)?;
+    }
+
+    if plugins.is_empty() {
+        println!("No plugin source directories found in {}", plugins_dir.display());
+        return Ok(());
+    }
+
+    // Check which plugins need rebuilding
+    let mut to_build = Vec::new();
+    let mut skipped = Vec::new();
+
+    for plugin_id in &plugins {
+        let plugin_dir = plugins_dir.join(plugin_id);
+        if options.force {
+            to_build.push(plugin_id.clone());
+        } else {
+            match plugin_needs_rebuild(plugin_id, &plugin_dir, &dist_plugins_dir, &options.cache_key(plugin_id), options.target.as_deref(), &options.defines, &options.hashed_flags()) {
+                Ok(true) => to_build.push(plugin_id.clone()),
+                Ok(false) => skipped.push(plugin_id.clone()),
+                Err(_) => to_build.push(plugin_id.clone()), // Build on error
+            }
+        }
+    }
+
+    // Up-to-date plugins still get their API.md; it isn't part of the source hash
+    if options.emit_docs {
+        for plugin_id in &skipped {
+            write_api_docs(&plugins_dir.join(plugin_id), plugin_id)?;
+        }
+    }
+
+    if to_build.is_empty() {
+        println!();
+        println!("  {} {}", style("✓").green().bold(), style("All plugins are up to date!").green());
+        println!();
+        return Ok(());
+    }
+
+    // Sort build order based on dependencies (dependencies first)
+    let config_path = get_config_path()?;
+    let config = WebArcadeConfig::load_or_create(&config_path)?;
+    let to_build = config.get_build_order(&to_build)?;
+    let dependencies = config.build_dependencies(&to_build)?;
+
+    let (jobs, jobs_source) = resolve_build_jobs(options.jobs)?;
+    println!("  {}", style(format!("Build parallelism: {} jobs ({})", jobs, jobs_source)).dim());
+
+    // Create progress display
+    let mut progress = BuildProgress::new(&to_build, &skipped);
+    for (plugin_id, deps) in &dependencies {
+        progress.set_waiting_on(plugin_id, deps.clone());
+    }
+    progress.render();
+
+    // Set global progress for PluginBuilder to use
+    set_build_progress(Some(&mut progress));
+
+    let mut errors: Vec<(String, String)> = Vec::new();
+    let mut built: Vec<(String, u64)> = Vec::new();
+    let mut linkers: Vec<&'static str> = Vec::new();
+    let mut pending = to_build.clone();
+    let mut retries = 0;
+    // Set while the summary and errors shown before the retry prompt are the final ones
+    let mut summary_shown = false;
+
+    loop {
+        errors.clear();
+        for plugin_id in &pending {
+            // A plugin is only built once everything it depends on built successfully
+            let failed_dep = dependencies[plugin_id]
+                .iter()
+                .find(|dep| errors.iter().any(|(id, _)| id == *dep));
+            if let Some(dep) = failed_dep {
+                progress.complete_plugin(plugin_id, false);
+                errors.push((plugin_id.clone(), format!("not built because dependency '{}' failed", dep)));
+                continue;
+            }
+
+            progress.start_plugin(plugin_id);
+
+            match build_plugin_internal(plugin_id, options) {
+                Ok((size, linker)) => {
+                    progress.complete_plugin(plugin_id, true);
+                    built.push((plugin_id.clone(), size));
+                    if let Some(linker) = linker.filter(|l| !linkers.contains(l)) {
+                        linkers.push(linker);
+                    }
+                }
+                Err(e) => {
+                    progress.complete_plugin(plugin_id, false);
+                    errors.push((plugin_id.clone(), e.to_string()));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            break;
+        }
+
+        // Failed plugins are rebuilt on their own, in build order, so a fixed
+        // dependency gets another go before its dependents
+        let retry = match options.retry {
+            Some(max) => retries < max,
+            None if console::user_attended() => {
+                set_build_progress(None);
+                progress.finish();
+                print_build_errors(&errors);
+                let retry = Confirm::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Retry failed plugins?")
+                    .default(false)
+                    .interact()?;
+                summary_shown = !retry;
+                set_build_progress(Some(&mut progress));
+                retry
+            }
+            None => false,
+        };
+        if !retry {
+            break;
+        }
+
+        retries += 1;
+        pending.retain(|id| errors.iter().any(|(failed, _)| failed == id));
+        for plugin_id in &pending {
+            progress.retry_plugin(plugin_id);
+        }
+        progress.render();
+    }
+
+    // Clear global progress
+    set_build_progress(None);
+
+    if !summary_shown {
+        progress.finish();
+    }
+
+    if options.size_report && !built.is_empty() {
+        print_size_report(&built, &linkers)?;
+    }
+
+    // Show errors at the end
+    if !errors.is_empty() {
+        if !summary_shown {
+            print_build_errors(&errors);
+        }
+        anyhow::bail!("Some plugins failed to build");
+    }
+
+    // Recalculate priorities based on dependency graph and save
+    let mut config = WebArcadeConfig::load_or_create(&config_path)?;
+    if let Err(e) = config.recalculate_priorities() {
+        println!("  {} {}", style("⚠").yellow(), style(format!("Priority calculation warning: {}", e)).yellow());
+    }
+    config.save(&config_path)?;
+
+    // Validate dependencies and warn about missing ones
+    let missing = config.validate_dependencies()?;
+    if !missing.is_empty() {
+        println!();
+        println!("  {} {}", style("⚠").yellow().bold(), style("Missing dependencies:").yellow());
+        for msg in &missing {
+            println!("    {} {}", style("→").dim(), msg);
+        }
+    }
+
+    Ok(())
+}
+
+/// List each failed plugin with its error under the build summary
+fn print_build_errors(errors: &[(String, String)]) {
+    println!("  {}", style("Errors:").red().bold());
+    for (plugin_id, error) in errors {
+        println!("    {} {}: {}", style("✗").red(), plugin_id, style(error).dim());
+    }
+    println!();
+}
+
+fn build_plugin(plugin_id: &str, options: &BuildOptions) -> Result<()> {
+    ensure_plugin_submodule(plugin_id, !options.no_submodule_update)?;
+
+    let plugins_dir = get_plugins_dir()?;
+    let dist_plugins_dir = options.dist_plugins_dir()?;
+    let plugin_dir = plugins_dir.join(plugin_id);
+
+    // Check if rebuild is needed (unless forced; a skipped build has no timings to report)
+    if !options.force && !options.timings {
+        // Build if needs rebuild or on error
+        if let Ok(false) = plugin_needs_rebuild(plugin_id, &plugin_dir, &dist_plugins_dir, &options.cache_key(plugin_id), options.target.as_deref(), &options.defines, &options.hashed_flags()) {
+            println!("{} Plugin '{}' is up to date (use -f to force rebuild)",
+                style("→").dim(), plugin_id);
+            if options.emit_docs {
+                write_api_docs(&plugin_dir, plugin_id)?;
+            }
+            return Ok(());
+        }
+    }
+
+    let (size, linker) = build_plugin_internal(plugin_id, options)?;
+
+    if options.timings {
+        let report = get_build_dir()?.join(plugin_id).join("timing.html");
+        if report.exists() {
+            println!("  {} Timing report: {}", style("→").dim(), report.display());
+            if let Err(e) = open::that(&report) {
+                println!("  {} {}", style("⚠").yellow(), style(format!("Couldn't open the timing report: {}", e)).yellow());
+            }
+        }
+    }
+
+    if options.size_report {
+        println!();
+        print_size_report(&[(plugin_id.to_string(), size)], linker.as_slice())?;
+    }
+
+    // Recalculate priorities after building
+    let config_path = get_config_path()?;
+    let mut config = WebArcadeConfig::load_or_create(&config_path)?;
+    config.recalculate_priorities()?;
+    config.save(&config_path)?;
+
+    Ok(())
+}
+
+/// Build a single plugin and record it in the cache and config. Returns the artifact size in
+/// bytes and the --fast-linker it was linked with.
+fn build_plugin_internal(plugin_id: &str, options: &BuildOptions) -> Result<(u64, Option<&'static str>)> {
+    // Use explicitly selected features, falling back to those in webarcade.config.json
+    let features = match &options.features {
+        Some(features) => features.clone(),
+        None => configured_plugin_features(plugin_id)?,
+    };
+
+    let builder = PluginBuilder::new(plugin_id, options)?;
+
+    // build.json features are always on, on top of the selected ones
+    let mut build_features = features.clone();
+    if let Some(build_json) = read_build_json(&builder.plugin_dir)? {
+        for feature in build_json.features {
+            if !build_features.contains(&feature) {
+                build_features.push(feature);
+            }
+        }
+    }
+    let build_info = builder.build(&build_features)?;
+
+    // --dual-target: a second pass for the web, without the checks the first pass already ran
+    if options.dual_target && build_info.has_backend {
+        let wasm_options = BuildOptions {
+            target: Some("wasm32-unknown-unknown".to_string()),
+            dual_target: false,
+            wasm_bindgen: true,
+            check_licenses: false,
+            deny: false,
+            analyze_binary: None,
+            check_unused_deps: false,
+            fix_unused_deps: false,
+            check_msrv: false,
+            test_after_build: false,
+            pgo_workload: None,
+            timings: false,
+            sentry_dsn: None,
+            max_size_kb: None,
+            ..options.clone()
+        };
+        PluginBuilder::new(plugin_id, &wasm_options)?.build(&build_features)?;
+    } else if build_info.has_backend {
+        // The module and glue of an earlier --dual-target build are stale now
+        if let Some(dir) = build_info.output_path.parent() {
+            for stale in [dir.join(format!("{}_bg.wasm", plugin_id)), dir.join(format!("{}.js", plugin_id))] {
+                if stale.is_file() {
+                    fs::remove_file(&stale).with_context(|| format!("Failed to remove {}", stale.display()))?;
+                }
+            }
+        }
+    }
+
+    let artifact_size = fs::metadata(&build_info.output_path).map(|m| m.len()).unwrap_or(0);
+
+    // Update cache on successful build
+    let plugins_dir = get_plugins_dir()?;
+    let plugin_dir = plugins_dir.join(plugin_id);
+
+    if options.emit_docs {
+        write_api_docs(&plugin_dir, plugin_id)?;
+    }
+    update_build_cache(plugin_id, &options.cache_key(plugin_id), options.target.as_deref(), &plugin_dir, &options.defines, &options.hashed_flags(), &build_info.output_path)?;
+
+    let mut size_history = SizeHistory::load()?;
+    size_history.record(plugin_id, artifact_size);
+    size_history.save()?;
+
+    // A failed upload only warns: the plugin is built and installed either way
+    let artifact_url = match options.upload_artifact {
+        Some(ref url) => match upload_artifact(url, plugin_id, &plugin_dir, options.target.as_deref(), &build_info.output_path) {
+            Ok(artifact_url) => {
+                let summary = format!("Uploaded to {}", artifact_url);
+                report_success(plugin_id, &summary);
+                Some(artifact_url)
+            }
+            Err(e) => {
+                let notice = format!("Upload of '{}' failed: {:#}", plugin_id, e);
+                report_notice(notice);
+                None
+            }
+        },
+        None => None,
+    };
+
+    // Update webarcade.config.json with plugin info
+    let custom_output = options.target_dir.as_ref().map(|_| build_info.output_path.as_path());
+    let target_features = if build_info.has_backend { options.target_features.as_slice() } else { &[] };
+    update_config_for_plugin(
+        plugin_id,
+        build_info.has_backend,
+        build_info.has_frontend,
+        build_info.routes,
+        custom_output,
+        &features,
+        target_features,
+        options.dual_target,
+        artifact_url.as_deref(),
+        None,
+        true,
+    )?;
+
+    Ok((artifact_size, build_info.linker))
+}
+
+/// POST a compiled artifact to `build --upload-artifact` as multipart/form-data with `plugin_id`,
+/// `version`, `platform` (the target triple) and `file` fields. Authenticated with
+/// `Authorization: Bearer $WEBARCADE_UPLOAD_TOKEN` when that is set. The store answers with
+/// `{ "artifact_url": "..." }`.
+fn upload_artifact(url: &str, plugin_id: &str, plugin_dir: &Path, target: Option<&str>, artifact: &Path) -> Result<String> {
+    let version = fs::read_to_string(plugin_dir.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+        .and_then(|pkg| pkg.get("version")?.as_str().map(String::from))
+        .unwrap_or_else(|| "1.0.0".to_string());
+    let platform = match target {
+        Some(target) => target.to_string(),
+        None => host_target_triple()?,
+    };
+    let file_name = artifact.file_name().unwrap_or_default().to_string_lossy().to_string();
+    let contents = fs::read(artifact).with_context(|| format!("Failed to read {}", artifact.display()))?;
+
+    let boundary = format!("webarcade-{:x}", Sha256::digest(&contents));
+    let mut body = Vec::new();
+    for (name, value) in [("plugin_id", plugin_id), ("version", version.as_str()), ("platform", platform.as_str())] {
+        body.extend_from_slice(format!(
+            "--{}\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+            boundary, name, value
+        ).as_bytes());
+    }
+    body.extend_from_slice(format!(
+        "--{}\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\nContent-Type: application/octet-stream\r\n\r\n",
+        boundary, file_name
+    ).as_bytes());
+    body.extend_from_slice(&contents);
+    body.extend_from_slice(format!("\r\n--{}--\r\n", boundary).as_bytes());
+
+    let mut request = http_agent(url)
+        .post(url)
+        .set("Content-Type", &format!("multipart/form-data; boundary={}", boundary));
+    if let Some(token) = std::env::var("WEBARCADE_UPLOAD_TOKEN").ok().filter(|t| !t.trim().is_empty()) {
+        request = request.set("Authorization", &format!("Bearer {}", token.trim()));
+    }
+    let response = match request.send_bytes(&body) {
+        Ok(response) => response.into_string()?,
+        Err(ureq::Error::Status(code, response)) => {
+            let detail = response.into_string().unwrap_or_default();
+            anyhow::bail!("{} answered HTTP {}: {}", url, code, detail.trim());
+        }
+        Err(e) => anyhow::bail!("{}", e),
+    };
+    let response: serde_json::Value = serde_json::from_str(&response)
+        .with_context(|| format!("{} did not answer with JSON", url))?;
+    response["artifact_url"]
+        .as_str()
+        .map(String::from)
+        .with_context(|| format!("{} answered without an artifact_url", url))
+}
+
+// ============================================================================
+// Build Progress Display
+// ============================================================================
+
+#[derive(Clone, Copy, PartialEq)]
+enum PluginStatus {
+    Pending,
+    Building,
+    Success,
+    /// Failed on the last attempt; no more retries
+    Failed,
+    /// Failed, and queued for another attempt
+    Retrying,
+    Skipped,
+}
+
+#[derive(Clone)]
+struct PluginState {
+    id: String,
+    status: PluginStatus,
+    /// Dependencies that haven't finished building yet
+    waiting_on: Vec<String>,
+    /// Builds started for this plugin
+    attempts: u32,
+}
+
+struct BuildProgress {
+    term: Term,
+    plugins: Vec<PluginState>,
+    current_plugin: Option<String>,
+    current_step: Option<String>,
+    spinner: ProgressBar,
+    // Cargo compilation progress
+    cargo_current: usize,
+    cargo_total: usize,
+    cargo_crate_name: Option<String>,
+    /// Warnings that must not scroll away with the step line, printed by `finish`
+    notices: Vec<String>,
+}
+
+impl BuildProgress {
+    fn new(to_build: &[String], skipped: &[String]) -> Self {
+        let term = Term::stdout();
+
+        // Create plugin states
+        let mut plugins: Vec<PluginState> = to_build
+            .iter()
+            .map(|id| PluginState {
+                id: id.clone(),
+                status: PluginStatus::Pending,
+                waiting_on: Vec::new(),
+                attempts: 0,
+            })
+            .collect();
+
+        // Add skipped plugins
+        for id in skipped {
+            plugins.push(PluginState {
+                id: id.clone(),
+                status: PluginStatus::Skipped,
+                waiting_on: Vec::new(),
+                attempts: 0,
+            });
+        }
+
+        // Sort plugins alphabetically for consistent display
+        plugins.sort_by(|a, b| a.id.cmp(&b.id));
+
+        // Create spinner for current action
+        let spinner = ProgressBar::new_spinner();
+        spinner.set_style(
+            ProgressStyle::default_spinner()
+                .template("  {spinner:.cyan} {msg}")
+                .unwrap()
+        );
+
+        Self {
+            term,
+            plugins,
+            current_plugin: None,
+            current_step: None,
+            spinner,
+            cargo_current: 0,
+            cargo_total: 0,
+            cargo_crate_name: None,
+            notices: Vec::new(),
+        }
+    }
+
+    fn render(&self) {
+        // Hide cursor and clear screen completely
+        let _ = self.term.hide_cursor();
+        let _ = self.term.clear_screen();
+        let _ = self.term.move_cursor_to(0, 0);
+        // Also clear scrollback buffer on supported terminals
+        print!("\x1B[3J");
+        let _ = std::io::stdout().flush();
+
+        // Header
+        println!();
+        println!("  {}  {}", style("▶").cyan().bold(), style("Building Plugins").cyan().bold());
+        println!("  {}", style("─".repeat(50)).dim());
+        println!();
+
+        // Plugin grid (3 columns)
+        let cols = 3;
+        let col_width = 18;
+
+        for (i, plugin) in self.plugins.iter().enumerate() {
+            if i % cols == 0 && i > 0 {
+                println!();
+            }
+
+            let icon = match plugin.status {
+                PluginStatus::Pending => style("○").dim(),
+                PluginStatus::Building => style("●").cyan().bold(),
+                PluginStatus::Success => style("✓").green().bold(),
+                PluginStatus::Failed => style("✗").red().bold(),
+                PluginStatus::Retrying => style("↻").yellow().bold(),
+                PluginStatus::Skipped => style("◦").dim(),
+            };
+
+            let name = if plugin.id.len() > col_width - 4 {
+                format!("{}…", &plugin.id[..col_width - 5])
+            } else {
+                plugin.id.clone()
+            };
+
+            let name_styled = match plugin.status {
+                PluginStatus::Pending => style(format!("{:<width$}", name, width = col_width - 3)).dim(),
+                PluginStatus::Building => style(format!("{:<width$}", name, width = col_width - 3)).cyan(),
+                PluginStatus::Success => style(format!("{:<width$}", name, width = col_width - 3)).green(),
+                PluginStatus::Failed => style(format!("{:<width$}", name, width = col_width - 3)).red(),
+                PluginStatus::Retrying => style(format!("{:<width$}", name, width = col_width - 3)).yellow(),
+                PluginStatus::Skipped => style(format!("{:<width$}", name, width = col_width - 3)).dim(),
+            };
+
+            print!("  {} {}", icon, name_styled);
+        }
+        println!();
+        println!();
+
+        // Pending plugins whose dependencies are still building
+        let mut any_waiting = false;
+        for plugin in &self.plugins {
+            if plugin.status == PluginStatus::Pending && !plugin.waiting_on.is_empty() {
+                println!("  {} {} {}", style("○").dim(), style(&plugin.id).dim(),
+                    style(format!("[waiting on: {}]", plugin.waiting_on.join(", "))).yellow());
+                any_waiting = true;
+            }
+        }
+        if any_waiting {
+            println!();
+        }
+
+        // Plugins that failed and are queued for another attempt
+        let retrying: Vec<String> = self.plugins.iter()
+            .filter(|p| p.status == PluginStatus::Retrying)
+            .map(|p| format!("{} (attempt {})", p.id, p.attempts + 1))
+            .collect();
+        if !retrying.is_empty() {
+            println!("  {} {}", style("↻").yellow(), style(format!("Failed, will retry: {}", retrying.join(", "))).yellow());
+            println!();
+        }
+
+        // Current action
+        if let (Some(plugin), Some(step)) = (&self.current_plugin, &self.current_step) {
+            println!("  {} {}: {}", style("→").cyan(), style(plugin).bold(), style(step).dim());
+
+            // Show cargo compilation progress if compiling
+            if step.contains("Compiling") && self.cargo_total > 0 {
+                let cargo_bar_width = 30;
+                let cargo_filled = (self.cargo_current * cargo_bar_width)
+                    .checked_div(self.cargo_total)
+                    .unwrap_or(0);
+                let cargo_empty = cargo_bar_width - cargo_filled;
+
+                let cargo_bar = format!("{}{}",
+                    style("=".repeat(cargo_filled)).cyan(),
+                    style(" ".repeat(cargo_empty)).dim()
+                );
+
+                let crate_display = self.cargo_crate_name.as_deref().unwrap_or("");
+                println!("    {} [{}] {}/{}: {}",
+                    style("Building").dim(),
+                    cargo_bar,
+                    self.cargo_current,
+                    self.cargo_total,
+                    style(crate_display).yellow()
+                );
+            }
+        }
+
+        // Progress bar
+        let done = self.plugins.iter().filter(|p| p.status == PluginStatus::Success || p.status == PluginStatus::Failed).count();
+        let total = self.plugins.iter().filter(|p| p.status != PluginStatus::Skipped).count();
+        let skipped = self.plugins.iter().filter(|p| p.status == PluginStatus::Skipped).count();
+
+        println!();
+        let bar_width = 40;
+        let filled = (done * bar_width).checked_div(total).unwrap_or(0);
+        let empty = bar_width - filled;
+
+        let bar = format!("{}{}",
+            style("━".repeat(filled)).cyan(),
+            style("─".repeat(empty)).dim()
+        );
+
+        let percent = (done * 100).checked_div(total).unwrap_or(0);
+        let progress_text = if skipped > 0 {
+            format!("{}% ({}/{}, {} skipped)", percent, done, total, skipped)
+        } else {
+            format!("{}% ({}/{})", percent, done, total)
+        };
+
+        println!("  {} {}", bar, style(progress_text).dim());
+        println!();
+    }
+
+    fn start_plugin(&mut self, plugin_id: &str) {
+        if let Some(plugin) = self.plugins.iter_mut().find(|p| p.id == plugin_id) {
+            plugin.status = PluginStatus::Building;
+            plugin.attempts += 1;
+        }
+        self.current_plugin = Some(plugin_id.to_string());
+        self.current_step = Some("Starting...".to_string());
+        self.render();
+    }
+
+    fn set_step(&mut self, plugin_id: &str, step: &str) {
+        self.current_plugin = Some(plugin_id.to_string());
+        self.current_step = Some(step.to_string());
+        // Reset cargo progress when step changes (unless it's still compiling)
+        if !step.contains("Compiling") {
+            self.cargo_current = 0;
+            self.cargo_total = 0;
+            self.cargo_crate_name = None;
+        }
+        self.render();
+    }
+
+    fn update_cargo_progress(&mut self, current: usize, total: usize, crate_name: Option<String>) {
+        self.cargo_current = current;
+        self.cargo_total = total;
+        self.cargo_crate_name = crate_name;
+        self.render();
+    }
+
+    fn set_waiting_on(&mut self, plugin_id: &str, dependencies: Vec<String>) {
+        if let Some(plugin) = self.plugins.iter_mut().find(|p| p.id == plugin_id) {
+            plugin.waiting_on = dependencies;
+        }
+    }
+
+    fn complete_plugin(&mut self, plugin_id: &str, success: bool) {
+        if let Some(plugin) = self.plugins.iter_mut().find(|p| p.id == plugin_id) {
+            plugin.status = if success { PluginStatus::Success } else { PluginStatus::Failed };
+        }
+        if success {
+            for plugin in &mut self.plugins {
+                plugin.waiting_on.retain(|dep| dep != plugin_id);
+            }
+        }
+        self.current_plugin = None;
+        self.current_step = None;
+        self.render();
+    }
+
+    /// Queue a failed plugin for another attempt
+    fn retry_plugin(&mut self, plugin_id: &str) {
+        if let Some(plugin) = self.plugins.iter_mut().find(|p| p.id == plugin_id) {
+            plugin.status = PluginStatus::Retrying;
+        }
+    }
+
+    fn finish(&self) {
+        self.spinner.finish_and_clear();
+
+        // Final render - show cursor and clear screen
+        let _ = self.term.show_cursor();
+        let _ = self.term.clear_screen();
+        let _ = self.term.move_cursor_to(0, 0);
+        // Clear scrollback buffer
+        print!("\x1B[3J");
+        let _ = std::io::stdout().flush();
+
+        println!();
+        println!("  {}  {}", style("✓").green().bold(), style("Build Complete").green().bold());
+        println!("  {}", style("─".repeat(50)).dim());
+        println!();
+
+        // Final plugin grid
+        let cols = 3;
+        let col_width = 18;
+
+        for (i, plugin) in self.plugins.iter().enumerate() {
+            if i % cols == 0 && i > 0 {
+                println!();
+            }
+
+            let icon = match plugin.status {
+                PluginStatus::Success => style("✓").green().bold(),
+                PluginStatus::Failed => style("✗").red().bold(),
+                PluginStatus::Retrying => style("↻").yellow().bold(),
+                PluginStatus::Skipped => style("◦").dim(),
+                _ => style("○").dim(),
+            };
+
+            let name = if plugin.id.len() > col_width - 4 {
+                format!("{}…", &plugin.id[..col_width - 5])
+            } else {
+                plugin.id.clone()
+            };
+
+            let name_styled = match plugin.status {
+                PluginStatus::Success => style(format!("{:<width$}", name, width = col_width - 3)).green(),
+                PluginStatus::Failed => style(format!("{:<width$}", name, width = col_width - 3)).red(),
+                PluginStatus::Retrying => style(format!("{:<width$}", name, width = col_width - 3)).yellow(),
+                PluginStatus::Skipped => style(format!("{:<width$}", name, width = col_width - 3)).dim(),
+                _ => style(format!("{:<width$}", name, width = col_width - 3)).dim(),
+            };
+
+            print!("  {} {}", icon, name_styled);
+        }
+        println!();
+        println!();
+
+        // Summary
+        let success_count = self.plugins.iter().filter(|p| p.status == PluginStatus::Success).count();
+        let failed_count = self.plugins.iter().filter(|p| p.status == PluginStatus::Failed).count();
+        let skipped_count = self.plugins.iter().filter(|p| p.status == PluginStatus::Skipped).count();
+
+        let max_attempts = self.plugins.iter()
+            .filter(|p| p.status == PluginStatus::Failed)
+            .map(|p| p.attempts)
+            .max()
+            .unwrap_or(0);
+
+        if failed_count > 0 {
+            let gave_up = if max_attempts > 1 { format!(" (gave up after {} attempts)", max_attempts) } else { String::new() };
+            println!("  {} built, {} failed{}{}",
+                style(success_count).green().bold(),
+                style(failed_count).red().bold(),
+                gave_up,
+                if skipped_count > 0 { format!(", {} skipped", skipped_count) } else { String::new() }
+            );
+        } else {
+            println!("  {} All {} plugins built successfully{}",
+                style("✓").green().bold(),
+                style(success_count).green().bold(),
+                if skipped_count > 0 { format!(" ({} skipped)", skipped_count) } else { String::new() }
+            );
+        }
+        println!();
+
+        for notice in &self.notices {
+            println!("  {} {}", style("⚠").yellow().bold(), style(notice).yellow().bold());
+        }
+        if !self.notices.is_empty() {
+            println!();
+        }
+    }
+
+    /// Keep a warning for the end of the build
+    fn add_notice(&mut self, notice: String) {
+        self.notices.push(notice);
+    }
+}
+
+// Shared progress state for use in PluginBuilder
+thread_local! {
+    static BUILD_PROGRESS: std::cell::RefCell<Option<*mut BuildProgress>> = const { std::cell::RefCell::new(None) };
+}
+
+fn set_build_progress(progress: Option<&mut BuildProgress>) {
+    BUILD_PROGRESS.with(|p| {
+        *p.borrow_mut() = progress.map(|p| p as *mut BuildProgress);
+    });
+}
+
+fn with_build_progress<F>(f: F)
+where
+    F: FnOnce(&mut BuildProgress),
+{
+    BUILD_PROGRESS.with(|p| {
+        if let Some(ptr) = *p.borrow() {
+            // Safety: We ensure the pointer is valid during the build process
+            unsafe {
+                f(&mut *ptr);
+            }
+        }
+    });
+}
+
+/// Whether a `build --all` progress display is active on this thread
+fn in_build_progress() -> bool {
+    BUILD_PROGRESS.with(|p| p.borrow().is_some())
+}
+
+/// Show a one-line build summary: as the plugin's current step inside the progress display,
+/// printed otherwise
+fn report_summary(plugin_id: &str, summary: &str) {
+    if in_build_progress() {
+        with_build_progress(|p| p.set_step(plugin_id, summary));
+    } else {
+        println!("  {} {}", style("→").dim(), summary);
+    }
+}
+
+/// Like `report_summary`, for a check that passed
+fn report_success(plugin_id: &str, summary: &str) {
+    if in_build_progress() {
+        with_build_progress(|p| p.set_step(plugin_id, summary));
+    } else {
+        println!("  {} {}", style("✓").green(), summary);
+    }
+}
+
+/// Show a warning that must not be overwritten by later steps: kept for the end of the
+/// progress display, printed right away otherwise
+fn report_notice(notice: String) {
+    if in_build_progress() {
+        with_build_progress(|p| p.add_notice(notice));
+    } else {
+        println!("  {} {}", style("⚠").yellow().bold(), style(&notice).yellow().bold());
+    }
+}
+
+/// Information about a completed plugin build
+struct PluginBuildInfo {
+    has_backend: bool,
+    has_frontend: bool,
+    routes: Vec<serde_json::Value>,
+    /// Where the built artifact was installed
+    output_path: PathBuf,
+    /// The --fast-linker the backend was linked with
+    linker: Option<&'static str>,
+}
+
+struct PluginBuilder {
+    plugin_id: String,
+    plugin_dir: PathBuf,
+    build_dir: PathBuf,
+    dist_plugins_dir: PathBuf,
+    repo_root: PathBuf,
+    target: Option<String>,
+    /// sccache binary to use as RUSTC_WRAPPER, if available
+    sccache: Option<PathBuf>,
+    /// Install a missing target with rustup without prompting
+    auto_install_targets: bool,
+    /// Frontend compile-time constants from --define
+    defines: Vec<(String, String)>,
+    /// Keep the bundler metafile after the build
+    bundle_analyze: bool,
+    /// Fail on [routes] handlers that router.rs doesn't define
+    strict: bool,
+    /// SPDX licenses dependencies may use, when --check-licenses is given
+    allowed_licenses: Option<Vec<String>>,
+    /// Release profile override from --keep-symbols / --strip-symbols
+    symbols: Option<SymbolMode>,
+    /// Whether --fast-linker was given, and the linker it found
+    fast_linker: Option<Option<&'static str>>,
+    /// Pass --timings=html to cargo and keep the report
+    timings: bool,
+    /// Package name, when the backend is built in place as a workspace member
+    workspace_package: Option<String>,
+    /// Build a self-contained library (--static)
+    static_link: bool,
+    /// Parallel cargo jobs
+    jobs: u32,
+    /// wasm-opt level from --strip-wasm
+    wasm_opt_level: Option<String>,
+    /// Ask the bundler to split the frontend into chunks
+    code_split: bool,
+    /// Precompile the wasm32 module to .cwasm (--aot)
+    aot: bool,
+    /// `-C target-feature` entries added to the target's rustflags
+    target_features: Vec<String>,
+    /// Incremental release profile, compiled in a target dir that survives between builds
+    incremental: bool,
+    /// `[profile.release] opt-level` override (--opt-level)
+    opt_level: Option<String>,
+    /// Size-minimizing release profile and explicit export list (--min-size)
+    min_size: bool,
+    /// Run `cargo deny check` after compiling
+    deny: bool,
+    /// Run twiggy/bloaty on the compiled library (--analyze-binary)
+    analyze_binary: Option<String>,
+    /// Report unused dependencies with cargo-udeps
+    check_unused_deps: bool,
+    /// Delete them from the plugin's Cargo.toml
+    fix_unused_deps: bool,
+    /// Workload for a two-stage PGO build (--pgo)
+    pgo_workload: Option<String>,
+    /// Build again with the plugin's minimum supported Rust version (--check-msrv)
+    check_msrv: bool,
+    /// Fallback MSRV from webarcade.config.json for plugins without `rust-version`
+    default_msrv: Option<String>,
+    /// Run `cargo test --lib` between compiling and installing (--test-after-build)
+    test_after_build: bool,
+    /// Fail before installing an artifact larger than this (--max-size-kb)
+    max_size_kb: Option<u64>,
+    /// wasm-bindgen pass of --dual-target: `#[wasm_bindgen]` lib.rs, installed with the wasm-bindgen CLI
+    wasm_bindgen: bool,
+    /// Stable metadata and paths, checked by compiling twice (--reproducible)
+    reproducible: bool,
+    /// Sentry DSN for the generated plugin_init (--sentry-dsn)
+    sentry_dsn: Option<String>,
+    /// Sentry environment tag (--sentry-environment)
+    sentry_environment: Option<String>,
+}
+
+/// Whether an SPDX license expression is satisfied by `allowed`. `AND` binds tighter than
+/// `OR` (old-style `/` counts as OR) and parentheses group; an expression that doesn't
+/// parse is not allowed.
+fn license_allowed(expression: &str, allowed: &[String]) -> bool {
+    let spaced = expression.replace('(', " ( ").replace(')', " ) ").replace('/', " OR ");
+    let tokens: Vec<&str> = spaced.split_whitespace().collect();
+    let mut pos = 0;
+    let result = license_or(&tokens, &mut pos, allowed);
+    result == Some(true) && pos == tokens.len()
+}
+
+/// `and-expr ("OR" and-expr)*`
+fn license_or(tokens: &[&str], pos: &mut usize, allowed: &[String]) -> Option<bool> {
+    let mut result = license_and(tokens, pos, allowed)?;
+    while tokens.get(*pos).is_some_and(|t| t.eq_ignore_ascii_case("OR")) {
+        *pos += 1;
+        result |= license_and(tokens, pos, allowed)?;
+    }
+    Some(result)
+}
+
+/// `term ("AND" term)*`
+fn license_and(tokens: &[&str], pos: &mut usize, allowed: &[String]) -> Option<bool> {
+    let mut result = license_term(tokens, pos, allowed)?;
+    while tokens.get(*pos).is_some_and(|t| t.eq_ignore_ascii_case("AND")) {
+        *pos += 1;
+        result &= license_term(tokens, pos, allowed)?;
+    }
+    Some(result)
+}
+
+/// `"(" or-expr ")"` or `license-id ["WITH" exception-id]`; an id with an exception is
+/// allowed when either `<id> WITH <exception>` or the bare id is in the list
+fn license_term(tokens: &[&str], pos: &mut usize, allowed: &[String]) -> Option<bool> {
+    let token = *tokens.get(*pos)?;
+    *pos += 1;
+    if token == "(" {
+        let result = license_or(tokens, pos, allowed)?;
+        if tokens.get(*pos) != Some(&")") {
+            return None;
+        }
+        *pos += 1;
+        return Some(result);
+    }
+    if token == ")" || ["AND", "OR", "WITH"].iter().any(|op| token.eq_ignore_ascii_case(op)) {
+        return None;
+    }
+
+    let mut with_exception = None;
+    if tokens.get(*pos).is_some_and(|t| t.eq_ignore_ascii_case("WITH")) {
+        with_exception = Some(format!("{} WITH {}", token, tokens.get(*pos + 1)?));
+        *pos += 2;
+    }
+    Some(allowed.iter().any(|a| {
+        a.eq_ignore_ascii_case(token) || with_exception.as_ref().is_some_and(|full| a.eq_ignore_ascii_case(full))
+    }))
+}
+
+/// Find an `sccache` executable on PATH
+fn detect_sccache() -> Option<PathBuf> {
+    find_on_path("sccache")
+}
+
+/// The faster linker to pass to `-fuse-ld=` for --fast-linker: mold, then lld
+fn detect_fast_linker() -> Option<&'static str> {
+    if find_on_path("mold").is_some() {
+        Some("mold")
+    } else if find_on_path("lld").is_some() || find_on_path("ld.lld").is_some() {
+        Some("lld")
+    } else {
+        None
+    }
+}
+
+/// Locate an executable the way `which` does, adding `.exe` on Windows
+fn find_on_path(name: &str) -> Option<PathBuf> {
+    let exe_name = if cfg!(target_os = "windows") && !name.ends_with(".exe") {
+        format!("{}.exe", name)
+    } else {
+        name.to_string()
+    };
+    let path_var = std::env::var_os("PATH")?;
+    std::env::split_paths(&path_var)
+        .map(|dir| dir.join(&exe_name))
+        .find(|candidate| candidate.is_file())
+}
+
+/// The host target triple reported by `rustc -vV`
+fn host_target_triple() -> Result<String> {
+    let output = Command::new("rustc")
+        .arg("-vV")
+        .output()
+        .context("Failed to run rustc -vV")?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix("host: "))
+        .map(|host| host.trim().to_string())
+        .context("Could not determine the host target triple from rustc -vV")
+}
+
+/// Generated code that turns a handler's `response` into the FFI response JSON and passes it to
+/// `respond(status, headers, body, body_base64)`. Shared by the WASI and wasm-bindgen lib.rs.
+const HANDLER_RESPONSE_TEMPLATE: &str = r#"
+let (parts, body) = response.into_parts();
+let status = parts.status.as_u16();
+
+let mut headers = std::collections::HashMap::new();
+for (key, value) in parts.headers.iter() {
+    if let Ok(v) = value.to_str() {
+        headers.insert(key.to_string(), v.to_string());
+    }
+}
+
+let body_bytes = body.to_vec();
+
+let content_type = headers.get("content-type")
+    .or_else(|| headers.get("Content-Type"))
+    .cloned()
+    .unwrap_or_default()
+    .to_lowercase();
+
+let is_binary = content_type.starts_with("image/")
+    || content_type.starts_with("application/octet-stream");
+
+if !is_binary {
+    if let Ok(body_str) = String::from_utf8(body_bytes.clone()) {
+        let body = api::serde_json::from_str::<api::serde_json::Value>(&body_str)
+            .unwrap_or(api::serde_json::Value::String(body_str));
+        return respond(status, headers, Some(body), None);
+    }
+}
+
+use api::base64::Engine;
+let encoded = api::base64::engine::general_purpose::STANDARD.encode(&body_bytes);
+respond(status, headers, None, Some(encoded))"#;
+
+/// HANDLER_RESPONSE_TEMPLATE calling `respond`, indented by `indent` spaces
+fn handler_response_code(respond: &str, indent: usize) -> String {
+    let pad = " ".repeat(indent);
+    HANDLER_RESPONSE_TEMPLATE
+        .replace("respond(", &format!("{}(", respond))
+        .lines()
+        .map(|line| if line.is_empty() { String::new() } else { format!("{}{}", pad, line) })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl PluginBuilder {
+    fn new(plugin_id: &str, options: &BuildOptions) -> Result<Self> {
+        let repo_root = get_repo_root()?;
+        let plugins_dir = get_plugins_dir()?;
+        let plugin_dir = plugins_dir.join(plugin_id);
+
+        if !plugin_dir.exists() {
+            anyhow::bail!("Plugin source not found: {}", plugin_dir.display());
+        }
+
+        if !plugin_dir.is_dir() {
+            anyhow::bail!("Plugin source must be a directory: {}", plugin_dir.display());
+        }
+
+        let build_dir = get_build_dir()?.join(plugin_id);
+        fs::create_dir_all(&build_dir)?;
+
+        let dist_plugins_dir = options.dist_plugins_dir()?;
+        fs::create_dir_all(&dist_plugins_dir)
+            .with_context(|| format!("Failed to create target directory {}", dist_plugins_dir.display()))?;
+
+        let allowed_licenses = if options.check_licenses {
+            let mut allowed = WebArcadeConfig::load_or_create(&get_config_path()?)?.allowed_licenses;
+            allowed.extend(options.allow_licenses.iter().cloned());
+            if allowed.is_empty() {
+                anyhow::bail!(
+                    "--check-licenses needs an allow-list: add \"allowedLicenses\": [\"MIT\", \"Apache-2.0\"] to webarcade.config.json \
+                     or pass --allow-license"
+                );
+            }
+            Some(allowed)
+        } else {
+            None
+        };
+
+        let default_msrv = if options.check_msrv {
+            WebArcadeConfig::load_or_create(&get_config_path()?)?.msrv
+        } else {
+            None
+        };
+
+        let workspace_package = if options.workspace_member || read_plugin_workspace_member(&plugin_dir)? {
+            let cargo_toml_path = plugin_dir.join("Cargo.toml");
+            let name = fs::read_to_string(&cargo_toml_path)
+                .ok()
+                .and_then(|c| c.parse::<toml::Value>().ok())
+                .and_then(|doc| doc.get("package")?.get("name")?.as_str().map(String::from))
+                .unwrap_or_else(|| plugin_id.to_string());
+            Some(name)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            plugin_id: plugin_id.to_string(),
+            plugin_dir,
+            build_dir,
+            dist_plugins_dir,
+            repo_root,
+            target: options.target.clone(),
+            sccache: if options.no_sccache { None } else { detect_sccache() },
+            auto_install_targets: options.auto_install_targets,
+            defines: options.defines.clone(),
+            bundle_analyze: options.bundle_analyze,
+            strict: options.strict,
+            allowed_licenses,
+            symbols: options.symbols,
+            fast_linker: options.fast_linker.then(detect_fast_linker),
+            timings: options.timings,
+            workspace_package,
+            static_link: options.static_link,
+            jobs: resolve_build_jobs(options.jobs)?.0,
+            wasm_opt_level: options.wasm_opt_level.clone(),
+            code_split: options.code_split,
+            aot: options.aot,
+            target_features: options.target_features.clone(),
+            incremental: options.incremental,
+            opt_level: options.opt_level.clone(),
+            min_size: options.min_size,
+            deny: options.deny,
+            analyze_binary: options.analyze_binary.clone(),
+            check_unused_deps: options.check_unused_deps,
+            fix_unused_deps: options.fix_unused_deps,
+            pgo_workload: options.pgo_workload.clone(),
+            check_msrv: options.check_msrv,
+            default_msrv,
+            test_after_build: options.test_after_build,
+            max_size_kb: options.max_size_kb,
+            wasm_bindgen: options.wasm_bindgen,
+            reproducible: options.reproducible,
+            sentry_dsn: options.sentry_dsn.clone(),
+            sentry_environment: options.sentry_environment.clone(),
+        })
+    }
+
+    /// Directory cargo runs in: the generated rust_build/ crate, or the repo root for workspace members
+    fn cargo_dir(&self) -> PathBuf {
+        if self.workspace_package.is_some() {
+            self.repo_root.clone()
+        } else {
+            self.build_dir.join("rust_build")
+        }
+    }
+
+    /// Cargo's target directory for `cargo_dir`, honouring CARGO_TARGET_DIR for workspace members
+    fn cargo_target_dir(&self) -> PathBuf {
+        // build/<plugin-id>/ is wiped on every build, which would throw the incremental state away
+        if self.incremental && self.workspace_package.is_none() {
+            return self.repo_root.join(".webarcade").join("incremental").join(&self.plugin_id);
+        }
+        match std::env::var_os("CARGO_TARGET_DIR") {
+            Some(dir) if self.workspace_package.is_some() => self.repo_root.join(dir),
+            _ => self.cargo_dir().join("target"),
+        }
+    }
+
+    /// Where cargo puts the release library
+    fn cargo_release_dir(&self) -> PathBuf {
+        match self.target {
+            Some(ref target) => self.cargo_target_dir().join(target).join("release"),
+            None => self.cargo_target_dir().join("release"),
+        }
+    }
+
+    /// Whether --target is a WebAssembly triple
+    fn is_wasm_target(&self) -> bool {
+        self.target.as_deref().map(|t| t.starts_with("wasm32")).unwrap_or(false)
+    }
+
+    /// Whether --target is a WASI triple (wasm32-wasip1, wasm32-wasip2, ...)
+    fn is_wasi_target(&self) -> bool {
+        self.target.as_deref().map(|t| t.starts_with("wasm32-wasi")).unwrap_or(false)
+    }
+
+    /// Get the native library filename for the target platform
+    /// Rust converts hyphens to underscores in crate/library names
+    fn lib_name(&self) -> String {
+        let crate_name = self.workspace_package.as_deref().unwrap_or(&self.plugin_id).replace('-', "_");
+        library_file_name(&crate_name, self.target.as_deref())
+    }
+
+    fn build(&self, features: &[String]) -> Result<PluginBuildInfo> {
+        let has_backend = self.plugin_dir.join("mod.rs").exists()
+            && self.plugin_dir.join("Cargo.toml").exists();
+        let has_frontend = self.plugin_dir.join("index.jsx").exists()
+            || self.plugin_dir.join("index.js").exists();
+
+        // Check if plugin has routes (needs bridge feature)
+        let has_routes = self.has_routes();
+
+        // Extract routes for config
+        let routes = self.extract_routes().unwrap_or_default();
+
+        // Report step progress
+        let plugin_id = self.plugin_id.clone();
+        let report_step = |step: &str| {
+            with_build_progress(|p| p.set_step(&plugin_id, step));
+        };
+
+        report_step("Preparing...");
+        self.warn_build_json_conflict();
+        if has_backend && self.sentry_dsn.is_some() && !self.uses_sentry() {
+            self.warn("--sentry-dsn only applies to native backends with a generated lib.rs, not wasm32 targets or workspace members");
+        }
+        let hooks = read_build_hooks(&self.plugin_dir)?;
+
+        // Fail fast on a missing cross-linker, before any compilation starts.
+        // Workspace members are built with the workspace's own cargo configuration.
+        let linker = if has_backend && self.workspace_package.is_none() { self.custom_linker()? } else { None };
+
+        // Clean build directory
+        if self.build_dir.exists() {
+            fs::remove_dir_all(&self.build_dir)?;
+        }
+        fs::create_dir_all(&self.build_dir)?;
+
+        if !hooks.pre_build.is_empty() {
+            report_step("Running pre-build hooks...");
+            self.run_hooks("pre-build", &hooks.pre_build)?;
+        }
+
+        // Build frontend first
+        if has_frontend {
+            report_step("Bundling frontend...");
+            self.bundle_frontend()?;
+
+            let plugin_js = self.build_dir.join("plugin.js");
+            if let (Some(command), true) = (read_postprocess(&self.plugin_dir)?.js, plugin_js.exists()) {
+                report_step("Post-processing JS...");
+                self.run_postprocess("js", &command, &plugin_js)?;
+            }
+        }
+
+        // Frontend-only plugins: output JS file to app/plugins
+        if !has_backend {
+            report_step("Installing JS...");
+            let js_name = format!("{}.js", self.plugin_id);
+            let src_plugin_js = self.build_dir.join("plugin.js");
+            let dest_plugin_js = self.dist_plugins_dir.join(&js_name);
+            if src_plugin_js.exists() {
+                self.check_max_size(&src_plugin_js)?;
+                fs::copy(&src_plugin_js, &dest_plugin_js)?;
+            }
+            self.install_chunks()?;
+
+            if !hooks.post_build.is_empty() {
+                report_step("Running post-build hooks...");
+                self.run_hooks("post-build", &hooks.post_build)?;
+            }
+
+            // Clean up build directory
+            report_step("Cleaning up...");
+            self.cleanup_build_dir()?;
+
+            return Ok(PluginBuildInfo {
+                has_backend: false,
+                has_frontend,
+                routes: routes.clone(),
+                output_path: dest_plugin_js,
+                linker: None,
+            });
+        }
+
+        // Backend plugins: build DLL with embedded frontend
+        let frontend_js = if has_frontend {
+            let plugin_js_path = self.build_dir.join("plugin.js");
+            if plugin_js_path.exists() {
+                fs::read_to_string(&plugin_js_path)?
+            } else {
+                String::new()
+            }
+        } else {
+            String::new()
+        };
+
+        // Create package.json / manifest
+        report_step("Creating manifest...");
+        let manifest = self.create_manifest()?;
+
+        report_step("Setting up backend...");
+        self.warn_unregistered_handlers();
+        if self.workspace_package.is_none() {
+            self.setup_backend_build(&frontend_js, &manifest, has_routes, self.needs_bridge(), linker.as_deref())?;
+        } else if self.symbols.is_some() || self.fast_linker.is_some() || self.static_link || !self.target_features.is_empty() || self.incremental || self.opt_level.is_some() || self.min_size {
+            self.warn("--keep-symbols, --strip-symbols, --fast-linker, --static, --target-feature, --incremental, --opt-level and --min-size don't apply to workspace members; set them in the workspace");
+        }
+
+        if let Some(allowed) = &self.allowed_licenses {
+            report_step("Checking licenses...");
+            self.check_licenses(allowed)?;
+        }
+
+        let required = required_cpu_features(&self.target_features);
+        if !required.is_empty() {
+            self.warn(&format!(
+                "Note: This plugin requires {0}. It will crash on CPUs without {0} support.",
+                required.join(", ")
+            ));
+        }
+
+        match self.pgo_workload {
+            Some(ref workload) if self.workspace_package.is_none() && !self.is_wasm_target() => {
+                self.compile_pgo(features, workload)?;
+            }
+            _ => {
+                if self.pgo_workload.is_some() {
+                    self.warn("--pgo doesn't apply to workspace members or wasm32 targets; building without a profile");
+                }
+                report_step("Compiling DLL...");
+                self.compile_backend(features)?;
+            }
+        }
+
+        if self.deny {
+            report_step("Checking dependency policy...");
+            self.check_deny()?;
+        }
+
+        if self.check_unused_deps {
+            report_step("Checking for unused dependencies...");
+            self.check_unused_deps()?;
+        }
+
+        if self.check_msrv {
+            report_step("Checking minimum Rust version...");
+            self.check_msrv()?;
+        }
+
+        if self.reproducible {
+            if self.workspace_package.is_some() {
+                self.warn("--reproducible doesn't apply to workspace members, which use the workspace's own cargo configuration");
+            } else {
+                report_step("Verifying reproducibility...");
+                self.verify_reproducible(features)?;
+            }
+        }
+
+        if let Some(ref level) = self.wasm_opt_level {
+            if self.is_wasm_target() {
+                report_step("Optimizing WASM...");
+                self.optimize_wasm(level)?;
+            } else {
+                self.warn("--strip-wasm only applies to wasm32 targets");
+            }
+        }
+
+        if self.aot {
+            if self.is_wasm_target() {
+                report_step("Precompiling AOT cache...");
+                self.compile_aot()?;
+            } else {
+                self.warn("--aot only applies to wasm32 targets; native libraries are already compiled ahead of time");
+            }
+        }
+
+        if let Some(ref tool) = self.analyze_binary {
+            if self.is_wasm_target() {
+                self.warn("--analyze-binary only applies to native cdylib targets, not wasm32");
+            } else {
+                report_step("Analyzing binary size...");
+                self.analyze_binary(tool)?;
+            }
+        }
+
+        if self.test_after_build {
+            report_step("Testing...");
+            if let Err(e) = self.run_backend_tests() {
+                let failed = self.quarantine_artifact()?;
+                anyhow::bail!("{}; the compiled library was moved to {} instead of being installed", e, failed.display());
+            }
+        }
+
+        if self.min_size && self.workspace_package.is_none() {
+            self.report_min_size()?;
+        }
+
+        // Copy final DLL to app/plugins
+        let output_path = if self.wasm_bindgen {
+            report_step("Generating wasm-bindgen glue...");
+            self.install_wasm_bindgen()?
+        } else {
+            report_step("Installing DLL...");
+            self.install_dll()?
+        };
+        self.install_chunks()?;
+
+        if !hooks.post_build.is_empty() {
+            report_step("Running post-build hooks...");
+            self.run_hooks("post-build", &hooks.post_build)?;
+        }
+
+        // Clean up build directory
+        report_step("Cleaning up...");
+        self.cleanup_build_dir()?;
+
+        Ok(PluginBuildInfo {
+            has_backend: true,
+            has_frontend,
+            routes,
+            output_path,
+            linker: self.linker_used(),
+        })
+    }
+
+    /// Fail if any dependency in the generated workspace has a license outside `allowed`
+    fn check_licenses(&self, allowed: &[String]) -> Result<()> {
+        let installed = Command::new("cargo")
+            .args(["license", "--version"])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+        if !installed {
+            anyhow::bail!("cargo-license is not installed. Install it with: cargo install cargo-license");
+        }
+
+        let cargo_dir = self.cargo_dir();
+        let output = Command::new("cargo")
+            .current_dir(&cargo_dir)
+            .args(["license", "--json"])
+            .output()
+            .context("Failed to run cargo license")?;
+        if !output.status.success() {
+            anyhow::bail!("cargo license failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        let packages: Vec<serde_json::Value> = serde_json::from_slice(&output.stdout)
+            .context("Failed to parse cargo license output")?;
+
+        // The plugin crate itself is listed too
+        let own_name = self.package_name();
+
+        let violations: Vec<String> = packages
+            .iter()
+            .filter(|pkg| pkg["name"].as_str() != Some(own_name.as_str()))
+            .filter_map(|pkg| {
+                let license = pkg["license"].as_str();
+                if license.map(|l| license_allowed(l, allowed)).unwrap_or(false) {
+                    return None;
+                }
+                Some(format!(
+                    "{} {} ({})",
+                    pkg["name"].as_str().unwrap_or("?"),
+                    pkg["version"].as_str().unwrap_or("?"),
+                    license.unwrap_or("no license")
+                ))
+            })
+            .collect();
+
+        if !violations.is_empty() {
+            anyhow::bail!(
+                "{} dependenc{} with a license outside allowedLicenses: {}",
+                violations.len(),
+                if violations.len() == 1 { "y" } else { "ies" },
+                violations.join(", ")
+            );
+        }
+        Ok(())
+    }
+
+    /// The plugin crate's package name from its Cargo.toml, falling back to the plugin ID
+    fn package_name(&self) -> String {
+        fs::read_to_string(self.plugin_dir.join("Cargo.toml"))
+            .ok()
+            .and_then(|c| c.parse::<toml::Value>().ok())
+            .and_then(|doc| doc.get("package")?.get("name")?.as_str().map(String::from))
+            .unwrap_or_else(|| self.plugin_id.clone())
+    }
+
+    /// deny.toml for `--deny`: the plugin's own, then the repo root's, then the built-in default
+    fn deny_config_path(&self) -> Result<PathBuf> {
+        for candidate in [self.plugin_dir.join("deny.toml"), self.repo_root.join("deny.toml")] {
+            if candidate.exists() {
+                return Ok(candidate);
+            }
+        }
+        let path = self.build_dir.join("deny.toml");
+        fs::write(&path, DEFAULT_DENY_TOML)?;
+        Ok(path)
+    }
+
+    /// Run `cargo deny check` on the compiled workspace and fail with a summary of the
+    /// crates that violate the policy
+    fn check_deny(&self) -> Result<()> {
+        let installed = Command::new("cargo")
+            .args(["deny", "--version"])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+        if !installed {
+            anyhow::bail!("cargo-deny is not installed. Install it with: cargo install cargo-deny");
+        }
+
+        let config = self.deny_config_path()?;
+        let output = Command::new("cargo")
+            .current_dir(self.cargo_dir())
+            .args(["deny", "--format", "json", "check", "--config"])
+            .arg(&config)
+            .output()
+            .context("Failed to run cargo deny")?;
+        if output.status.success() {
+            return Ok(());
+        }
+
+        // Diagnostics are JSON lines on stderr; the plugin crate itself is unpublished and unlicensed
+        let own_name = self.package_name();
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let mut errors_found = false;
+        let mut violations: BTreeSet<String> = BTreeSet::new();
+        for line in stderr.lines() {
+            let Ok(entry) = serde_json::from_str::<serde_json::Value>(line) else {
+                continue;
+            };
+            let fields = &entry["fields"];
+            if entry["type"] != "diagnostic" || fields["severity"] != "error" {
+                continue;
+            }
+            errors_found = true;
+            let code = fields["code"].as_str().unwrap_or("denied");
+            let graphs = fields["graphs"].as_array().cloned().unwrap_or_default();
+            if graphs.is_empty() {
+                violations.insert(format!("{}: {}", code, fields["message"].as_str().unwrap_or("").trim()));
+            }
+            for graph in &graphs {
+                let name = graph["Krate"]["name"].as_str().unwrap_or("?");
+                if name != own_name {
+                    let version = graph["Krate"]["version"].as_str().unwrap_or("?");
+                    violations.insert(format!("{} {} ({})", name, version, code));
+                }
+            }
+        }
+
+        if !errors_found {
+            anyhow::bail!("cargo deny failed: {}", stderr.trim());
+        }
+        // Only the plugin crate itself was flagged
+        if violations.is_empty() {
+            return Ok(());
+        }
+        anyhow::bail!(
+            "cargo deny ({}) rejected {} dependenc{}: {}",
+            config.display(),
+            violations.len(),
+            if violations.len() == 1 { "y" } else { "ies" },
+            violations.into_iter().collect::<Vec<_>>().join(", ")
+        );
+    }
+
+    /// Build the backend with its minimum supported Rust version (`rust-version` in Cargo.toml,
+    /// else `msrv` in webarcade.config.json); on failure, list the compiler errors, which name
+    /// the APIs and language features that need a newer Rust
+    fn check_msrv(&self) -> Result<()> {
+        let declared = fs::read_to_string(self.plugin_dir.join("Cargo.toml"))
+            .ok()
+            .and_then(|c| c.parse::<toml::Value>().ok())
+            .and_then(|doc| doc.get("package")?.get("rust-version")?.as_str().map(String::from));
+        let Some(msrv) = declared.or_else(|| self.default_msrv.clone()) else {
+            self.warn("--check-msrv: no rust-version in Cargo.toml and no \"msrv\" in webarcade.config.json; skipped");
+            return Ok(());
+        };
+        let installed = Command::new("rustup")
+            .args(["run", &msrv, "rustc", "--version"])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+        if !installed {
+            self.warn(&format!(
+                "--check-msrv needs the Rust {0} toolchain (rustup toolchain install {0} --profile minimal); skipped",
+                msrv
+            ));
+            return Ok(());
+        }
+
+        // Cargo before 1.78 can't read the version 4 Cargo.lock newer toolchains write. The
+        // generated workspace's lockfile is rewritten as version 3 (which only changes how git
+        // source URLs are escaped); a workspace member's lockfile belongs to the user.
+        let minor = msrv.split('.').nth(1).and_then(|m| m.parse::<u32>().ok());
+        if minor.is_some_and(|minor| minor < 78) {
+            let lock_path = self.cargo_dir().join("Cargo.lock");
+            let lock = fs::read_to_string(&lock_path).unwrap_or_default();
+            if lock.lines().any(|line| line.trim() == "version = 4") {
+                if self.workspace_package.is_some() || minor.is_some_and(|minor| minor < 53) {
+                    self.warn(&format!(
+                        "--check-msrv: Cargo.lock is version 4, which Rust {} can't read; skipped",
+                        msrv
+                    ));
+                    return Ok(());
+                }
+                let downgraded: Vec<&str> = lock
+                    .lines()
+                    .map(|line| if line.trim() == "version = 4" { "version = 3" } else { line })
+                    .collect();
+                fs::write(&lock_path, downgraded.join("\n") + "\n")?;
+            }
+        }
+
+        // A host debug build in its own target dir: it only has to type-check and link, and
+        // must not touch the release artifacts
+        let mut command = Command::new("cargo");
+        command
+            .current_dir(self.cargo_dir())
+            .arg(format!("+{}", msrv))
+            .args(["build", "--lib", "--message-format", "json"])
+            .env("CARGO_TARGET_DIR", self.build_dir.join("msrv-target"));
+        if let Some(ref package) = self.workspace_package {
+            command.args(["--package", package]);
+        }
+        let output = command.output().context("Failed to run cargo build for --check-msrv")?;
+
+        if output.status.success() {
+            let summary = format!("Builds with Rust {} (MSRV)", msrv);
+            report_success(&self.plugin_id, &summary);
+            return Ok(());
+        }
+
+        // Compiler errors (e.g. "use of unstable library feature `is_some_and`") with their location
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let mut errors: Vec<String> = Vec::new();
+        for line in stdout.lines() {
+            let Ok(entry) = serde_json::from_str::<serde_json::Value>(line) else {
+                continue;
+            };
+            let message = &entry["message"];
+            if entry["reason"] != "compiler-message" || message["level"] != "error" {
+                continue;
+            }
+            let text = message["message"].as_str().unwrap_or("").trim();
+            if text.is_empty() || text.starts_with("aborting due to") {
+                continue;
+            }
+            let location = message["spans"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .find(|span| span["is_primary"] == true)
+                .map(|span| format!(" ({}:{})", span["file_name"].as_str().unwrap_or("?"), span["line_start"]))
+                .unwrap_or_default();
+            let krate = entry["target"]["name"].as_str().unwrap_or("?");
+            let error = format!("{}: {}{}", krate, text, location);
+            if !errors.contains(&error) {
+                errors.push(error);
+            }
+        }
+        // Cargo-level failures, e.g. a dependency whose own rust-version is newer
+        if errors.is_empty() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            errors.extend(
+                stderr
+                    .lines()
+                    .filter_map(|l| l.trim().strip_prefix("error: "))
+                    .filter(|l| !l.starts_with("could not compile"))
+                    .map(String::from),
+            );
+        }
+        if errors.is_empty() {
+            anyhow::bail!("Build with Rust {} failed: {}", msrv, String::from_utf8_lossy(&output.stderr).trim());
+        }
+
+        const SHOWN: usize = 10;
+        let mut report = errors.iter().take(SHOWN).map(|e| format!("\n  - {}", e)).collect::<String>();
+        if errors.len() > SHOWN {
+            report.push_str(&format!("\n  ... and {} more", errors.len() - SHOWN));
+        }
+        anyhow::bail!(
+            "Plugin doesn't build with its minimum Rust version {} ({} error{}):{}\n\
+             Raise rust-version in Cargo.toml, or avoid the newer APIs above",
+            msrv,
+            errors.len(),
+            if errors.len() == 1 { "" } else { "s" },
+            report
+        );
+    }
+
+    /// Run cargo-udeps on the compiled crate and list the plugin's unused dependencies with the
+    /// Cargo.toml line to delete, or remove them with --fix-unused-deps
+    fn check_unused_deps(&self) -> Result<()> {
+        let nightly = Command::new("rustup")
+            .args(["run", "nightly", "rustc", "--version"])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+        if !nightly {
+            self.warn("--check-unused-deps needs a nightly toolchain (rustup toolchain install nightly); skipped");
+            return Ok(());
+        }
+        let installed = Command::new("cargo")
+            .args(["+nightly", "udeps", "--version"])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+        if !installed {
+            self.warn("--check-unused-deps: cargo-udeps not found (cargo install cargo-udeps --locked); skipped");
+            return Ok(());
+        }
+
+        // A separate target dir keeps the nightly check build away from the release artifacts
+        let mut command = Command::new("cargo");
+        command
+            .current_dir(self.cargo_dir())
+            .args(["+nightly", "udeps", "--lib", "--output", "json"])
+            .env("CARGO_TARGET_DIR", self.build_dir.join("udeps-target"));
+        if let Some(ref package) = self.workspace_package {
+            command.args(["--package", package]);
+        }
+        let output = command.output().context("Failed to run cargo udeps")?;
+        // cargo-udeps exits non-zero when it finds unused dependencies, so trust the JSON instead
+        let report: serde_json::Value = serde_json::from_slice(&output.stdout).map_err(|_| {
+            anyhow::anyhow!("cargo udeps failed: {}", String::from_utf8_lossy(&output.stderr).trim())
+        })?;
+
+        // api is injected by the build and used by the generated lib.rs
+        let mut unused: Vec<(&str, String)> = Vec::new();
+        for deps in report["unused_deps"].as_object().into_iter().flat_map(|crates| crates.values()) {
+            for (kind, table) in [("normal", "dependencies"), ("development", "dev-dependencies"), ("build", "build-dependencies")] {
+                for name in deps[kind].as_array().into_iter().flatten().filter_map(|n| n.as_str()) {
+                    if name != "api" {
+                        unused.push((table, name.to_string()));
+                    }
+                }
+            }
+        }
+
+        let manifest_path = self.plugin_dir.join("Cargo.toml");
+        let names: Vec<&str> = unused.iter().map(|(_, name)| name.as_str()).collect();
+        let summary = if unused.is_empty() {
+            "No unused dependencies".to_string()
+        } else if self.fix_unused_deps {
+            let mut doc: toml_edit::DocumentMut = fs::read_to_string(&manifest_path)?
+                .parse()
+                .with_context(|| format!("Failed to parse {}", manifest_path.display()))?;
+            for (table, name) in &unused {
+                if let Some(deps) = doc.get_mut(table).and_then(|t| t.as_table_like_mut()) {
+                    deps.remove(name);
+                }
+            }
+            write_atomic(&manifest_path, &doc.to_string())?;
+            format!(
+                "Removed {} unused dependenc{} from Cargo.toml: {}",
+                unused.len(),
+                if unused.len() == 1 { "y" } else { "ies" },
+                names.join(", ")
+            )
+        } else {
+            format!("Unused dependencies: {}", names.join(", "))
+        };
+
+        if unused.is_empty() || self.fix_unused_deps {
+            report_success(&self.plugin_id, &summary);
+            return Ok(());
+        }
+        let content = fs::read_to_string(&manifest_path).unwrap_or_default();
+        // Under --all each dependency becomes a notice, listed after the progress display
+        if in_build_progress() {
+            for (table, name) in &unused {
+                let location = match dependency_line(&content, table, name) {
+                    Some((number, _)) => format!("Cargo.toml:{}", number),
+                    None => format!("[{}] in Cargo.toml", table),
+                };
+                report_notice(format!(
+                    "{}: unused dependency, remove {} from {} (or rerun with --fix-unused-deps)",
+                    self.plugin_id, name, location
+                ));
+            }
+            return Ok(());
+        }
+        self.warn(&summary);
+        for (table, name) in &unused {
+            match dependency_line(&content, table, name) {
+                Some((number, line)) => println!("      Cargo.toml:{}  {}", number, style(line).dim()),
+                None => println!("      {} (in [{}])", name, table),
+            }
+        }
+        println!(
+            "  {} Delete these lines, or rerun with {}",
+            style("→").dim(),
+            style("--fix-unused-deps").bold()
+        );
+        Ok(())
+    }
+
+    /// Generate the backend's temporary cargo workspace without compiling it.
+    /// Returns the `rust_build` directory.
+    fn prepare_backend_workspace(&self) -> Result<PathBuf> {
+        if self.build_dir.exists() {
+            fs::remove_dir_all(&self.build_dir)?;
+        }
+        fs::create_dir_all(&self.build_dir)?;
+
+        let manifest = self.create_manifest()?;
+        self.setup_backend_build("", &manifest, self.has_routes(), self.needs_bridge(), None)?;
+
+        Ok(self.build_dir.join("rust_build"))
+    }
+
+    /// Clean up the build directory after successful build
+    fn cleanup_build_dir(&self) -> Result<()> {
+        // Keep only the metafile requested with --bundle-analyze and the --timings and
+        // --analyze-binary reports
+        let keep: Vec<PathBuf> = [
+            (self.bundle_analyze, "bundle-meta.json"),
+            (self.timings, "timing.html"),
+            (self.analyze_binary.is_some(), "size-analysis.txt"),
+        ]
+        .into_iter()
+        .filter(|(wanted, _)| *wanted)
+        .map(|(_, name)| self.build_dir.join(name))
+        .filter(|path| path.exists())
+        .collect();
+        if !keep.is_empty() {
+            for entry in fs::read_dir(&self.build_dir)?.filter_map(|e| e.ok()) {
+                let path = entry.path();
+                if keep.contains(&path) {
+                    continue;
+                }
+                if path.is_dir() {
+                    fs::remove_dir_all(&path)?;
+                } else {
+                    fs::remove_file(&path)?;
+                }
+            }
+            return Ok(());
+        }
+
+        if self.build_dir.exists() {
+            fs::remove_dir_all(&self.build_dir)?;
+        }
+
+        // Also remove the parent build/ directory if it's empty
+        if let Some(parent) = self.build_dir.parent() {
+            if parent.exists() {
+                if let Ok(entries) = fs::read_dir(parent) {
+                    if entries.count() == 0 {
+                        let _ = fs::remove_dir(parent);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The --fast-linker to use for `triple`, warning when none applies
+    fn fast_linker_for(&self, triple: &str) -> Option<&'static str> {
+        match self.fast_linker? {
+            None => {
+                self.warn("--fast-linker: neither mold nor lld found, using the default linker (install mold)");
+                None
+            }
+            Some(_) if triple.contains("windows") => {
+                self.warn("--fast-linker is not supported for Windows targets, using the default linker");
+                None
+            }
+            Some("mold") if triple.contains("apple") => {
+                self.warn("--fast-linker: mold can't link macOS targets, using the default linker");
+                None
+            }
+            found => found,
+        }
+    }
+
+    /// The --fast-linker the backend was actually linked with, after the fallbacks in
+    /// `fast_linker_for`; workspace members use the workspace's own linker settings
+    fn linker_used(&self) -> Option<&'static str> {
+        if self.workspace_package.is_some() {
+            return None;
+        }
+        let linker = self.fast_linker.flatten()?;
+        let triple = match self.target {
+            Some(ref target) => target.clone(),
+            None => host_target_triple().ok()?,
+        };
+        let unsupported = triple.contains("windows") || (linker == "mold" && triple.contains("apple"));
+        (!unsupported).then_some(linker)
+    }
+
+    /// Show a build warning (as the current step inside the --all progress display)
+    fn warn(&self, message: &str) {
+        if in_build_progress() {
+            with_build_progress(|p| p.set_step(&self.plugin_id, &format!("Warning: {}", message)));
+        } else {
+            println!("  {} {}", style("!").yellow(), style(message).yellow());
+        }
+    }
+
+    /// Whether webarcade-api's `bridge` feature is needed: the plugin declares routes,
+    /// or its sources use the HTTP/bridge API directly
+    fn needs_bridge(&self) -> bool {
+        if self.has_routes() {
+            return true;
+        }
+
+        let uses_bridge_re = regex::Regex::new(r"\buse\s+api::(?:http|bridge)\b|\bHttpRequest\b").unwrap();
+        ["mod.rs", "router.rs"].iter().any(|file| {
+            fs::read_to_string(self.plugin_dir.join(file))
+                .map(|content| uses_bridge_re.is_match(&content))
+                .unwrap_or(false)
+        })
+    }
+
+    /// Warn about `-> HttpResponse` handlers in router.rs that no `[routes]` entry points to
+    fn warn_unregistered_handlers(&self) {
+        let router_content = match fs::read_to_string(self.plugin_dir.join("router.rs")) {
+            Ok(c) => c,
+            Err(_) => return,
+        };
+        let registered: Vec<String> = read_plugin_routes(&self.plugin_dir)
+            .into_iter()
+            .map(|(_, handler)| handler)
+            .collect();
+
+        let handler_re = regex::Regex::new(
+            r"(?m)^(?:pub\s+)?async\s+fn\s+([A-Za-z_][A-Za-z0-9_]*)\s*\([^)]*\)\s*->\s*HttpResponse"
+        ).unwrap();
+        let unregistered: Vec<&str> = handler_re
+            .captures_iter(&router_content)
+            .filter_map(|c| c.get(1))
+            .map(|m| m.as_str())
+            .filter(|name| !registered.iter().any(|h| h == name))
+            .collect();
+
+        if !unregistered.is_empty() {
+            self.warn(&format!(
+                "handler(s) in router.rs not registered in [routes]: {} (add e.g. \"GET /path\" = \"{}\" to Cargo.toml)",
+                unregistered.join(", "),
+                unregistered[0]
+            ));
+        }
+    }
+
+    /// Check if the plugin has routes defined in build.json or Cargo.toml
+    fn has_routes(&self) -> bool {
+        !read_plugin_routes(&self.plugin_dir).is_empty()
+    }
+
+    /// Warn when build.json shadows `[routes]` / `[hooks]` / `[postprocess]` tables that are still in Cargo.toml
+    fn warn_build_json_conflict(&self) {
+        if !self.plugin_dir.join("build.json").exists() {
+            return;
+        }
+        let cargo_toml = fs::read_to_string(self.plugin_dir.join("Cargo.toml"))
+            .ok()
+            .and_then(|content| content.parse::<toml::Value>().ok());
+        let shadowed: Vec<&str> = ["routes", "hooks", "postprocess"]
+            .into_iter()
+            .filter(|table| cargo_toml.as_ref().is_some_and(|doc| doc.get(*table).is_some()))
+            .collect();
+        if !shadowed.is_empty() {
+            let tables: Vec<String> = shadowed.iter().map(|t| format!("[{}]", t)).collect();
+            self.warn(&format!("build.json takes precedence; ignoring {} in Cargo.toml", tables.join(", ")));
+        }
+    }
+
+    /// Run the plugin's pre-build or post-build hook commands from its directory
+    fn run_hooks(&self, stage: &str, commands: &[String]) -> Result<()> {
+        for command in commands {
+            let mut shell = if cfg!(windows) {
+                let mut c = Command::new("cmd");
+                c.arg("/C");
+                c
+            } else {
+                let mut c = Command::new("sh");
+                c.arg("-c");
+                c
+            };
+            let output = shell
+                .arg(command)
+                .current_dir(&self.plugin_dir)
+                .env("WEBARCADE_PLUGIN_ID", &self.plugin_id)
+                .env("WEBARCADE_BUILD_DIR", &self.build_dir)
+                .output()
+                .with_context(|| format!("Failed to run {} hook: {}", stage, command))?;
+            if !output.status.success() {
+                anyhow::bail!(
+                    "{} hook failed: {}\n{}{}",
+                    stage,
+                    command,
+                    String::from_utf8_lossy(&output.stdout),
+                    String::from_utf8_lossy(&output.stderr)
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// `cargo test --lib` in the compiled workspace for --test-after-build, with the output
+    /// captured so the progress display stays intact; the failing tests end up in the error
+    fn run_backend_tests(&self) -> Result<()> {
+        if self.is_wasm_target() || self.target.as_ref().is_some_and(|t| host_target_triple().ok().as_ref() != Some(t)) {
+            self.warn("--test-after-build: tests can't run for a cross-compiled target; skipped");
+            return Ok(());
+        }
+
+        let mut cargo = Command::new("cargo");
+        cargo.current_dir(self.cargo_dir()).args(["test", "--lib"]);
+        if let Some(ref package) = self.workspace_package {
+            cargo.args(["--package", package]);
+        } else {
+            set_rlib_crate_type(&self.cargo_dir())?;
+        }
+        cargo.env("WEBARCADE_PLUGIN_DIR", &self.plugin_dir);
+        let output = cargo.output().context("Failed to run cargo test")?;
+        if output.status.success() {
+            return Ok(());
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let failed: Vec<&str> = stdout
+            .lines()
+            .filter_map(|line| line.strip_prefix("test ")?.strip_suffix(" ... FAILED"))
+            .collect();
+        if failed.is_empty() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let errors: Vec<&str> = stderr.lines().filter(|l| l.starts_with("error")).collect();
+            anyhow::bail!("Tests failed to build for '{}': {}", self.plugin_id, errors.join("\n"));
+        }
+        anyhow::bail!("{} test{} failed for '{}': {}", failed.len(), if failed.len() == 1 { "" } else { "s" }, self.plugin_id, failed.join(", "));
+    }
+
+    /// Move the compiled library to build/.failed/ so a plugin whose tests fail never reaches
+    /// app/plugins/, where a running app would load it
+    fn quarantine_artifact(&self) -> Result<PathBuf> {
+        let lib_name = self.lib_name();
+        let src_path = if self.workspace_package.is_some() {
+            self.cargo_release_dir().join(&lib_name)
+        } else {
+            self.build_dir.join(&lib_name)
+        };
+        let failed_dir = self.build_dir.parent().unwrap_or(&self.build_dir).join(".failed");
+        fs::create_dir_all(&failed_dir)?;
+        let dest_path = failed_dir.join(&lib_name);
+        // The workspace's own target dir is left as cargo expects it
+        if self.workspace_package.is_some() {
+            fs::copy(&src_path, &dest_path)
+        } else {
+            fs::rename(&src_path, &dest_path).map(|_| 0)
+        }
+        .with_context(|| format!("Failed to move {} to {}", src_path.display(), dest_path.display()))?;
+        Ok(dest_path)
+    }
+
+    /// Run a `[postprocess]` command from the plugin directory. The artifact's path is its first
+    /// argument and is in `WEBARCADE_ARTIFACT`; the command must write its result back to that path.
+    fn run_postprocess(&self, kind: &str, command: &str, artifact: &Path) -> Result<()> {
+        let mut shell = if cfg!(windows) {
+            let mut c = Command::new("cmd");
+            c.arg("/C").arg(format!("{} \"%WEBARCADE_ARTIFACT%\"", command));
+            c
+        } else {
+            let mut c = Command::new("sh");
+            c.arg("-c").arg(format!("{} \"$WEBARCADE_ARTIFACT\"", command));
+            c
+        };
+        let output = shell
+            .current_dir(&self.plugin_dir)
+            .env("WEBARCADE_ARTIFACT", artifact)
+            .env("WEBARCADE_PLUGIN_ID", &self.plugin_id)
+            .env("WEBARCADE_BUILD_DIR", &self.build_dir)
+            .output()
+            .with_context(|| format!("Failed to run {} post-processor: {}", kind, command))?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "{} post-processor failed: {}\n{}{}",
+                kind,
+                command,
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        if !artifact.is_file() {
+            anyhow::bail!(
+                "{} post-processor `{}` didn't leave its output at {}",
+                kind,
+                command,
+                artifact.display()
+            );
+        }
+        Ok(())
+    }
+
+    /// Two-stage profile-guided build: compile with `-C profile-generate`, run the workload against
+    /// the instrumented library, merge the raw profiles with llvm-profdata, then recompile with
+    /// `-C profile-use`. Profiles live in build/<plugin-id>/pgo/.
+    fn compile_pgo(&self, features: &[String], workload: &str) -> Result<()> {
+        let Some(llvm_profdata) = find_on_path("llvm-profdata") else {
+            anyhow::bail!(
+                "--pgo needs llvm-profdata on your PATH (rustup component add llvm-tools-preview, \
+                 then add its bin directory under ~/.rustup/toolchains/<toolchain>/lib/rustlib/<host>/bin)"
+            );
+        };
+
+        let profile_dir = self.build_dir.join("pgo");
+        if profile_dir.exists() {
+            fs::remove_dir_all(&profile_dir)?;
+        }
+        fs::create_dir_all(&profile_dir)?;
+
+        // Rustflags go into the generated config's target table so the link args there are kept
+        let config_path = self.cargo_dir().join(".cargo").join("config.toml");
+        let base_config = fs::read_to_string(&config_path).unwrap_or_default();
+        let triple = match self.target {
+            Some(ref target) => target.clone(),
+            None => host_target_triple()?,
+        };
+
+        with_build_progress(|p| p.set_step(&self.plugin_id, "Compiling instrumented DLL (PGO 1/3)..."));
+        let generate = format!("-Cprofile-generate={}", profile_dir.display());
+        fs::write(&config_path, add_target_rustflags(&base_config, &triple, &[generate])?)?;
+        self.compile_backend(features)?;
+
+        with_build_progress(|p| p.set_step(&self.plugin_id, "Running PGO workload (PGO 2/3)..."));
+        let mut shell = if cfg!(windows) {
+            let mut c = Command::new("cmd");
+            c.arg("/C");
+            c
+        } else {
+            let mut c = Command::new("sh");
+            c.arg("-c");
+            c
+        };
+        let output = shell
+            .arg(workload)
+            .current_dir(&self.plugin_dir)
+            .env("WEBARCADE_PLUGIN_ID", &self.plugin_id)
+            .env("WEBARCADE_PLUGIN_LIB", self.compiled_artifact())
+            .env("LLVM_PROFILE_FILE", profile_dir.join("%p-%m.profraw"))
+            .output()
+            .with_context(|| format!("Failed to run PGO workload: {}", workload))?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "PGO workload failed: {}\n{}{}",
+                workload,
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            );
+        }
+        let has_profiles = fs::read_dir(&profile_dir)?
+            .filter_map(|e| e.ok())
+            .any(|e| e.path().extension().is_some_and(|ext| ext == "profraw"));
+        if !has_profiles {
+            anyhow::bail!("PGO workload wrote no profiles to {}; does it load {}?", profile_dir.display(), self.lib_name());
+        }
+
+        let merged = profile_dir.join("merged.profdata");
+        let output = Command::new(&llvm_profdata)
+            .arg("merge")
+            .arg("-o")
+            .arg(&merged)
+            .arg(&profile_dir)
+            .output()
+            .context("Failed to run llvm-profdata")?;
+        if !output.status.success() {
+            anyhow::bail!("llvm-profdata merge failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+
+        with_build_progress(|p| p.set_step(&self.plugin_id, "Compiling optimized DLL (PGO 3/3)..."));
+        let use_profile = format!("-Cprofile-use={}", merged.display());
+        fs::write(&config_path, add_target_rustflags(&base_config, &triple, &[use_profile])?)?;
+        self.compile_backend(features)
+    }
+
+    /// Read `[build] linker` from the plugin's Cargo.toml and make sure it can be found
+    fn custom_linker(&self) -> Result<Option<String>> {
+        let cargo_toml_path = self.plugin_dir.join("Cargo.toml");
+        let content = fs::read_to_string(&cargo_toml_path)
+            .with_context(|| format!("Failed to read {}", cargo_toml_path.display()))?;
+        let cargo_toml: toml::Value = content
+            .parse()
+            .with_context(|| format!("Failed to parse {}", cargo_toml_path.display()))?;
+
+        let linker = match cargo_toml.get("build").and_then(|b| b.get("linker")) {
+            Some(value) => value
+                .as_str()
+                .with_context(|| format!("[build] linker in {} must be a string", cargo_toml_path.display()))?,
+            None => return Ok(None),
+        };
+
+        let found = if Path::new(linker).components().count() > 1 {
+            Path::new(linker).is_file()
+        } else {
+            find_on_path(linker).is_some()
+        };
+        if !found {
+            anyhow::bail!(
+                "Linker '{}' configured in {} was not found in PATH. Install it or update [build] linker",
+                linker,
+                cargo_toml_path.display()
+            );
+        }
+
+        Ok(Some(linker.to_string()))
+    }
+
+    fn setup_backend_build(
+        &self,
+        frontend_js: &str,
+        manifest: &str,
+        has_routes: bool,
+        bridge: bool,
+        linker: Option<&str>,
+    ) -> Result<()> {
+        let rust_build_dir = self.build_dir.join("rust_build");
+        fs::create_dir_all(&rust_build_dir)?;
+
+        // Copy Rust source files
+        self.copy_rust_files(&self.plugin_dir, &rust_build_dir)?;
+
+        // Generate Cargo.toml
+        // API dependency from crates.io with optional bridge feature (routes or direct HTTP API use)
+        let api_dep = if bridge {
+            r#"api = { package = "webarcade-api", version = "0.1", features = ["bridge"] }"#.to_string()
+        } else {
+            r#"api = { package = "webarcade-api", version = "0.1" }"#.to_string()
+        };
+
+        let plugin_cargo_toml = self.plugin_dir.join("Cargo.toml");
+        let cargo_toml = if plugin_cargo_toml.exists() {
+            let mut content = fs::read_to_string(&plugin_cargo_toml)?;
+
+            // Inject API dependency with appropriate features
+            let re = regex::Regex::new(r#"api\s*=\s*\{[^}]*\}"#)?;
+            content = if re.is_match(&content) {
+                re.replace(&content, &api_dep).to_string()
+            } else {
+                let deps_re = regex::Regex::new(r"(?m)^\[dependencies\]\s*$")?;
+                if let Some(mat) = deps_re.find(&content) {
+                    let insert_pos = mat.end();
+                    let mut new_content = content.clone();
+                    new_content.insert_str(insert_pos, &format!("\n{}", api_dep));
+                    new_content
+                } else {
+                    format!("{}\n[dependencies]\n{}\n", content, api_dep)
+                }
+            };
+
+            // Ensure [lib] section
+            let lib_section_re = regex::Regex::new(r"(?m)\n?\[lib\][^\[]*")?;
+            content = lib_section_re.replace(&content, "").to_string();
+
+            // [build] holds webarcade build options, not manifest keys
+            let build_section_re = regex::Regex::new(r"(?m)\n?^\[build\][^\[]*")?;
+            content = build_section_re.replace(&content, "").to_string();
+
+            let package_re = regex::Regex::new(r"(?m)(\[package\][^\[]+)")?;
+            if let Some(mat) = package_re.find(&content) {
+                let insert_pos = mat.end();
+                content.insert_str(insert_pos, "\n[lib]\ncrate-type = [\"cdylib\"]\npath = \"lib.rs\"\n");
+            }
+
+            content
+        } else {
+            format!(
+                r#"[package]
+name = "{}"
+version = "1.0.0"
+edition = "2021"
+
+[lib]
+crate-type = ["cdylib"]
+path = "lib.rs"
+
+[dependencies]
+{}
+
+[profile.release]
+opt-level = "z"
+lto = true
+codegen-units = 1
+strip = true
+"#,
+                self.plugin_id, api_dep
+            )
+        };
+
+        let cargo_toml = match self.symbols {
+            Some(mode) => mode.apply(&cargo_toml)?,
+            None => cargo_toml,
+        };
+        let cargo_toml = if self.static_link { apply_static_profile(&cargo_toml)? } else { cargo_toml };
+        let cargo_toml = if self.incremental { apply_incremental_profile(&cargo_toml)? } else { cargo_toml };
+        let cargo_toml = match self.opt_level {
+            Some(ref level) => apply_opt_level(&cargo_toml, level)?,
+            None => cargo_toml,
+        };
+        let cargo_toml = if self.min_size {
+            self.warn("--min-size builds with panic = \"abort\": a panicking handler aborts the app instead of returning a 500");
+            apply_min_size_profile(&cargo_toml)?
+        } else {
+            cargo_toml
+        };
+        let cargo_toml = if self.is_wasi_target() { add_wasi_dependency(&cargo_toml)? } else { cargo_toml };
+        let cargo_toml = if self.wasm_bindgen { add_wasm_bindgen_dependencies(&cargo_toml, &wasm_bindgen_cli_version()?)? } else { cargo_toml };
+        let cargo_toml = if self.uses_sentry() { add_sentry_dependency(&cargo_toml)? } else { cargo_toml };
+        fs::write(rust_build_dir.join("Cargo.toml"), cargo_toml)?;
+
+        // A build.rs at the plugin root becomes the crate's build script; cargo runs it as usual
+        let build_script = self.plugin_dir.join("build.rs");
+        if build_script.exists() {
+            fs::copy(&build_script, rust_build_dir.join("build.rs"))?;
+        }
+
+        // Create .cargo/config.toml
+        let cargo_config_dir = rust_build_dir.join(".cargo");
+        fs::create_dir_all(&cargo_config_dir)?;
+        // Static plugins must link without unresolved host symbols, so they get no allow-undefined link args
+        let cargo_config = if self.static_link {
+            ""
+        } else {
+            r#"[target.x86_64-pc-windows-msvc]
+rustflags = ["-C", "link-args=/FORCE:UNRESOLVED"]
+
+[target.x86_64-unknown-linux-gnu]
+rustflags = ["-C", "link-args=-Wl,--allow-shlib-undefined"]
+
+[target.x86_64-apple-darwin]
+rustflags = ["-C", "link-args=-undefined dynamic_lookup"]
+
+[target.aarch64-apple-darwin]
+rustflags = ["-C", "link-args=-undefined dynamic_lookup"]
+"#
+        };
+        let cargo_config = if linker.is_some() || self.fast_linker.is_some() || self.static_link || !self.target_features.is_empty() {
+            let triple = match self.target {
+                Some(ref target) => target.clone(),
+                None => host_target_triple()?,
+            };
+            let mut doc: toml_edit::DocumentMut = cargo_config.parse()?;
+            // --static starts from an empty config, so [target] may not exist yet
+            let targets = doc["target"].or_insert(toml_edit::table());
+            if let Some(targets) = targets.as_table_mut() {
+                targets.set_implicit(true);
+            }
+            let target_table = targets[triple.as_str()].or_insert(toml_edit::table());
+            if let Some(linker) = linker {
+                target_table["linker"] = toml_edit::value(linker);
+            }
+            // --static cfg (and the static CRT on Windows MSVC)
+            if self.static_link {
+                let rustflags = target_table["rustflags"].or_insert(toml_edit::value(toml_edit::Array::new()));
+                if let Some(flags) = rustflags.as_array_mut() {
+                    flags.push("--cfg");
+                    flags.push("staticplugin");
+                    if triple.contains("windows-msvc") {
+                        flags.push("-C");
+                        flags.push("target-feature=+crt-static");
+                    }
+                }
+            }
+            // Added to the target's rustflags rather than RUSTFLAGS, which would replace the link-args above
+            if let Some(fast_linker) = self.fast_linker_for(&triple) {
+                let rustflags = target_table["rustflags"].or_insert(toml_edit::value(toml_edit::Array::new()));
+                if let Some(flags) = rustflags.as_array_mut() {
+                    flags.push("-C");
+                    flags.push(format!("link-arg=-fuse-ld={}", fast_linker));
+                }
+            }
+            if !self.target_features.is_empty() {
+                let rustflags = target_table["rustflags"].or_insert(toml_edit::value(toml_edit::Array::new()));
+                if let Some(flags) = rustflags.as_array_mut() {
+                    flags.push("-C");
+                    flags.push(format!("target-feature={}", self.target_features.join(",")));
+                }
+            }
+            doc.to_string()
+        } else {
+            cargo_config.to_string()
+        };
+        fs::write(cargo_config_dir.join("config.toml"), cargo_config)?;
+
+        // Generate lib.rs with embedded assets
+        self.create_lib_rs(&rust_build_dir, frontend_js, manifest, has_routes)?;
+        if self.min_size {
+            self.restrict_exports(&rust_build_dir)?;
+        }
+        if self.reproducible {
+            self.apply_reproducible_flags(&rust_build_dir)?;
+        }
+        // The DSN reaches lib.rs through env!, so it is only in the (temporary) cargo config
+        if let (Some(dsn), true) = (&self.sentry_dsn, self.uses_sentry()) {
+            let config_path = cargo_config_dir.join("config.toml");
+            let mut doc: toml_edit::DocumentMut = fs::read_to_string(&config_path)?.parse()?;
+            doc["env"]["WEBARCADE_SENTRY_DSN"] = toml_edit::value(dsn.as_str());
+            fs::write(&config_path, doc.to_string())?;
+        }
+
+        Ok(())
+    }
+
+    fn copy_rust_files(&self, src: &Path, dst: &Path) -> Result<()> {
+        let plugin_mod_dir = dst.join("plugin_mod");
+        fs::create_dir_all(&plugin_mod_dir)?;
+
+        let handler_re = regex::Regex::new(r"(?m)^async fn ([a-zA-Z_][a-zA-Z0-9_]*)\(([^)]*)\) -> HttpResponse")?;
+
+        for entry in fs::read_dir(src)? {
+            let entry = entry?;
+            let path = entry.path();
+            let file_name = entry.file_name();
+            let file_name_str = file_name.to_string_lossy();
+
+            if path.is_file() {
+                if let Some(ext) = path.extension() {
+                    // build.rs is the crate's build script, not a plugin module
+                    if ext == "rs" && file_name_str != "build.rs" {
+                        let dest_path = plugin_mod_dir.join(&file_name);
+                        let content = fs::read_to_string(&path)?;
+
+                        let modified_content = if file_name_str == "mod.rs" {
+                            if content.contains("pub mod router;") {
+                                content
+                            } else {
+                                content.replace("mod router;", "pub mod router;")
+                            }
+                        } else if file_name_str == "router.rs" {
+                            handler_re.replace_all(&content, "pub async fn $1($2) -> HttpResponse").to_string()
+                        } else {
+                            content
+                        };
+
+                        fs::write(&dest_path, modified_content)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// `--min-size`: allow dead code in the generated lib.rs and hand the linker an explicit list
+    /// of its `#[no_mangle]` exports (the fixed entry points plus one per handler), as a version
+    /// script on Linux and a .def file on Windows MSVC
+    fn restrict_exports(&self, rust_build_dir: &Path) -> Result<()> {
+        let lib_rs_path = rust_build_dir.join("lib.rs");
+        let lib_rs = fs::read_to_string(&lib_rs_path)?;
+        let exports = generated_exports(&lib_rs);
+        fs::write(&lib_rs_path, format!("#![allow(dead_code)]\n{}", lib_rs))?;
+
+        let triple = match self.target {
+            Some(ref target) => target.clone(),
+            None => host_target_triple()?,
+        };
+        let flag = if triple.contains("linux") {
+            let path = rust_build_dir.join("exports.map");
+            let symbols: String = exports.iter().map(|name| format!("    {};\n", name)).collect();
+            fs::write(&path, format!("{{\n  global:\n{}  local:\n    *;\n}};\n", symbols))?;
+            format!("link-arg=-Wl,--version-script={}", path.display())
+        } else if triple.contains("windows-msvc") {
+            let path = rust_build_dir.join("exports.def");
+            let symbols: String = exports.iter().map(|name| format!("    {}\n", name)).collect();
+            fs::write(&path, format!("EXPORTS\n{}", symbols))?;
+            format!("link-arg=/DEF:{}", path.display())
+        } else {
+            return Ok(());
+        };
+
+        let config_path = rust_build_dir.join(".cargo").join("config.toml");
+        let config = fs::read_to_string(&config_path).unwrap_or_default();
+        fs::write(&config_path, add_target_rustflags(&config, &triple, &["-C".to_string(), flag])?)?;
+        Ok(())
+    }
+
+    /// `--min-size`: compare the compiled library with the one installed by the previous build
+    fn report_min_size(&self) -> Result<()> {
+        let after = fs::metadata(self.compiled_artifact())?.len();
+        let summary = match fs::metadata(self.dist_plugins_dir.join(self.installed_lib_name())) {
+            Ok(previous) => {
+                let before = previous.len();
+                let change = (after as f64 - before as f64) / before.max(1) as f64 * 100.0;
+                format!("Min size: {} KB → {} KB ({:+.0}%)", before.div_ceil(1024), after.div_ceil(1024), change)
+            }
+            Err(_) => format!("Min size: {} KB (no previous build to compare)", after.div_ceil(1024)),
+        };
+        report_summary(&self.plugin_id, &summary);
+        Ok(())
     }
 
     fn create_lib_rs(&self, rust_build_dir: &Path, frontend_js: &str, manifest: &str, has_routes: bool) -> Result<()> {
+        if self.is_wasi_target() {
+            return self.create_wasi_lib_rs(rust_build_dir, frontend_js, manifest, has_routes);
+        }
+        if self.wasm_bindgen {
+            return self.create_wasm_bindgen_lib_rs(rust_build_dir, frontend_js, manifest, has_routes);
+        }
+        let plugin_struct = self.get_plugin_struct_name();
+
+        // Escape the embedded strings for Rust
+        let escaped_frontend = frontend_js.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n").replace('\r', "");
+        let escaped_manifest = manifest.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n").replace('\r', "");
+
+        // Only generate handler wrappers if plugin has routes
+        let handler_wrappers = if !has_routes {
+            String::new()
+        } else {
+            let handlers = self.extract_handlers()?;
+            handlers.iter().map(|(handler_name, takes_request)| {
+            let handler_call = if *takes_request {
+                format!("plugin_mod::router::{}(http_request.clone()).await", handler_name)
+            } else {
+                format!("plugin_mod::router::{}().await", handler_name)
+            };
+
+            format!(r##"
+#[no_mangle]
+pub extern "C" fn {handler_name}(request_ptr: *const u8, request_len: usize, _runtime_ptr: *const ()) -> *const u8 {{
+    use std::panic;
+    use std::ffi::CString;
+    use api::ffi_http::Response as FFIResponse;
+    use api::http::HttpRequest;
+
+    let result = panic::catch_unwind(|| {{
+        let _http_request = match HttpRequest::from_ffi_json(request_ptr, request_len) {{
+            Ok(r) => r,
+            Err(e) => {{
+                let error_response = FFIResponse::new(400)
+                    .json(&api::serde_json::json!({{"error": e}}));
+                return error_response.into_ffi_ptr();
+            }}
+        }};
+        #[allow(unused_variables)]
+        let http_request = _http_request;
+
+        // Create a dedicated single-threaded runtime for this handler
+        // This avoids deadlock when called from within an existing async context
+        let rt = api::tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("Failed to create handler runtime");
+        rt.block_on(async {{
+            let handler_result = {handler_call};
+            let response = handler_result;
+
+            let (parts, body) = response.into_parts();
+            let status = parts.status.as_u16();
+
+            let mut headers = std::collections::HashMap::new();
+            for (key, value) in parts.headers.iter() {{
+                if let Ok(v) = value.to_str() {{
+                    headers.insert(key.to_string(), v.to_string());
+                }}
+            }}
+
+            let body_bytes = body.to_vec();
+
+            let mut ffi_response = FFIResponse::new(status);
+            ffi_response.headers = headers.clone();
+
+            let content_type = headers.get("content-type")
+                .or_else(|| headers.get("Content-Type"))
+                .cloned()
+                .unwrap_or_default()
+                .to_lowercase();
+
+            let is_binary = content_type.starts_with("image/")
+                || content_type.starts_with("application/octet-stream");
+
+            if is_binary {{
+                use api::base64::Engine;
+                ffi_response.body_base64 = Some(
+                    api::base64::engine::general_purpose::STANDARD.encode(&body_bytes)
+                );
+            }} else if let Ok(body_str) = String::from_utf8(body_bytes.clone()) {{
+                if let Ok(json_value) = api::serde_json::from_str::<api::serde_json::Value>(&body_str) {{
+                    ffi_response.body = Some(json_value);
+                }} else {{
+                    ffi_response.body = Some(api::serde_json::Value::String(body_str));
+                }}
+            }} else {{
+                use api::base64::Engine;
+                ffi_response.body_base64 = Some(
+                    api::base64::engine::general_purpose::STANDARD.encode(&body_bytes)
+                );
+            }}
+
+            ffi_response.into_ffi_ptr()
+        }})
+    }});
+
+    match result {{
+        Ok(ptr) => ptr,
+        Err(_) => {{
+            let error = CString::new(r#"{{"__ffi_response__":true,"status":500,"headers":{{"Content-Type":"application/json"}},"body":{{"error":"Handler panicked"}}}}"#).unwrap();
+            Box::leak(Box::new(error)).as_ptr() as *const u8
+        }}
+    }}
+}}
+"##)
+            }).collect::<Vec<_>>().join("\n")
+        };
+
+        let lifecycle = self.lifecycle_exports();
+
+        // Generate lib.rs - use minimal version if no routes (no bridge dependencies)
+        let lib_content = if has_routes {
+            format!(r#"// Auto-generated plugin library (with bridge support)
+pub mod plugin_mod;
+pub use plugin_mod::*;
+pub use api::ffi_http::free_string;
+
+/// Embedded frontend JavaScript (plugin.js)
+const EMBEDDED_FRONTEND: &str = "{escaped_frontend}";
+
+/// Embedded manifest (package.json)
+const EMBEDDED_MANIFEST: &str = "{escaped_manifest}";
+
+{lifecycle}
+#[no_mangle]
+pub extern "C" fn plugin_metadata() -> *const u8 {{
+    use api::{{Plugin, serde_json}};
+    let plugin = plugin_mod::{plugin_struct};
+    let metadata = plugin.metadata();
+    let json = serde_json::to_string(&metadata).unwrap_or_default();
+    Box::leak(Box::new(json)).as_ptr() as *const u8
+}}
+
+/// Returns the embedded manifest (package.json) as a null-terminated string
+#[no_mangle]
+pub extern "C" fn get_plugin_manifest() -> *const u8 {{
+    let manifest = EMBEDDED_MANIFEST.to_string();
+    let leaked = Box::leak(Box::new(manifest));
+    leaked.as_ptr()
+}}
+
+/// Returns the length of the embedded manifest
+#[no_mangle]
+pub extern "C" fn get_plugin_manifest_len() -> usize {{
+    EMBEDDED_MANIFEST.len()
+}}
+
+/// Returns the embedded frontend (plugin.js) as a null-terminated string
+#[no_mangle]
+pub extern "C" fn get_plugin_frontend() -> *const u8 {{
+    let frontend = EMBEDDED_FRONTEND.to_string();
+    let leaked = Box::leak(Box::new(frontend));
+    leaked.as_ptr()
+}}
+
+/// Returns the length of the embedded frontend
+#[no_mangle]
+pub extern "C" fn get_plugin_frontend_len() -> usize {{
+    EMBEDDED_FRONTEND.len()
+}}
+
+/// Returns whether this plugin has a frontend
+#[no_mangle]
+pub extern "C" fn has_frontend() -> bool {{
+    !EMBEDDED_FRONTEND.is_empty()
+}}
+
+/// Free a string allocated by this plugin
+#[no_mangle]
+pub extern "C" fn free_plugin_string(ptr: *mut u8) {{
+    if !ptr.is_null() {{
+        unsafe {{
+            let _ = std::ffi::CString::from_raw(ptr as *mut i8);
+        }}
+    }}
+}}
+
+{handler_wrappers}
+"#)
+        } else {
+            // Minimal version without bridge dependencies (no tokio, http, etc.)
+            format!(r#"// Auto-generated plugin library (minimal - no bridge)
+pub mod plugin_mod;
+pub use plugin_mod::*;
+
+/// Embedded frontend JavaScript (plugin.js)
+const EMBEDDED_FRONTEND: &str = "{escaped_frontend}";
+
+/// Embedded manifest (package.json)
+const EMBEDDED_MANIFEST: &str = "{escaped_manifest}";
+
+{lifecycle}
+#[no_mangle]
+pub extern "C" fn plugin_metadata() -> *const u8 {{
+    use api::{{Plugin, serde_json}};
+    let plugin = plugin_mod::{plugin_struct};
+    let metadata = plugin.metadata();
+    let json = serde_json::to_string(&metadata).unwrap_or_default();
+    Box::leak(Box::new(json)).as_ptr() as *const u8
+}}
+
+/// Returns the embedded manifest (package.json) as a null-terminated string
+#[no_mangle]
+pub extern "C" fn get_plugin_manifest() -> *const u8 {{
+    let manifest = EMBEDDED_MANIFEST.to_string();
+    let leaked = Box::leak(Box::new(manifest));
+    leaked.as_ptr()
+}}
+
+/// Returns the length of the embedded manifest
+#[no_mangle]
+pub extern "C" fn get_plugin_manifest_len() -> usize {{
+    EMBEDDED_MANIFEST.len()
+}}
+
+/// Returns the embedded frontend (plugin.js) as a null-terminated string
+#[no_mangle]
+pub extern "C" fn get_plugin_frontend() -> *const u8 {{
+    let frontend = EMBEDDED_FRONTEND.to_string();
+    let leaked = Box::leak(Box::new(frontend));
+    leaked.as_ptr()
+}}
+
+/// Returns the length of the embedded frontend
+#[no_mangle]
+pub extern "C" fn get_plugin_frontend_len() -> usize {{
+    EMBEDDED_FRONTEND.len()
+}}
+
+/// Returns whether this plugin has a frontend
+#[no_mangle]
+pub extern "C" fn has_frontend() -> bool {{
+    !EMBEDDED_FRONTEND.is_empty()
+}}
+
+/// Free a string allocated by this plugin
+#[no_mangle]
+pub extern "C" fn free_plugin_string(ptr: *mut u8) {{
+    if !ptr.is_null() {{
+        unsafe {{
+            let _ = std::ffi::CString::from_raw(ptr as *mut i8);
+        }}
+    }}
+}}
+"#)
+        };
+
+        // Split frontends list their chunks; get_plugin_frontend returns the loader stub
+        let chunks = self.frontend_chunks();
+        let lib_content = if chunks.is_empty() {
+            lib_content
+        } else {
+            let escaped_chunks = serde_json::to_string(&chunks)?.replace('\\', "\\\\").replace('"', "\\\"");
+            lib_content + &format!(r#"
+/// Frontend chunks (JSON array of paths under app/plugins/<plugin-id>/chunks/)
+const EMBEDDED_CHUNKS: &str = "{escaped_chunks}";
+
+/// Returns the frontend chunk manifest
+#[no_mangle]
+pub extern "C" fn get_plugin_chunks() -> *const u8 {{
+    let chunks = EMBEDDED_CHUNKS.to_string();
+    let leaked = Box::leak(Box::new(chunks));
+    leaked.as_ptr()
+}}
+
+/// Returns the length of the frontend chunk manifest
+#[no_mangle]
+pub extern "C" fn get_plugin_chunks_len() -> usize {{
+    EMBEDDED_CHUNKS.len()
+}}
+"#)
+        };
+
+        // --static plugins pin their own allocator rather than relying on the host's defaults
+        let lib_content = if self.static_link {
+            lib_content + r#"
+#[cfg(staticplugin)]
+#[global_allocator]
+static STATIC_PLUGIN_ALLOCATOR: std::alloc::System = std::alloc::System;
+"#
+        } else {
+            lib_content
+        };
+
+        fs::write(rust_build_dir.join("lib.rs"), lib_content)?;
+        Ok(())
+    }
+
+    /// Whether this build compiles Sentry in: --sentry-dsn on a native, generated lib.rs
+    fn uses_sentry(&self) -> bool {
+        self.sentry_dsn.is_some() && self.workspace_package.is_none() && !self.is_wasm_target()
+    }
+
+    /// plugin_init/plugin_start/plugin_stop for the native lib.rs. With --sentry-dsn, plugin_init
+    /// starts a Sentry client and session (its panic integration reports handler panics) and
+    /// plugin_stop ends the session and flushes pending events.
+    fn lifecycle_exports(&self) -> String {
+        if !self.uses_sentry() {
+            return r#"#[no_mangle]
+pub extern "C" fn plugin_init(_ffi_ctx: *const ()) -> i32 { 0 }
+
+#[no_mangle]
+pub extern "C" fn plugin_start(_ffi_ctx: *const ()) -> i32 { 0 }
+
+#[no_mangle]
+pub extern "C" fn plugin_stop() -> i32 { 0 }
+"#.to_string();
+        }
+        let environment = match self.sentry_environment {
+            Some(ref env) => format!("Some(\"{}\".into())", env),
+            None => "None".to_string(),
+        };
+        format!(r#"/// Keeps the Sentry client alive until plugin_stop
+static SENTRY_GUARD: std::sync::Mutex<Option<sentry::ClientInitGuard>> = std::sync::Mutex::new(None);
+
+#[no_mangle]
+pub extern "C" fn plugin_init(_ffi_ctx: *const ()) -> i32 {{
+    // The DSN comes from `webarcade build --sentry-dsn` at compile time
+    let guard = sentry::init((env!("WEBARCADE_SENTRY_DSN"), sentry::ClientOptions {{
+        release: sentry::release_name!(),
+        environment: {environment},
+        ..Default::default()
+    }}));
+    sentry::start_session();
+    if let Ok(mut slot) = SENTRY_GUARD.lock() {{
+        *slot = Some(guard);
+    }}
+    0
+}}
+
+#[no_mangle]
+pub extern "C" fn plugin_start(_ffi_ctx: *const ()) -> i32 {{ 0 }}
+
+#[no_mangle]
+pub extern "C" fn plugin_stop() -> i32 {{
+    sentry::end_session();
+    // Dropping the guard flushes queued events
+    if let Ok(mut slot) = SENTRY_GUARD.lock() {{
+        slot.take();
+    }}
+    0
+}}
+"#)
+    }
+
+    /// Generate lib.rs for the wasm-bindgen pass of --dual-target: the desktop entry points as
+    /// `#[wasm_bindgen]` functions on strings. Handlers take the request JSON and are async, so
+    /// they return a Promise of the FFI response JSON in the JS glue.
+    fn create_wasm_bindgen_lib_rs(&self, rust_build_dir: &Path, frontend_js: &str, manifest: &str, has_routes: bool) -> Result<()> {
+        let plugin_struct = self.get_plugin_struct_name();
+
+        let escaped_frontend = frontend_js.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n").replace('\r', "");
+        let escaped_manifest = manifest.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n").replace('\r', "");
+
+        let handler_wrappers = if !has_routes {
+            String::new()
+        } else {
+            let handlers = self.extract_handlers()?;
+            let wrappers = handlers.iter().map(|(handler_name, takes_request)| {
+                let handler_call = if *takes_request {
+                    format!("plugin_mod::router::{}(http_request.clone()).await", handler_name)
+                } else {
+                    format!("plugin_mod::router::{}().await", handler_name)
+                };
+
+                let to_response = handler_response_code("ffi_response", 4);
+                format!(r##"
+/// Takes the request JSON, resolves to the response JSON
+#[wasm_bindgen]
+pub async fn {handler_name}(request: String) -> String {{
+    use api::http::HttpRequest;
+
+    let _http_request = match HttpRequest::from_ffi_json(request.as_ptr(), request.len()) {{
+        Ok(r) => r,
+        Err(e) => {{
+            return ffi_response(400, Default::default(), Some(api::serde_json::json!({{"error": e}})), None);
+        }}
+    }};
+    #[allow(unused_variables)]
+    let http_request = _http_request;
+
+    let response = {handler_call};
+{to_response}
+}}
+"##)
+            }).collect::<Vec<_>>().join("\n");
+
+            format!(r#"
+/// An FFI response (the same JSON shape as the desktop ABI)
+fn ffi_response(
+    status: u16,
+    headers: std::collections::HashMap<String, String>,
+    body: Option<api::serde_json::Value>,
+    body_base64: Option<String>,
+) -> String {{
+    let mut response = api::serde_json::json!({{
+        "__ffi_response__": true,
+        "status": status,
+        "headers": headers,
+    }});
+    if let Some(body) = body {{
+        response["body"] = body;
+    }}
+    if let Some(body_base64) = body_base64 {{
+        response["body_base64"] = api::serde_json::Value::String(body_base64);
+    }}
+    response.to_string()
+}}
+{wrappers}"#)
+        };
+
+        let chunks = self.frontend_chunks();
+        let chunk_export = if chunks.is_empty() {
+            String::new()
+        } else {
+            let escaped_chunks = serde_json::to_string(&chunks)?.replace('\\', "\\\\").replace('"', "\\\"");
+            format!(r#"
+/// Frontend chunks (JSON array of paths under app/plugins/<plugin-id>/chunks/)
+#[wasm_bindgen]
+pub fn get_plugin_chunks() -> String {{
+    "{escaped_chunks}".to_string()
+}}
+"#)
+        };
+
+        let lib_content = format!(r#"// Auto-generated plugin library (wasm-bindgen - string exports for the web)
+pub mod plugin_mod;
+pub use plugin_mod::*;
+
+use wasm_bindgen::prelude::*;
+
+/// Embedded frontend JavaScript (plugin.js)
+const EMBEDDED_FRONTEND: &str = "{escaped_frontend}";
+
+/// Embedded manifest (package.json)
+const EMBEDDED_MANIFEST: &str = "{escaped_manifest}";
+
+#[wasm_bindgen]
+pub fn plugin_init() -> i32 {{ 0 }}
+
+#[wasm_bindgen]
+pub fn plugin_start() -> i32 {{ 0 }}
+
+#[wasm_bindgen]
+pub fn plugin_stop() -> i32 {{ 0 }}
+
+/// Plugin metadata JSON
+#[wasm_bindgen]
+pub fn plugin_metadata() -> String {{
+    use api::{{Plugin, serde_json}};
+    let plugin = plugin_mod::{plugin_struct};
+    let metadata = plugin.metadata();
+    serde_json::to_string(&metadata).unwrap_or_default()
+}}
+
+/// Embedded manifest (package.json)
+#[wasm_bindgen]
+pub fn get_plugin_manifest() -> String {{
+    EMBEDDED_MANIFEST.to_string()
+}}
+
+/// Embedded frontend (plugin.js)
+#[wasm_bindgen]
+pub fn get_plugin_frontend() -> String {{
+    EMBEDDED_FRONTEND.to_string()
+}}
+
+/// Returns whether this plugin has a frontend
+#[wasm_bindgen]
+pub fn has_frontend() -> bool {{
+    !EMBEDDED_FRONTEND.is_empty()
+}}
+{chunk_export}{handler_wrappers}"#);
+
+        fs::write(rust_build_dir.join("lib.rs"), lib_content)?;
+        Ok(())
+    }
+
+    /// Generate lib.rs for WASI targets. Exports take no pointers: requests are read from stdin
+    /// and strings and responses are written to stdout, so any WASI host can drive the module
+    /// through pipes. Hosts call the reactor's `_initialize` export (which runs
+    /// `__wasm_call_ctors`) once before any other export.
+    fn create_wasi_lib_rs(&self, rust_build_dir: &Path, frontend_js: &str, manifest: &str, has_routes: bool) -> Result<()> {
         let plugin_struct = self.get_plugin_struct_name();
 
-        // Escape the embedded strings for Rust
-        let escaped_frontend = frontend_js.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n").replace('\r', "");
-        let escaped_manifest = manifest.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n").replace('\r', "");
+        let escaped_frontend = frontend_js.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n").replace('\r', "");
+        let escaped_manifest = manifest.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n").replace('\r', "");
+
+        let handler_wrappers = if !has_routes {
+            String::new()
+        } else {
+            let handlers = self.extract_handlers()?;
+            let wrappers = handlers.iter().map(|(handler_name, takes_request)| {
+                let handler_call = if *takes_request {
+                    format!("plugin_mod::router::{}(http_request.clone()).await", handler_name)
+                } else {
+                    format!("plugin_mod::router::{}().await", handler_name)
+                };
+
+                let to_response = handler_response_code("write_response", 12);
+                format!(r##"
+/// Reads the request JSON from stdin and writes the response JSON to stdout
+#[no_mangle]
+pub extern "C" fn {handler_name}() -> i32 {{
+    use api::http::HttpRequest;
+
+    let mut request = Vec::new();
+    if std::io::stdin().read_to_end(&mut request).is_err() {{
+        return write_response(400, Default::default(), Some(api::serde_json::json!({{"error": "Failed to read request from stdin"}})), None);
+    }}
+
+    let result = std::panic::catch_unwind(|| {{
+        let _http_request = match HttpRequest::from_ffi_json(request.as_ptr(), request.len()) {{
+            Ok(r) => r,
+            Err(e) => {{
+                return write_response(400, Default::default(), Some(api::serde_json::json!({{"error": e}})), None);
+            }}
+        }};
+        #[allow(unused_variables)]
+        let http_request = _http_request;
+
+        // WASI has no I/O driver for tokio, so the runtime only drives the handler future
+        let rt = api::tokio::runtime::Builder::new_current_thread()
+            .build()
+            .expect("Failed to create handler runtime");
+        rt.block_on(async {{
+            let response = {handler_call};
+{to_response}
+        }})
+    }});
+
+    match result {{
+        Ok(code) => code,
+        Err(_) => write_response(500, Default::default(), Some(api::serde_json::json!({{"error": "Handler panicked"}})), None),
+    }}
+}}
+"##)
+            }).collect::<Vec<_>>().join("\n");
+
+            format!(r#"
+/// Write an FFI response (the same JSON shape as the desktop ABI) to stdout
+fn write_response(
+    status: u16,
+    headers: std::collections::HashMap<String, String>,
+    body: Option<api::serde_json::Value>,
+    body_base64: Option<String>,
+) -> i32 {{
+    let mut response = api::serde_json::json!({{
+        "__ffi_response__": true,
+        "status": status,
+        "headers": headers,
+    }});
+    if let Some(body) = body {{
+        response["body"] = body;
+    }}
+    if let Some(body_base64) = body_base64 {{
+        response["body_base64"] = api::serde_json::Value::String(body_base64);
+    }}
+    write_stdout(&response.to_string())
+}}
+{wrappers}"#)
+        };
+
+        let chunks = self.frontend_chunks();
+        let chunk_export = if chunks.is_empty() {
+            String::new()
+        } else {
+            let escaped_chunks = serde_json::to_string(&chunks)?.replace('\\', "\\\\").replace('"', "\\\"");
+            format!(r#"
+/// Frontend chunks (JSON array of paths under app/plugins/<plugin-id>/chunks/)
+const EMBEDDED_CHUNKS: &str = "{escaped_chunks}";
+
+/// Writes the frontend chunk manifest to stdout
+#[no_mangle]
+pub extern "C" fn get_plugin_chunks() -> i32 {{
+    write_stdout(EMBEDDED_CHUNKS)
+}}
+"#)
+        };
+
+        let lib_content = format!(r#"// Auto-generated plugin library (WASI - requests on stdin, responses on stdout)
+pub mod plugin_mod;
+pub use plugin_mod::*;
+
+#[allow(unused_imports)]
+use std::io::{{Read, Write}};
+
+/// Embedded frontend JavaScript (plugin.js)
+const EMBEDDED_FRONTEND: &str = "{escaped_frontend}";
+
+/// Embedded manifest (package.json)
+const EMBEDDED_MANIFEST: &str = "{escaped_manifest}";
+
+/// Write an export's result to stdout; the host reads its pipe after the call returns
+fn write_stdout(content: &str) -> i32 {{
+    let mut stdout = std::io::stdout();
+    match stdout.write_all(content.as_bytes()).and_then(|_| stdout.flush()) {{
+        Ok(()) => 0,
+        Err(_) => -1,
+    }}
+}}
+
+#[no_mangle]
+pub extern "C" fn plugin_init() -> i32 {{ 0 }}
+
+#[no_mangle]
+pub extern "C" fn plugin_start() -> i32 {{ 0 }}
+
+#[no_mangle]
+pub extern "C" fn plugin_stop() -> i32 {{ 0 }}
+
+/// Writes the plugin metadata JSON to stdout
+#[no_mangle]
+pub extern "C" fn plugin_metadata() -> i32 {{
+    use api::{{Plugin, serde_json}};
+    let plugin = plugin_mod::{plugin_struct};
+    let metadata = plugin.metadata();
+    write_stdout(&serde_json::to_string(&metadata).unwrap_or_default())
+}}
+
+/// Writes the embedded manifest (package.json) to stdout
+#[no_mangle]
+pub extern "C" fn get_plugin_manifest() -> i32 {{
+    write_stdout(EMBEDDED_MANIFEST)
+}}
+
+/// Writes the embedded frontend (plugin.js) to stdout
+#[no_mangle]
+pub extern "C" fn get_plugin_frontend() -> i32 {{
+    write_stdout(EMBEDDED_FRONTEND)
+}}
+
+/// Returns whether this plugin has a frontend
+#[no_mangle]
+pub extern "C" fn has_frontend() -> bool {{
+    !EMBEDDED_FRONTEND.is_empty()
+}}
+{chunk_export}{handler_wrappers}"#);
+
+        fs::write(rust_build_dir.join("lib.rs"), lib_content)?;
+        Ok(())
+    }
+
+    fn extract_handlers(&self) -> Result<Vec<(String, bool)>> {
+        let mut handlers: Vec<(String, bool)> = Vec::new();
+
+        for (_, handler) in read_plugin_routes(&self.plugin_dir) {
+            if !handlers.iter().any(|(h, _)| *h == handler) {
+                handlers.push((handler, false));
+            }
+        }
+
+        let router_path = self.plugin_dir.join("router.rs");
+        let router_content = if router_path.exists() {
+            fs::read_to_string(&router_path)?
+        } else {
+            String::new()
+        };
+
+        // A handler without a matching function would only surface as a confusing link error
+        let missing: Vec<&str> = handlers
+            .iter()
+            .map(|(name, _)| name.as_str())
+            .filter(|name| {
+                let pattern = format!(r"(?m)^(?:pub\s+)?async\s+fn\s+{}\s*\(", regex::escape(name));
+                !regex::Regex::new(&pattern).map(|re| re.is_match(&router_content)).unwrap_or(false)
+            })
+            .collect();
+        if !missing.is_empty() {
+            let message = format!(
+                "[routes] handler(s) not defined in router.rs: {}",
+                missing.join(", ")
+            );
+            if self.strict {
+                anyhow::bail!("{}", message);
+            }
+            self.warn(&message);
+        }
+
+        if !router_content.is_empty() {
+            for (handler_name, takes_request) in handlers.iter_mut() {
+                let pattern = format!(r"(?m)^pub\s+async\s+fn\s+{}\s*\(([^)]*)\)", regex::escape(handler_name));
+                if let Ok(re) = regex::Regex::new(&pattern) {
+                    if let Some(captures) = re.captures(&router_content) {
+                        if let Some(params) = captures.get(1) {
+                            let params_str = params.as_str().trim();
+                            *takes_request = !params_str.is_empty() &&
+                                (params_str.contains("HttpRequest") ||
+                                 params_str.contains("Request") ||
+                                 params_str.contains(":"));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(handlers)
+    }
+
+    fn get_plugin_struct_name(&self) -> String {
+        let parts: Vec<&str> = self.plugin_id.split(['_', '-']).collect();
+        let mut name = String::new();
+        for part in parts {
+            let mut chars = part.chars();
+            if let Some(first) = chars.next() {
+                name.push(first.to_uppercase().next().unwrap());
+                name.push_str(chars.as_str());
+            }
+        }
+        name.push_str("Plugin");
+        name
+    }
+
+    /// Make sure the cross-compilation target is installed, offering to add it with rustup.
+    /// Without rustup (e.g. a distro toolchain) the check is skipped and cargo reports any problem.
+    fn ensure_target_installed(&self, target: &str) -> Result<()> {
+        let output = match Command::new("rustup").args(["target", "list", "--installed"]).output() {
+            Ok(o) if o.status.success() => o,
+            _ => return Ok(()),
+        };
+        let installed = String::from_utf8_lossy(&output.stdout);
+        if installed.lines().any(|l| l.trim() == target) {
+            return Ok(());
+        }
+
+        if !self.auto_install_targets {
+            if !console::user_attended() {
+                anyhow::bail!(
+                    "Rust target '{}' is not installed. Run 'rustup target add {}' or pass --auto-install-targets",
+                    target, target
+                );
+            }
+            let install = Confirm::with_theme(&ColorfulTheme::default())
+                .with_prompt(format!("Rust target '{}' is not installed. Install it with rustup?", target))
+                .default(true)
+                .interact()?;
+            if !install {
+                anyhow::bail!("Rust target '{}' is not installed", target);
+            }
+        }
+
+        with_build_progress(|p| p.set_step(&self.plugin_id, &format!("Installing target {}...", target)));
+        let output = Command::new("rustup")
+            .args(["target", "add", target])
+            .output()
+            .context("Failed to run rustup target add")?;
+        if !output.status.success() {
+            anyhow::bail!(
+                "Failed to install Rust target '{}': {}",
+                target,
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        with_build_progress(|p| p.set_step(&self.plugin_id, "Compiling DLL..."));
+
+        Ok(())
+    }
+
+    fn compile_backend(&self, features: &[String]) -> Result<()> {
+        if let Some(ref target) = self.target {
+            self.ensure_target_installed(target)?;
+        }
+
+        // Spawn cargo with piped stderr to capture progress
+        let jobs_string = self.jobs.to_string();
+        let mut args = vec!["build", "--release", "--lib", "--jobs", &jobs_string];
+        if let Some(ref package) = self.workspace_package {
+            args.push("--package");
+            args.push(package);
+        }
+        let target_string;
+        if let Some(ref target) = self.target {
+            target_string = target.clone();
+            args.push("--target");
+            args.push(&target_string);
+        }
+        let features_string = features.join(",");
+        if !features.is_empty() {
+            args.push("--features");
+            args.push(&features_string);
+        }
+        if self.timings {
+            args.push("--timings=html");
+        }
+
+        let mut cargo = Command::new("cargo");
+        cargo.current_dir(self.cargo_dir()).args(&args);
+
+        // build.rs runs from the temporary rust_build/ copy; point it at the real sources
+        cargo.env("WEBARCADE_PLUGIN_DIR", &self.plugin_dir);
+
+        if self.reproducible {
+            match source_date_epoch(&self.plugin_dir) {
+                Some(epoch) => {
+                    cargo.env("SOURCE_DATE_EPOCH", epoch);
+                }
+                None => self.warn("--reproducible: no git commit to take SOURCE_DATE_EPOCH from; build-time timestamps will vary"),
+            }
+        }
+
+        if self.incremental && self.workspace_package.is_none() {
+            cargo.env("CARGO_TARGET_DIR", self.cargo_target_dir());
+        }
+
+        // Workspace members have no generated lib.rs, so they embed the bundled frontend themselves
+        if self.workspace_package.is_some() {
+            cargo.env("WEBARCADE_FRONTEND_JS", self.build_dir.join("plugin.js"));
+        }
+
+        // Share compiled dependencies across plugins and CI runs via sccache
+        // (an explicit RUSTC_WRAPPER in the environment wins)
+        if let Some(ref sccache) = self.sccache {
+            if std::env::var_os("RUSTC_WRAPPER").is_none() {
+                cargo.env("RUSTC_WRAPPER", sccache);
+            }
+        }
+
+        // With --timings, cargo's own output is shown as-is instead of a progress display
+        if self.timings {
+            let status = cargo.status().context("Failed to run cargo build")?;
+            if !status.success() {
+                anyhow::bail!("Cargo build failed (see the output above)");
+            }
+            if let Some(ref sccache) = self.sccache {
+                self.report_sccache_stats(sccache);
+            }
+            self.copy_timing_report()?;
+            return self.copy_compiled_binary();
+        }
+
+        let mut child = cargo
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .context("Failed to run cargo build")?;
+
+        // Read stderr to parse progress (cargo outputs progress to stderr)
+        let stderr = child.stderr.take().expect("Failed to capture stderr");
+        let reader = std::io::BufReader::new(stderr);
+
+        let mut compiled_count = 0usize;
+        let mut total_crates = 0usize;
+        let mut error_output = String::new();
+        let mut last_crate_name = String::new();
+
+        for line in reader.lines() {
+            let line = match line {
+                Ok(l) => l,
+                Err(_) => continue,
+            };
+
+            // Parse "Compiling crate_name v0.1.0" lines
+            if line.trim_start().starts_with("Compiling ") {
+                compiled_count += 1;
+                // Extract crate name from "Compiling crate_name v0.1.0 (path)"
+                let parts: Vec<&str> = line.trim_start().splitn(3, ' ').collect();
+                if parts.len() >= 2 {
+                    last_crate_name = parts[1].to_string();
+                }
+
+                // Estimate total based on typical plugin build
+                if total_crates == 0 {
+                    total_crates = 150; // Default estimate
+                }
+                if compiled_count > total_crates {
+                    total_crates = compiled_count + 10; // Adjust if we exceeded estimate
+                }
+
+                // Update progress display
+                let current = compiled_count;
+                let total = total_crates;
+                let crate_name = last_crate_name.clone();
+                with_build_progress(|p| {
+                    p.update_cargo_progress(current, total, Some(crate_name));
+                });
+            }
+            // Parse "Building [=====> ] N/M: crate" progress lines
+            else if line.contains("Building") && line.contains("/") {
+                // Try to extract N/M from progress line like "Building [=====> ] 50/100: crate"
+                if let Some(progress_part) = line.split(']').nth(1) {
+                    let parts: Vec<&str> = progress_part.trim().split(':').collect();
+                    if !parts.is_empty() {
+                        let nums: Vec<&str> = parts[0].trim().split('/').collect();
+                        if nums.len() == 2 {
+                            if let (Ok(current), Ok(total)) = (nums[0].parse::<usize>(), nums[1].parse::<usize>()) {
+                                total_crates = total;
+                                compiled_count = current;
+                                if parts.len() > 1 {
+                                    last_crate_name = parts[1].trim().to_string();
+                                }
+                                let c = compiled_count;
+                                let t = total_crates;
+                                let crate_name = last_crate_name.clone();
+                                with_build_progress(|p| {
+                                    p.update_cargo_progress(c, t, Some(crate_name));
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+            // Capture error lines
+            else if line.contains("error") || line.contains("Error") {
+                error_output.push_str(&line);
+                error_output.push('\n');
+            }
+        }
+
+        // Wait for the process to complete
+        let status = child.wait().context("Failed to wait for cargo build")?;
+
+        if !status.success() {
+            if error_output.is_empty() {
+                error_output = "Cargo build failed (unknown error)".to_string();
+            }
+            anyhow::bail!("Cargo build failed:\n{}", error_output);
+        }
+
+        if let Some(ref sccache) = self.sccache {
+            self.report_sccache_stats(sccache);
+        }
+
+        // Copy compiled binary
+        self.copy_compiled_binary()
+    }
+
+    /// Print sccache hit/miss counts after a compile
+    fn report_sccache_stats(&self, sccache: &Path) {
+        let output = match Command::new(sccache).arg("--show-stats").output() {
+            Ok(o) if o.status.success() => o,
+            _ => return,
+        };
+        let stats = String::from_utf8_lossy(&output.stdout);
+
+        let stat = |label: &str| -> Option<String> {
+            stats.lines()
+                .find(|l| l.trim_start().starts_with(label))
+                .and_then(|l| l.split_whitespace().last())
+                .map(String::from)
+        };
+        let summary = format!("sccache: {} hits, {} misses",
+            stat("Cache hits").unwrap_or_else(|| "?".to_string()),
+            stat("Cache misses").unwrap_or_else(|| "?".to_string())
+        );
+
+        report_summary(&self.plugin_id, &summary);
+    }
+
+    /// Run wasm-opt on the compiled module in place and report the size change
+    fn optimize_wasm(&self, level: &str) -> Result<()> {
+        let Some(wasm_opt) = find_on_path("wasm-opt") else {
+            self.warn("--strip-wasm: wasm-opt not found, module left unoptimized (get Binaryen from https://github.com/WebAssembly/binaryen/releases)");
+            return Ok(());
+        };
+
+        let module = self.compiled_artifact();
+        let before = fs::metadata(&module)?.len();
+        let output = Command::new(&wasm_opt)
+            .arg(format!("-O{}", level))
+            .arg("-o")
+            .arg(&module)
+            .arg(&module)
+            .output()
+            .context("Failed to run wasm-opt")?;
+        if !output.status.success() {
+            anyhow::bail!("wasm-opt failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        let after = fs::metadata(&module)?.len();
+
+        let change = (after as f64 - before as f64) / before.max(1) as f64 * 100.0;
+        let summary = format!(
+            "WASM optimized: {} KB → {} KB ({:+.0}%)",
+            before.div_ceil(1024),
+            after.div_ceil(1024),
+            change
+        );
+        report_summary(&self.plugin_id, &summary);
+        Ok(())
+    }
+
+    /// The library cargo just produced, before it is installed
+    fn compiled_artifact(&self) -> PathBuf {
+        // Workspace members' libraries stay in the workspace target directory
+        if self.workspace_package.is_some() {
+            self.cargo_release_dir().join(self.lib_name())
+        } else {
+            self.build_dir.join(self.lib_name())
+        }
+    }
+
+    /// Run twiggy and/or bloaty on the compiled library, print the ten largest items and
+    /// keep the full output in build/<plugin-id>/size-analysis.txt
+    fn analyze_binary(&self, tool: &str) -> Result<()> {
+        let tools: Vec<&str> = match tool {
+            "both" => vec!["twiggy", "bloaty"],
+            "auto" => match ["twiggy", "bloaty"].into_iter().find(|t| find_on_path(t).is_some()) {
+                Some(found) => vec![found],
+                None => vec!["twiggy", "bloaty"],
+            },
+            other => vec![other],
+        };
+        let missing: Vec<&str> = tools.iter().copied().filter(|t| find_on_path(t).is_none()).collect();
+        for name in &missing {
+            let (repo, install, columns) = size_tool_info(name);
+            self.warn(&format!(
+                "--analyze-binary: {} not found ({}, {}); it reports {}",
+                name, repo, install, columns
+            ));
+        }
+
+        let library = self.compiled_artifact();
+        let total = fs::metadata(&library)?.len();
+        let mut archive = format!("Size analysis of {} ({} bytes)\n", library.display(), total);
+        let mut reports = Vec::new();
+        for name in tools.into_iter().filter(|t| !missing.contains(t)) {
+            let (raw, rows) = run_size_tool(name, &library, total)?;
+            archive.push_str(&format!("\n== {} ==\n{}\n", name, raw.trim_end()));
+            reports.push((name, rows));
+        }
+        if reports.is_empty() {
+            return Ok(());
+        }
+        let report_path = self.build_dir.join("size-analysis.txt");
+        fs::write(&report_path, archive)?;
+
+        let relative = report_path.strip_prefix(&self.repo_root).unwrap_or(&report_path).display().to_string();
+        let summary = format!("Size analysis written to {}", relative);
+        if !in_build_progress() {
+            for (name, rows) in reports {
+                println!("  {} Largest items ({}):", style("→").dim(), name);
+                println!("      {:>10}  {:>7}  Item", "Bytes", "%");
+                for row in rows.iter().take(10) {
+                    println!("      {:>10}  {:>6.2}%  {}", row.bytes, row.percent, style(&row.name).dim());
+                }
+            }
+        }
+        report_summary(&self.plugin_id, &summary);
+        Ok(())
+    }
+
+    /// Precompile the wasm module to a `.cwasm` native code cache next to it with `wasmtime compile`
+    fn compile_aot(&self) -> Result<()> {
+        let Some(wasmtime) = find_on_path("wasmtime") else {
+            self.warn("--aot: wasmtime not found, no AOT cache written (install it from https://wasmtime.dev)");
+            return Ok(());
+        };
+
+        let module = self.compiled_artifact();
+        let output = Command::new(&wasmtime)
+            .arg("compile")
+            .arg("-o")
+            .arg(module.with_extension("cwasm"))
+            .arg(&module)
+            .output()
+            .context("Failed to run wasmtime compile")?;
+        if !output.status.success() {
+            anyhow::bail!("wasmtime compile failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(())
+    }
+
+    /// Copy cargo's HTML timing report to build/<plugin-id>/timing.html
+    fn copy_timing_report(&self) -> Result<()> {
+        let target_dir = self.cargo_target_dir();
+        // Newer cargo writes reports under cargo-timings/, older versions to the target root
+        let report = [
+            target_dir.join("cargo-timings").join("cargo-timing.html"),
+            target_dir.join("cargo-timing.html"),
+        ]
+        .into_iter()
+        .find(|path| path.exists())
+        .with_context(|| format!("cargo-timing.html not found in {}", target_dir.display()))?;
+        fs::copy(&report, self.build_dir.join("timing.html"))?;
+        Ok(())
+    }
+
+    fn copy_compiled_binary(&self) -> Result<()> {
+        // install_dll takes workspace members' libraries straight from the workspace target directory
+        if self.workspace_package.is_some() {
+            return Ok(());
+        }
+
+        let lib_name = self.lib_name();
+
+        let src_path = self.cargo_release_dir().join(&lib_name);
+        if src_path.exists() {
+            let dest_path = self.build_dir.join(&lib_name);
+            fs::copy(&src_path, &dest_path)?;
+            Ok(())
+        } else {
+            anyhow::bail!("Compiled library not found: {}", src_path.display())
+        }
+    }
+
+    fn bundle_frontend(&self) -> Result<()> {
+        let has_frontend = self.plugin_dir.join("index.jsx").exists()
+            || self.plugin_dir.join("index.js").exists();
+
+        if !has_frontend {
+            return Ok(());
+        }
+
+        // Install dependencies if needed
+        self.install_npm_dependencies()?;
+
+        // Find bundler script
+        let bundler_script = self.repo_root.join("app").join("scripts").join("build.js");
+
+        if !bundler_script.exists() {
+            println!("    Warning: Frontend bundler not found at {}", bundler_script.display());
+            return Ok(());
+        }
+
+        let plugin_dir_str = self.plugin_dir.to_string_lossy();
+        let build_dir_str = self.build_dir.to_string_lossy();
+        let metafile = self.build_dir.join("metafile.json");
+        let chunks_dir = self.build_dir.join("chunks");
+        let mut bundler_args = vec![
+            plugin_dir_str.to_string(),
+            build_dir_str.to_string(),
+        ];
+        // The metafile path goes through the environment, which older bundler scripts ignore;
+        // whether one was written is decided by the file itself
+        let _ = fs::remove_file(&metafile);
+        if self.code_split {
+            bundler_args.push("--splitting".to_string());
+            bundler_args.push(chunks_dir.to_string_lossy().to_string());
+        }
+
+        // --define constants reach the bundler as DEFINE_<KEY> environment variables
+        let define_vars: Vec<(String, &String)> = self.defines
+            .iter()
+            .map(|(key, value)| (format!("DEFINE_{}", key), value))
+            .collect();
+
+        let output = if Command::new("bun").arg("--version").output().is_ok() {
+            Command::new("bun")
+                .arg("run")
+                .arg(&bundler_script)
+                .args(&bundler_args)
+                .envs(define_vars.iter().map(|(k, v)| (k, v)))
+                .env("WEBARCADE_METAFILE", &metafile)
+                .output()
+                .context("Failed to run bundler with bun")?
+        } else {
+            Command::new("node")
+                .arg(&bundler_script)
+                .args(&bundler_args)
+                .envs(define_vars.iter().map(|(k, v)| (k, v)))
+                .env("WEBARCADE_METAFILE", &metafile)
+                .output()
+                .context("Failed to run bundler with node")?
+        };
+
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            anyhow::bail!("Frontend bundling failed: {}", stderr);
+        }
+
+        if self.code_split {
+            if chunks_dir.join("plugin.js").exists() {
+                // plugin.js becomes a stub that loads the split entry from the installed chunks.
+                // It lists every chunk so they are fetched in parallel instead of one import at a time.
+                let base = format!("/plugins/{}/chunks/", self.plugin_id);
+                let chunks = serde_json::to_string(&self.frontend_chunks())?;
+                let stub = format!(
+                    "// Loader stub generated by `webarcade build --code-split`\n\
+                     export const chunks = {chunks};\n\
+                     if (typeof document !== \"undefined\") {{\n\
+                     \x20 for (const chunk of chunks) {{\n\
+                     \x20   const link = document.createElement(\"link\");\n\
+                     \x20   link.rel = \"modulepreload\";\n\
+                     \x20   link.href = \"{base}\" + chunk;\n\
+                     \x20   document.head.appendChild(link);\n\
+                     \x20 }}\n\
+                     }}\n\
+                     export * from \"{base}plugin.js\";\n\
+                     const module = await import(\"{base}plugin.js\");\n\
+                     export default module.default;\n"
+                );
+                fs::write(self.build_dir.join("plugin.js"), stub)?;
+            } else {
+                self.warn("--code-split: the bundler didn't write chunks/plugin.js (does app/scripts/build.js support --splitting?); using a single bundle");
+                let _ = fs::remove_dir_all(&chunks_dir);
+            }
+        }
+
+        // The report is best-effort
+        let metafile_content = fs::read_to_string(&metafile).ok();
+        if metafile_content.is_none() && self.bundle_analyze {
+            self.warn("--bundle-analyze: app/scripts/build.js didn't write a metafile to WEBARCADE_METAFILE; no bundle-meta.json written");
+        }
+        if let Some(content) = metafile_content {
+            if let Ok(meta) = serde_json::from_str::<serde_json::Value>(&content) {
+                self.report_bundle_stats(&meta);
+            }
+            if self.bundle_analyze {
+                fs::write(self.build_dir.join("bundle-meta.json"), &content)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Summarize an esbuild metafile: output size, included modules and tree-shaken modules
+    fn report_bundle_stats(&self, meta: &serde_json::Value) {
+        let empty = serde_json::Map::new();
+        let outputs = meta.get("outputs").and_then(|o| o.as_object()).unwrap_or(&empty);
+        let inputs = meta.get("inputs").and_then(|i| i.as_object()).unwrap_or(&empty);
+
+        let mut total_bytes = 0u64;
+        let mut included: HashMap<&str, u64> = HashMap::new();
+        for (path, output) in outputs {
+            if path.ends_with(".map") {
+                continue;
+            }
+            total_bytes += output.get("bytes").and_then(|b| b.as_u64()).unwrap_or(0);
+            for (input, info) in output.get("inputs").and_then(|i| i.as_object()).into_iter().flatten() {
+                let bytes = info.get("bytesInOutput").and_then(|b| b.as_u64()).unwrap_or(0);
+                if bytes > 0 {
+                    *included.entry(input.as_str()).or_default() += bytes;
+                }
+            }
+        }
+        let unused = inputs.keys().filter(|path| !included.contains_key(path.as_str())).count();
+
+        let summary = format!(
+            "Frontend: {:.1} KB ({} modules, {} unused)",
+            total_bytes as f64 / 1024.0,
+            included.len(),
+            unused
+        );
+
+        report_summary(&self.plugin_id, &summary);
+        if !in_build_progress() {
+            let mut largest: Vec<(&str, u64)> = included.into_iter().collect();
+            largest.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+            for (path, bytes) in largest.into_iter().take(3) {
+                println!("      {:>8.1} KB  {}", bytes as f64 / 1024.0, style(path).dim());
+            }
+        }
+    }
+
+    fn install_npm_dependencies(&self) -> Result<()> {
+        let package_json_path = self.plugin_dir.join("package.json");
+        if !package_json_path.exists() {
+            return Ok(());
+        }
+
+        let content = fs::read_to_string(&package_json_path)?;
+        let json: serde_json::Value = serde_json::from_str(&content)?;
+
+        let has_deps = json.get("dependencies").and_then(|d| d.as_object()).map(|o| !o.is_empty()).unwrap_or(false);
+        let has_dev_deps = json.get("devDependencies").and_then(|d| d.as_object()).map(|o| !o.is_empty()).unwrap_or(false);
+
+        if !has_deps && !has_dev_deps {
+            return Ok(());
+        }
+
+        // Capture output to avoid cluttering progress display
+        let output = if Command::new("bun").arg("--version").output().is_ok() {
+            Command::new("bun")
+                .arg("install")
+                .current_dir(&self.plugin_dir)
+                .output()
+        } else {
+            Command::new("npm")
+                .arg("install")
+                .current_dir(&self.plugin_dir)
+                .output()
+        };
+
+        if let Ok(o) = output {
+            if !o.status.success() {
+                // Silently continue - npm install failures are often non-critical
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Files the bundler split the frontend into, relative to build/<plugin-id>/chunks/
+    fn frontend_chunks(&self) -> Vec<String> {
+        let chunks_dir = self.build_dir.join("chunks");
+        let mut chunks: Vec<String> = WalkDir::new(&chunks_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter_map(|e| e.path().strip_prefix(&chunks_dir).ok().map(|p| p.to_string_lossy().replace('\\', "/")))
+            .collect();
+        chunks.sort();
+        chunks
+    }
+
+    /// Install frontend chunks to app/plugins/<plugin-id>/chunks/, replacing any from an earlier build
+    fn install_chunks(&self) -> Result<()> {
+        let dest_dir = self.dist_plugins_dir.join(&self.plugin_id).join("chunks");
+        if dest_dir.exists() {
+            fs::remove_dir_all(&dest_dir)?;
+        }
+        let chunks = self.frontend_chunks();
+        if chunks.is_empty() {
+            // Remove the plugin's directory too if the chunks were all it held
+            let _ = fs::remove_dir(self.dist_plugins_dir.join(&self.plugin_id));
+            return Ok(());
+        }
+        for chunk in &chunks {
+            let dest = dest_dir.join(chunk);
+            if let Some(parent) = dest.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::copy(self.build_dir.join("chunks").join(chunk), &dest)?;
+        }
+        Ok(())
+    }
+
+    /// Save the generated lib.rs exports to `<plugin-id>.exports.json` in app/plugins/, warning
+    /// when symbols from the previous build are gone: an app that still has the old library
+    /// loaded may call them and crash
+    fn record_exports(&self) -> Result<()> {
+        let lib_rs = fs::read_to_string(self.build_dir.join("rust_build").join("lib.rs"))?;
+        let mut exports = generated_exports(&lib_rs);
+        exports.sort();
+        exports.dedup();
+
+        let sidecar = self.dist_plugins_dir.join(format!("{}.exports.json", self.plugin_id));
+        let previous: Vec<String> = fs::read_to_string(&sidecar)
+            .ok()
+            .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+            .and_then(|doc| serde_json::from_value(doc["exports"].clone()).ok())
+            .unwrap_or_default();
+        let removed: Vec<&String> = previous.iter().filter(|name| !exports.contains(name)).collect();
+        if !removed.is_empty() {
+            let added: Vec<&String> = exports.iter().filter(|name| !previous.contains(name)).collect();
+            let mut notice = format!(
+                "ABI change in '{}': removed export{} {}",
+                self.plugin_id,
+                if removed.len() == 1 { "" } else { "s" },
+                removed.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", ")
+            );
+            if !added.is_empty() {
+                notice.push_str(&format!(" (added {})", added.iter().map(|s| s.as_str()).collect::<Vec<_>>().join(", ")));
+            }
+            notice.push_str(". Restart the app; a running instance still has the old library loaded.");
+            report_notice(notice);
+        }
+
+        let content = serde_json::to_string_pretty(&serde_json::json!({ "exports": exports }))?;
+        write_atomic(&sidecar, &(content + "\n"))?;
+        Ok(())
+    }
+
+    /// --reproducible: add rustflags that keep machine-specific data out of the library. Cargo's
+    /// per-crate metadata gets a value derived from the plugin ID, and the checkout, cargo home
+    /// and both target dirs (see `verify_reproducible`) are remapped to fixed paths. Later
+    /// remappings win, so the target dirs come after the repo root that contains them.
+    fn apply_reproducible_flags(&self, rust_build_dir: &Path) -> Result<()> {
+        let triple = match self.target {
+            Some(ref target) => target.clone(),
+            None => host_target_triple()?,
+        };
+        let metadata = format!("{:x}", Sha256::digest(self.plugin_id.as_bytes()));
+        let mut flags = vec!["-C".to_string(), format!("metadata={}", &metadata[..16])];
+        let cargo_home = std::env::var_os("CARGO_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE")).map(|home| PathBuf::from(home).join(".cargo")));
+        let mut remaps = vec![(self.repo_root.clone(), "/repo")];
+        if let Some(cargo_home) = cargo_home {
+            remaps.push((cargo_home, "/cargo"));
+        }
+        remaps.push((self.cargo_target_dir(), "/target"));
+        remaps.push((self.reproducible_target_dir(), "/target"));
+        for (from, to) in remaps {
+            flags.push(format!("--remap-path-prefix={}={}", from.display(), to));
+        }
+
+        let config_path = rust_build_dir.join(".cargo").join("config.toml");
+        let config = fs::read_to_string(&config_path)?;
+        fs::write(&config_path, add_target_rustflags(&config, &triple, &flags)?)?;
+        Ok(())
+    }
+
+    /// Target directory of the second --reproducible compile
+    fn reproducible_target_dir(&self) -> PathBuf {
+        self.build_dir.join("repro-target")
+    }
+
+    /// Compile the backend again from scratch in its own target directory and compare the two
+    /// libraries. If they differ, list the symbols only one of them has (from `nm`).
+    fn verify_reproducible(&self, features: &[String]) -> Result<()> {
+        let repro_target = self.reproducible_target_dir();
+        let mut command = Command::new("cargo");
+        command
+            .current_dir(self.cargo_dir())
+            .args(["build", "--release", "--lib", "--jobs", &self.jobs.to_string()])
+            .env("CARGO_TARGET_DIR", &repro_target)
+            .env("WEBARCADE_PLUGIN_DIR", &self.plugin_dir);
+        if let Some(ref target) = self.target {
+            command.args(["--target", target]);
+        }
+        if !features.is_empty() {
+            command.args(["--features", &features.join(",")]);
+        }
+        if let Some(epoch) = source_date_epoch(&self.plugin_dir) {
+            command.env("SOURCE_DATE_EPOCH", epoch);
+        }
+        let output = command.output().context("Failed to run cargo build for --reproducible")?;
+        if !output.status.success() {
+            anyhow::bail!("Second --reproducible build failed:\n{}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+
+        let first = self.compiled_artifact();
+        let mut second = repro_target;
+        if let Some(ref target) = self.target {
+            second = second.join(target);
+        }
+        let second = second.join("release").join(self.lib_name());
+        let first_hash = format!("{:x}", Sha256::digest(fs::read(&first)?));
+        let second_hash = format!("{:x}", Sha256::digest(fs::read(&second)?));
+
+        if first_hash == second_hash {
+            let summary = format!("Reproducible: SHA-256 {}", &first_hash[..16]);
+            report_success(&self.plugin_id, &summary);
+            return Ok(());
+        }
+
+        let mut message = format!(
+            "'{}' is not reproducible: two builds gave SHA-256 {} and {}",
+            self.plugin_id, &first_hash[..16], &second_hash[..16]
+        );
+        let symbols = |lib: &Path| -> Option<Vec<String>> {
+            let output = Command::new("nm").arg(lib).output().ok().filter(|o| o.status.success())?;
+            // "<address> <type> <name>": addresses shift with any change, so only type and name count
+            let mut lines: Vec<String> = String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .map(|line| line.split_whitespace().rev().take(2).collect::<Vec<_>>().into_iter().rev().collect::<Vec<_>>().join(" "))
+                .collect();
+            lines.sort();
+            Some(lines)
+        };
+        match (symbols(&first), symbols(&second)) {
+            (Some(first_symbols), Some(second_symbols)) => {
+                let diff: Vec<String> = first_symbols.iter()
+                    .filter(|s| second_symbols.binary_search(s).is_err())
+                    .map(|s| format!("- {}", s))
+                    .chain(second_symbols.iter().filter(|s| first_symbols.binary_search(s).is_err()).map(|s| format!("+ {}", s)))
+                    .collect();
+                if diff.is_empty() {
+                    message.push_str("\nThe symbol tables match, so the difference is in code or data (try diffoscope)");
+                } else {
+                    message.push_str("\nSymbol table differences (nm):");
+                    for line in diff.iter().take(30) {
+                        message.push_str(&format!("\n  {}", line));
+                    }
+                    if diff.len() > 30 {
+                        message.push_str(&format!("\n  ... and {} more", diff.len() - 30));
+                    }
+                }
+            }
+            _ => message.push_str("\n(install binutils for an nm diff of the symbol tables)"),
+        }
+        anyhow::bail!(message)
+    }
+
+    /// Run the wasm-bindgen CLI on the compiled module for --dual-target, writing
+    /// `<plugin-id>_bg.wasm` and its `<plugin-id>.js` glue to app/plugins/
+    fn install_wasm_bindgen(&self) -> Result<PathBuf> {
+        let module = self.compiled_artifact();
+        if !module.exists() {
+            anyhow::bail!("Compiled module not found: {}", module.display());
+        }
+        let output = Command::new("wasm-bindgen")
+            .arg(&module)
+            .args(["--target", "web", "--no-typescript", "--out-name", &self.plugin_id, "--out-dir"])
+            .arg(&self.dist_plugins_dir)
+            .output()
+            .context("Failed to run wasm-bindgen")?;
+        if !output.status.success() {
+            anyhow::bail!("wasm-bindgen failed: {}", String::from_utf8_lossy(&output.stderr).trim());
+        }
+        Ok(self.dist_plugins_dir.join(format!("{}_bg.wasm", self.plugin_id)))
+    }
+
+    /// File name of the library in app/plugins/
+    fn installed_lib_name(&self) -> String {
+        // Destination uses plugin ID (may have hyphens) for loader compatibility
+        library_file_name(&self.plugin_id, self.target.as_deref())
+    }
+
+    fn install_dll(&self) -> Result<PathBuf> {
+        // Source uses Rust naming (underscores)
+        let lib_name = self.lib_name();
+        let src_path = self.build_dir.join(&lib_name);
+        if self.workspace_package.is_some() {
+            // Stage the member's library in the build directory, leaving the shared target dir untouched
+            let built = self.cargo_release_dir().join(&lib_name);
+            if !built.exists() {
+                anyhow::bail!("Compiled library not found: {}", built.display());
+            }
+            fs::create_dir_all(&self.build_dir)?;
+            fs::copy(&built, &src_path)
+                .with_context(|| format!("Failed to copy {} to {}", built.display(), src_path.display()))?;
+        }
+        if !src_path.exists() {
+            anyhow::bail!("Compiled library not found: {}", src_path.display());
+        }
+
+        // Post-process before installing, so a failing post-processor leaves the installed plugin alone
+        if let Some(command) = read_postprocess(&self.plugin_dir)?.dll {
+            with_build_progress(|p| p.set_step(&self.plugin_id, "Post-processing DLL..."));
+            self.run_postprocess("dll", &command, &src_path)?;
+        }
+        self.check_max_size(&src_path)?;
+
+        let dest_path = self.dist_plugins_dir.join(self.installed_lib_name());
+        if let Err(e) = fs::copy(&src_path, &dest_path) {
+            if e.kind() == std::io::ErrorKind::PermissionDenied {
+                anyhow::bail!(
+                    "Cannot overwrite {}: the file is locked, most likely by a running app that has the plugin loaded. \
+                     Close the app (or detach the debugger) and build again.",
+                    dest_path.display()
+                );
+            }
+            return Err(e).with_context(|| format!("Failed to copy {} to {}", src_path.display(), dest_path.display()));
+        }
+
+        if self.workspace_package.is_none() {
+            self.record_exports()?;
+        }
+
+        // The AOT cache goes alongside the module; a stale one from an earlier --aot build is removed
+        let src_aot = src_path.with_extension("cwasm");
+        let dest_aot = dest_path.with_extension("cwasm");
+        if self.aot && src_aot.exists() {
+            fs::copy(&src_aot, &dest_aot)
+                .with_context(|| format!("Failed to copy {} to {}", src_aot.display(), dest_aot.display()))?;
+        } else if dest_aot.exists() {
+            fs::remove_file(&dest_aot)?;
+        }
+
+        Ok(dest_path)
+    }
+
+    /// --max-size-kb: fail while the artifact is still in the build directory, so an
+    /// oversized build never replaces the installed one
+    fn check_max_size(&self, artifact: &Path) -> Result<()> {
+        let Some(max_kb) = self.max_size_kb else {
+            return Ok(());
+        };
+        let size = fs::metadata(artifact).map(|m| m.len()).unwrap_or(0);
+        if size > max_kb * 1024 {
+            anyhow::bail!(
+                "{} is {} KB, exceeding the --max-size-kb limit of {} KB; it was not installed",
+                artifact.file_name().unwrap_or_default().to_string_lossy(),
+                size.div_ceil(1024),
+                max_kb
+            );
+        }
+        Ok(())
+    }
 
-        // Only generate handler wrappers if plugin has routes
-        let handler_wrappers = if !has_routes {
-            String::new()
+    fn create_manifest(&self) -> Result<String> {
+        let package_json_path = self.plugin_dir.join("package.json");
+
+        let mut package_json = if package_json_path.exists() {
+            let content = fs::read_to_string(&package_json_path)?;
+            serde_json::from_str::<serde_json::Value>(&content)?
         } else {
-            let handlers = self.extract_handlers()?;
-            handlers.iter().map(|(handler_name, takes_request)| {
-            let handler_call = if *takes_request {
-                format!("plugin_mod::router::{}(http_request.clone()).await", handler_name)
+            serde_json::json!({
+                "name": self.plugin_id,
+                "version": "1.0.0"
+            })
+        };
+
+        // Links declared only in plugin.toml go into the manifest too
+        let links = PluginLinks::read(&self.plugin_dir)?;
+        let fields = [
+            ("homepage", links.homepage.map(serde_json::Value::from)),
+            ("repository", links.repository.map(serde_json::Value::from)),
+            ("license", links.license.map(serde_json::Value::from)),
+            ("keywords", Some(links.keywords).filter(|k| !k.is_empty()).map(serde_json::Value::from)),
+        ];
+        for (key, value) in fields {
+            if let (Some(value), true) = (value, package_json.get(key).is_none()) {
+                package_json[key] = value;
+            }
+        }
+
+        let routes = self.extract_routes()?;
+
+        package_json["webarcade"] = serde_json::json!({
+            "id": self.plugin_id,
+            "routes": routes
+        });
+
+        Ok(serde_json::to_string_pretty(&package_json)?)
+    }
+
+    fn extract_routes(&self) -> Result<Vec<serde_json::Value>> {
+        let mut routes = Vec::new();
+
+        for (key, handler) in read_plugin_routes(&self.plugin_dir) {
+            let parts: Vec<&str> = key.splitn(2, ' ').collect();
+            if parts.len() == 2 {
+                routes.push(serde_json::json!({
+                    "method": parts[0],
+                    "path": parts[1],
+                    "handler": handler
+                }));
+            }
+        }
+
+        Ok(routes)
+    }
+}
+
+// ============================================================================
+// AUDIT - Check plugin dependencies against the RustSec advisory database
+// ============================================================================
+
+/// One advisory, merged across every plugin that pulls in an affected crate
+struct AuditFinding {
+    title: String,
+    /// RUSTSEC advisory ID
+    advisory_id: String,
+    /// "vulnerability", or the warning kind (e.g. "unmaintained")
+    kind: String,
+    /// Affected crates as `name version`
+    packages: BTreeSet<String>,
+    plugins: BTreeSet<String>,
+    patched: Vec<String>,
+}
+
+/// Findings keyed by CVE ID (or the RUSTSEC ID when no CVE is assigned)
+#[derive(Default)]
+struct AuditReport {
+    findings: BTreeMap<String, AuditFinding>,
+    audited: Vec<String>,
+}
+
+impl AuditReport {
+    /// Merge one entry of `cargo audit --json` output (a vulnerability or warning)
+    fn add(&mut self, plugin_id: &str, kind: &str, entry: &serde_json::Value) {
+        let advisory = match entry.get("advisory") {
+            Some(a) if a.is_object() => a,
+            _ => return,
+        };
+        let str_field = |v: &serde_json::Value, key: &str| {
+            v.get(key).and_then(|x| x.as_str()).unwrap_or_default().to_string()
+        };
+
+        let advisory_id = str_field(advisory, "id");
+        let key = advisory
+            .get("aliases")
+            .and_then(|a| a.as_array())
+            .and_then(|aliases| aliases.iter().filter_map(|a| a.as_str()).find(|a| a.starts_with("CVE-")))
+            .map(String::from)
+            .unwrap_or_else(|| advisory_id.clone());
+
+        let finding = self.findings.entry(key).or_insert_with(|| AuditFinding {
+            title: str_field(advisory, "title"),
+            advisory_id: advisory_id.clone(),
+            kind: kind.to_string(),
+            packages: BTreeSet::new(),
+            plugins: BTreeSet::new(),
+            patched: entry
+                .get("versions")
+                .and_then(|v| v.get("patched"))
+                .and_then(|p| p.as_array())
+                .map(|p| p.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+                .unwrap_or_default(),
+        });
+        if let Some(package) = entry.get("package") {
+            finding.packages.insert(format!("{} {}", str_field(package, "name"), str_field(package, "version")));
+        }
+        finding.plugins.insert(plugin_id.to_string());
+    }
+
+    fn vulnerability_count(&self) -> usize {
+        self.findings.values().filter(|f| f.kind == "vulnerability").count()
+    }
+
+    fn print(&self) {
+        println!();
+        println!("  {}  {}", style("▶").cyan().bold(), style("Dependency audit").cyan().bold());
+        println!("  {}", style("─".repeat(50)).dim());
+        println!();
+
+        if self.audited.is_empty() {
+            println!("  {} No backend plugins to audit", style("!").yellow());
+            println!();
+            return;
+        }
+
+        for (id, finding) in &self.findings {
+            let marker = if finding.kind == "vulnerability" {
+                style("✗").red().bold()
             } else {
-                format!("plugin_mod::router::{}().await", handler_name)
+                style("!").yellow().bold()
             };
+            println!("  {} {}  {}", marker, style(id).bold(), finding.title);
+            if id != &finding.advisory_id {
+                println!("      Advisory: {}", finding.advisory_id);
+            }
+            if finding.kind != "vulnerability" {
+                println!("      Kind:     {}", finding.kind);
+            }
+            println!("      Crates:   {}", finding.packages.iter().cloned().collect::<Vec<_>>().join(", "));
+            println!("      Plugins:  {}", finding.plugins.iter().cloned().collect::<Vec<_>>().join(", "));
+            if !finding.patched.is_empty() {
+                println!("      Fixed in: {}", finding.patched.join(", "));
+            }
+            println!();
+        }
 
-            format!(r##"
-#[no_mangle]
-pub extern "C" fn {handler_name}(request_ptr: *const u8, request_len: usize, _runtime_ptr: *const ()) -> *const u8 {{
-    use std::panic;
-    use std::ffi::CString;
-    use api::ffi_http::Response as FFIResponse;
-    use api::http::HttpRequest;
-
-    let result = panic::catch_unwind(|| {{
-        let _http_request = match HttpRequest::from_ffi_json(request_ptr, request_len) {{
-            Ok(r) => r,
-            Err(e) => {{
-                let error_response = FFIResponse::new(400)
-                    .json(&api::serde_json::json!({{"error": e}}));
-                return error_response.into_ffi_ptr();
-            }}
-        }};
-        #[allow(unused_variables)]
-        let http_request = _http_request;
+        let vulnerabilities = self.vulnerability_count();
+        let warnings = self.findings.len() - vulnerabilities;
+        if self.findings.is_empty() {
+            println!("  {} No advisories found in {} plugin(s)", style("✓").green(), self.audited.len());
+        } else {
+            println!(
+                "  {} vulnerabilit{}, {} warning(s) across {} plugin(s)",
+                style(vulnerabilities).red().bold(),
+                if vulnerabilities == 1 { "y" } else { "ies" },
+                style(warnings).yellow().bold(),
+                self.audited.len()
+            );
+        }
+        println!();
+    }
+}
 
-        // Create a dedicated single-threaded runtime for this handler
-        // This avoids deadlock when called from within an existing async context
-        let rt = api::tokio::runtime::Builder::new_current_thread()
-            .enable_all()
-            .build()
-            .expect("Failed to create handler runtime");
-        rt.block_on(async {{
-            let handler_result = {handler_call};
-            let response = handler_result;
+/// Run `cargo audit` against every backend plugin's generated workspace
+fn run_audit() -> Result<AuditReport> {
+    let installed = Command::new("cargo")
+        .args(["audit", "--version"])
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false);
+    if !installed {
+        anyhow::bail!("cargo-audit is not installed. Install it with: cargo install cargo-audit --locked");
+    }
 
-            let (parts, body) = response.into_parts();
-            let status = parts.status.as_u16();
+    let plugins_dir = get_plugins_dir()?;
+    if !plugins_dir.exists() {
+        anyhow::bail!("Plugins directory not found: {}", plugins_dir.display());
+    }
 
-            let mut headers = std::collections::HashMap::new();
-            for (key, value) in parts.headers.iter() {{
-                if let Ok(v) = value.to_str() {{
-                    headers.insert(key.to_string(), v.to_string());
-                }}
-            }}
+    let mut plugin_ids: Vec<String> = fs::read_dir(&plugins_dir)?
+        .filter_map(|e| e.ok())
+        .filter(|e| {
+            let path = e.path();
+            path.join("mod.rs").exists() && path.join("Cargo.toml").exists()
+        })
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .collect();
+    plugin_ids.sort();
 
-            let body_bytes = body.to_vec();
+    let mut report = AuditReport::default();
+    let options = BuildOptions::default();
 
-            let mut ffi_response = FFIResponse::new(status);
-            ffi_response.headers = headers.clone();
+    for plugin_id in &plugin_ids {
+        println!("  {} Auditing {}...", style("→").dim(), plugin_id);
+        let builder = PluginBuilder::new(plugin_id, &options)?;
+        let rust_build_dir = builder.prepare_backend_workspace()?;
 
-            let content_type = headers.get("content-type")
-                .or_else(|| headers.get("Content-Type"))
-                .cloned()
-                .unwrap_or_default()
-                .to_lowercase();
+        let lockfile = Command::new("cargo")
+            .current_dir(&rust_build_dir)
+            .arg("generate-lockfile")
+            .output()
+            .context("Failed to run cargo generate-lockfile")?;
+        if !lockfile.status.success() {
+            builder.cleanup_build_dir()?;
+            anyhow::bail!(
+                "Failed to resolve dependencies for '{}': {}",
+                plugin_id,
+                String::from_utf8_lossy(&lockfile.stderr).trim()
+            );
+        }
 
-            let is_binary = content_type.starts_with("image/")
-                || content_type.starts_with("application/octet-stream");
+        // cargo audit exits non-zero when it finds something, so judge by the JSON instead
+        let output = Command::new("cargo")
+            .current_dir(&rust_build_dir)
+            .args(["audit", "--json"])
+            .output()
+            .context("Failed to run cargo audit")?;
+        builder.cleanup_build_dir()?;
 
-            if is_binary {{
-                use api::base64::Engine;
-                ffi_response.body_base64 = Some(
-                    api::base64::engine::general_purpose::STANDARD.encode(&body_bytes)
-                );
-            }} else if let Ok(body_str) = String::from_utf8(body_bytes.clone()) {{
-                if let Ok(json_value) = api::serde_json::from_str::<api::serde_json::Value>(&body_str) {{
-                    ffi_response.body = Some(json_value);
-                }} else {{
-                    ffi_response.body = Some(api::serde_json::Value::String(body_str));
-                }}
-            }} else {{
-                use api::base64::Engine;
-                ffi_response.body_base64 = Some(
-                    api::base64::engine::general_purpose::STANDARD.encode(&body_bytes)
-                );
-            }}
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).with_context(|| {
+            format!(
+                "cargo audit failed for '{}': {}",
+                plugin_id,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )
+        })?;
 
-            ffi_response.into_ffi_ptr()
-        }})
-    }});
+        if let Some(list) = json.pointer("/vulnerabilities/list").and_then(|l| l.as_array()) {
+            for entry in list {
+                report.add(plugin_id, "vulnerability", entry);
+            }
+        }
+        if let Some(warnings) = json.get("warnings").and_then(|w| w.as_object()) {
+            for (kind, entries) in warnings {
+                for entry in entries.as_array().into_iter().flatten() {
+                    report.add(plugin_id, kind, entry);
+                }
+            }
+        }
+        report.audited.push(plugin_id.clone());
+    }
 
-    match result {{
-        Ok(ptr) => ptr,
-        Err(_) => {{
-            let error = CString::new(r#"{{"__ffi_response__":true,"status":500,"headers":{{"Content-Type":"application/json"}},"body":{{"error":"Handler panicked"}}}}"#).unwrap();
-            Box::leak(Box::new(error)).as_ptr() as *const u8
-        }}
-    }}
-}}
-"##)
-            }).collect::<Vec<_>>().join("\n")
-        };
+    Ok(report)
+}
 
-        // Generate lib.rs - use minimal version if no routes (no bridge dependencies)
-        let lib_content = if has_routes {
-            format!(r#"// Auto-generated plugin library (with bridge support)
-pub mod plugin_mod;
-pub use plugin_mod::*;
-pub use api::ffi_http::free_string;
+/// Print `<sha256>  <file-name>`, the line `install` expects in a release's checksums.sha256
+fn print_archive_hash(archive: &Path) -> Result<()> {
+    let bytes = fs::read(archive).with_context(|| format!("Failed to read {}", archive.display()))?;
+    let file_name = archive.file_name().unwrap_or_default().to_string_lossy();
+    println!("{:x}  {}", Sha256::digest(bytes), file_name);
+    Ok(())
+}
 
-/// Embedded frontend JavaScript (plugin.js)
-const EMBEDDED_FRONTEND: &str = "{escaped_frontend}";
+/// Run a plugin backend's tests in the generated workspace, built as an rlib and without
+/// bundling the frontend. Cargo's output is streamed as-is.
+fn test_plugin(plugin_id: &str, filter: Option<&str>) -> Result<()> {
+    let plugin_dir = get_plugins_dir()?.join(plugin_id);
+    if !plugin_dir.join("mod.rs").exists() || !plugin_dir.join("Cargo.toml").exists() {
+        anyhow::bail!("Plugin '{}' has no Rust backend to test (needs mod.rs and Cargo.toml)", plugin_id);
+    }
 
-/// Embedded manifest (package.json)
-const EMBEDDED_MANIFEST: &str = "{escaped_manifest}";
+    let builder = PluginBuilder::new(plugin_id, &BuildOptions::default())?;
+    let rust_build_dir = builder.prepare_backend_workspace()?;
 
-#[no_mangle]
-pub extern "C" fn plugin_init(_ffi_ctx: *const ()) -> i32 {{ 0 }}
+    set_rlib_crate_type(&rust_build_dir)?;
 
-#[no_mangle]
-pub extern "C" fn plugin_start(_ffi_ctx: *const ()) -> i32 {{ 0 }}
+    println!();
+    println!("  {} Testing {}...", style("→").dim(), style(plugin_id).cyan());
+    println!();
 
-#[no_mangle]
-pub extern "C" fn plugin_stop() -> i32 {{ 0 }}
+    let mut cargo = Command::new("cargo");
+    cargo.current_dir(&rust_build_dir).args(["test", "--lib"]);
+    if let Some(filter) = filter {
+        cargo.arg(filter);
+    }
+    cargo.env("WEBARCADE_PLUGIN_DIR", &plugin_dir);
+    let status = cargo.status().context("Failed to run cargo test");
+    builder.cleanup_build_dir()?;
 
-#[no_mangle]
-pub extern "C" fn plugin_metadata() -> *const u8 {{
-    use api::{{Plugin, serde_json}};
-    let plugin = plugin_mod::{plugin_struct};
-    let metadata = plugin.metadata();
-    let json = serde_json::to_string(&metadata).unwrap_or_default();
-    Box::leak(Box::new(json)).as_ptr() as *const u8
-}}
+    if !status?.success() {
+        anyhow::bail!("Tests failed for '{}'", plugin_id);
+    }
+    println!();
+    println!("  {} Tests passed for {}", style("✓").green().bold(), style(plugin_id).cyan());
+    println!();
+    Ok(())
+}
 
-/// Returns the embedded manifest (package.json) as a null-terminated string
-#[no_mangle]
-pub extern "C" fn get_plugin_manifest() -> *const u8 {{
-    let manifest = EMBEDDED_MANIFEST.to_string();
-    let leaked = Box::leak(Box::new(manifest));
-    leaked.as_ptr()
-}}
+/// Tests link a test harness, not the plugin library the app loads, so the generated crate is
+/// switched to an rlib
+fn set_rlib_crate_type(rust_build_dir: &Path) -> Result<()> {
+    let cargo_toml_path = rust_build_dir.join("Cargo.toml");
+    let mut doc: toml_edit::DocumentMut = fs::read_to_string(&cargo_toml_path)?
+        .parse()
+        .context("Failed to parse generated Cargo.toml")?;
+    let mut crate_type = toml_edit::Array::new();
+    crate_type.push("rlib");
+    doc["lib"]["crate-type"] = toml_edit::value(crate_type);
+    fs::write(&cargo_toml_path, doc.to_string())?;
+    Ok(())
+}
 
-/// Returns the length of the embedded manifest
-#[no_mangle]
-pub extern "C" fn get_plugin_manifest_len() -> usize {{
-    EMBEDDED_MANIFEST.len()
-}}
+fn audit_plugins(deny_warnings: bool) -> Result<()> {
+    let report = run_audit()?;
+    report.print();
 
-/// Returns the embedded frontend (plugin.js) as a null-terminated string
-#[no_mangle]
-pub extern "C" fn get_plugin_frontend() -> *const u8 {{
-    let frontend = EMBEDDED_FRONTEND.to_string();
-    let leaked = Box::leak(Box::new(frontend));
-    leaked.as_ptr()
-}}
+    if deny_warnings && !report.findings.is_empty() {
+        anyhow::bail!(
+            "Audit found {} advisor{}",
+            report.findings.len(),
+            if report.findings.len() == 1 { "y" } else { "ies" }
+        );
+    }
 
-/// Returns the length of the embedded frontend
-#[no_mangle]
-pub extern "C" fn get_plugin_frontend_len() -> usize {{
-    EMBEDDED_FRONTEND.len()
-}}
+    Ok(())
+}
 
-/// Returns whether this plugin has a frontend
-#[no_mangle]
-pub extern "C" fn has_frontend() -> bool {{
-    !EMBEDDED_FRONTEND.is_empty()
-}}
+// ============================================================================
+// SCAFFOLD - CI/CD workflow templates
+// ============================================================================
 
-/// Free a string allocated by this plugin
-#[no_mangle]
-pub extern "C" fn free_plugin_string(ptr: *mut u8) {{
-    if !ptr.is_null() {{
-        unsafe {{
-            let _ = std::ffi::CString::from_raw(ptr as *mut i8);
-        }}
-    }}
-}}
+/// GitHub Actions workflow. `{cli_version}` is replaced with the running CLI version.
+const GITHUB_WORKFLOW_TEMPLATE: &str = r#"# Generated by webarcade scaffold hooks
+name: WebArcade
 
-{handler_wrappers}
-"#)
-        } else {
-            // Minimal version without bridge dependencies (no tokio, http, etc.)
-            format!(r#"// Auto-generated plugin library (minimal - no bridge)
-pub mod plugin_mod;
-pub use plugin_mod::*;
+on:
+  push:
+    branches: [main]
+    tags: ['v*']
+  pull_request:
 
-/// Embedded frontend JavaScript (plugin.js)
-const EMBEDDED_FRONTEND: &str = "{escaped_frontend}";
+env:
+  WEBARCADE_VERSION: "{cli_version}"
+  WEBARCADE_TOKEN: ${{ secrets.WEBARCADE_TOKEN }}
 
-/// Embedded manifest (package.json)
-const EMBEDDED_MANIFEST: &str = "{escaped_manifest}";
+jobs:
+  build:
+    runs-on: ${{ startsWith(github.ref, 'refs/tags/') && 'windows-latest' || 'ubuntu-latest' }}
+    steps:
+      - uses: actions/checkout@v4
 
-#[no_mangle]
-pub extern "C" fn plugin_init(_ffi_ctx: *const ()) -> i32 {{ 0 }}
+      - uses: dtolnay/rust-toolchain@stable
 
-#[no_mangle]
-pub extern "C" fn plugin_start(_ffi_ctx: *const ()) -> i32 {{ 0 }}
+      - uses: oven-sh/setup-bun@v2
 
-#[no_mangle]
-pub extern "C" fn plugin_stop() -> i32 {{ 0 }}
+      - uses: actions/cache@v4
+        with:
+          path: |
+            ~/.cargo/bin
+            ~/.cargo/registry
+            ~/.cargo/git
+            node_modules
+          key: ${{ runner.os }}-webarcade-${{ hashFiles('**/Cargo.lock', '**/bun.lockb') }}
 
-#[no_mangle]
-pub extern "C" fn plugin_metadata() -> *const u8 {{
-    use api::{{Plugin, serde_json}};
-    let plugin = plugin_mod::{plugin_struct};
-    let metadata = plugin.metadata();
-    let json = serde_json::to_string(&metadata).unwrap_or_default();
-    Box::leak(Box::new(json)).as_ptr() as *const u8
-}}
+      - name: Install WebArcade CLI
+        run: cargo install webarcade --version ${{ env.WEBARCADE_VERSION }} --locked
 
-/// Returns the embedded manifest (package.json) as a null-terminated string
-#[no_mangle]
-pub extern "C" fn get_plugin_manifest() -> *const u8 {{
-    let manifest = EMBEDDED_MANIFEST.to_string();
-    let leaked = Box::leak(Box::new(manifest));
-    leaked.as_ptr()
-}}
+      - name: Install dependencies
+        run: bun install
 
-/// Returns the length of the embedded manifest
-#[no_mangle]
-pub extern "C" fn get_plugin_manifest_len() -> usize {{
-    EMBEDDED_MANIFEST.len()
-}}
+      - name: Build plugins
+        run: webarcade build --all
 
-/// Returns the embedded frontend (plugin.js) as a null-terminated string
-#[no_mangle]
-pub extern "C" fn get_plugin_frontend() -> *const u8 {{
-    let frontend = EMBEDDED_FRONTEND.to_string();
-    let leaked = Box::leak(Box::new(frontend));
-    leaked.as_ptr()
-}}
+      - name: Package
+        if: startsWith(github.ref, 'refs/tags/')
+        run: webarcade package --skip-prompts
+"#;
 
-/// Returns the length of the embedded frontend
-#[no_mangle]
-pub extern "C" fn get_plugin_frontend_len() -> usize {{
-    EMBEDDED_FRONTEND.len()
-}}
+/// GitLab CI pipeline. `{cli_version}` is replaced with the running CLI version.
+const GITLAB_CI_TEMPLATE: &str = r#"# Generated by webarcade scaffold hooks
+# Set WEBARCADE_TOKEN under Settings > CI/CD > Variables.
+image: rust:latest
+
+variables:
+  WEBARCADE_VERSION: "{cli_version}"
+  CARGO_HOME: $CI_PROJECT_DIR/.cargo
+
+cache:
+  key: webarcade
+  paths:
+    - .cargo/
+    - node_modules/
+
+stages:
+  - build
+  - package
+
+before_script:
+  - curl -fsSL https://bun.sh/install | bash
+  - export PATH="$HOME/.bun/bin:$CARGO_HOME/bin:$PATH"
+  - cargo install webarcade --version "$WEBARCADE_VERSION" --locked
+  - bun install
+
+build:
+  stage: build
+  script:
+    - webarcade build --all
+
+package:
+  stage: package
+  rules:
+    - if: $CI_COMMIT_TAG
+  script:
+    - webarcade build --all
+    - webarcade package --skip-prompts
+"#;
 
-/// Returns whether this plugin has a frontend
-#[no_mangle]
-pub extern "C" fn has_frontend() -> bool {{
-    !EMBEDDED_FRONTEND.is_empty()
-}}
+/// Bitbucket Pipelines config. `{cli_version}` is replaced with the running CLI version.
+const BITBUCKET_PIPELINES_TEMPLATE: &str = r#"# Generated by webarcade scaffold hooks
+# Set WEBARCADE_TOKEN under Repository settings > Pipelines > Repository variables.
+image: rust:latest
+
+definitions:
+  caches:
+    cargo: ~/.cargo
+  steps:
+    - step: &setup-and-build
+        name: Build plugins
+        caches:
+          - cargo
+          - node
+        script:
+          - curl -fsSL https://bun.sh/install | bash
+          - export PATH="$HOME/.bun/bin:$HOME/.cargo/bin:$PATH"
+          - cargo install webarcade --version "{cli_version}" --locked
+          - bun install
+          - webarcade build --all
+
+pipelines:
+  default:
+    - step: *setup-and-build
+  tags:
+    'v*':
+      - step:
+          <<: *setup-and-build
+          name: Build and package
+          script:
+            - curl -fsSL https://bun.sh/install | bash
+            - export PATH="$HOME/.bun/bin:$HOME/.cargo/bin:$PATH"
+            - cargo install webarcade --version "{cli_version}" --locked
+            - bun install
+            - webarcade build --all
+            - webarcade package --skip-prompts
+"#;
 
-/// Free a string allocated by this plugin
-#[no_mangle]
-pub extern "C" fn free_plugin_string(ptr: *mut u8) {{
-    if !ptr.is_null() {{
-        unsafe {{
-            let _ = std::ffi::CString::from_raw(ptr as *mut i8);
-        }}
-    }}
-}}
-"#)
-        };
+/// Generate CI/CD workflow files at the repo root for the chosen providers
+/// Built-in cargo-deny policy, used by `build --deny` when neither the plugin nor the repo has a deny.toml
+const DEFAULT_DENY_TOML: &str = include_str!("deny.toml");
 
-        fs::write(rust_build_dir.join("lib.rs"), lib_content)?;
-        Ok(())
+fn scaffold_deny_config(force: bool) -> Result<()> {
+    let path = get_repo_root()?.join("deny.toml");
+    if path.exists() && !force {
+        anyhow::bail!("{} already exists (use --force to overwrite)", path.display());
     }
+    fs::write(&path, DEFAULT_DENY_TOML).with_context(|| format!("Failed to write {}", path.display()))?;
+    println!("  {} Created {}", style("✓").green(), path.display());
+    println!("  {} Used by {} for every plugin without its own deny.toml", style("→").dim(), style("webarcade build --deny").bold());
+    Ok(())
+}
 
-    fn extract_handlers(&self) -> Result<Vec<(String, bool)>> {
-        let mut handlers: Vec<(String, bool)> = Vec::new();
-
-        let cargo_toml_path = self.plugin_dir.join("Cargo.toml");
-        if cargo_toml_path.exists() {
-            let cargo_content = fs::read_to_string(&cargo_toml_path)?;
-            if let Ok(cargo_toml) = cargo_content.parse::<toml::Value>() {
-                if let Some(routes_table) = cargo_toml.get("routes").and_then(|r| r.as_table()) {
-                    for (_, value) in routes_table {
-                        if let Some(handler) = value.as_str() {
-                            if !handlers.iter().any(|(h, _)| h == handler) {
-                                handlers.push((handler.to_string(), false));
-                            }
-                        }
-                    }
-                }
-            }
-        }
+fn scaffold_hooks(providers: &[String], force: bool) -> Result<()> {
+    let repo_root = get_repo_root()?;
 
-        let router_path = self.plugin_dir.join("router.rs");
-        if router_path.exists() {
-            let router_content = fs::read_to_string(&router_path)?;
+    // (provider id, label, output path, template)
+    let all_providers = [
+        ("github", "GitHub Actions", ".github/workflows/webarcade.yml", GITHUB_WORKFLOW_TEMPLATE),
+        ("gitlab", "GitLab CI", ".gitlab-ci.yml", GITLAB_CI_TEMPLATE),
+        ("bitbucket", "Bitbucket Pipelines", "bitbucket-pipelines.yml", BITBUCKET_PIPELINES_TEMPLATE),
+    ];
 
-            for (handler_name, takes_request) in handlers.iter_mut() {
-                let pattern = format!(r"(?m)^pub\s+async\s+fn\s+{}\s*\(([^)]*)\)", regex::escape(handler_name));
-                if let Ok(re) = regex::Regex::new(&pattern) {
-                    if let Some(captures) = re.captures(&router_content) {
-                        if let Some(params) = captures.get(1) {
-                            let params_str = params.as_str().trim();
-                            *takes_request = !params_str.is_empty() &&
-                                (params_str.contains("HttpRequest") ||
-                                 params_str.contains("Request") ||
-                                 params_str.contains(":"));
-                        }
-                    }
-                }
-            }
-        }
+    let selected: Vec<usize> = if providers.is_empty() {
+        let labels: Vec<&str> = all_providers.iter().map(|(_, label, _, _)| *label).collect();
+        MultiSelect::with_theme(&ColorfulTheme::default())
+            .with_prompt("CI providers (space to select, enter to confirm)")
+            .items(&labels)
+            .defaults(&[true, false, false])
+            .interact()?
+    } else {
+        all_providers
+            .iter()
+            .enumerate()
+            .filter(|(_, (id, _, _, _))| providers.iter().any(|p| p == id))
+            .map(|(i, _)| i)
+            .collect()
+    };
 
-        Ok(handlers)
+    if selected.is_empty() {
+        println!("No CI providers selected.");
+        return Ok(());
     }
 
-    fn get_plugin_struct_name(&self) -> String {
-        let parts: Vec<&str> = self.plugin_id.split(|c| c == '_' || c == '-').collect();
-        let mut name = String::new();
-        for part in parts {
-            let mut chars = part.chars();
-            if let Some(first) = chars.next() {
-                name.push(first.to_uppercase().next().unwrap());
-                name.push_str(chars.as_str());
-            }
+    println!();
+    for index in selected {
+        let (_, label, rel_path, template) = all_providers[index];
+        let path = repo_root.join(rel_path);
+        if path.exists() && !force {
+            println!("  {} {} already exists (use --force to overwrite)", style("!").yellow(), rel_path);
+            continue;
         }
-        name.push_str("Plugin");
-        name
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, template.replace("{cli_version}", CURRENT_VERSION))
+            .with_context(|| format!("Failed to write {}", path.display()))?;
+        println!("  {} {} → {}", style("✓").green(), label, rel_path);
     }
 
-    fn compile_backend(&self) -> Result<()> {
-        let rust_build_dir = self.build_dir.join("rust_build");
+    println!();
+    println!("Add a {} secret to your CI settings before pushing a release tag.", style("WEBARCADE_TOKEN").bold());
 
-        // Spawn cargo with piped stderr to capture progress
-        let mut args = vec!["build", "--release", "--lib"];
-        let target_string;
-        if let Some(ref target) = self.target {
-            target_string = target.clone();
-            args.push("--target");
-            args.push(&target_string);
-        }
+    Ok(())
+}
 
-        let mut child = Command::new("cargo")
-            .current_dir(&rust_build_dir)
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()
-            .context("Failed to run cargo build")?;
+// ============================================================================
+// PACKAGE COMMAND - Interactive app packaging
+// ============================================================================
 
-        // Read stderr to parse progress (cargo outputs progress to stderr)
-        let stderr = child.stderr.take().expect("Failed to capture stderr");
-        let reader = std::io::BufReader::new(stderr);
+#[derive(Debug, Clone)]
+struct AppConfig {
+    name: String,
+    /// Human-readable name (spaces allowed) in the packager `product-name`, which `init --name`
+    /// may have set apart from the cargo package name
+    product_name: String,
+    version: String,
+    description: String,
+    author: String,
+    identifier: String,
+    locked: bool,
+    /// Release notes shown on the installer's finish page
+    release_notes: Option<String>,
+}
 
-        let mut compiled_count = 0usize;
-        let mut total_crates = 0usize;
-        let mut error_output = String::new();
-        let mut last_crate_name = String::new();
+impl AppConfig {
+    fn from_cargo_toml(cargo_toml_path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(cargo_toml_path)?;
+        let doc: toml::Value = content.parse()?;
 
-        for line in reader.lines() {
-            let line = match line {
-                Ok(l) => l,
-                Err(_) => continue,
-            };
+        let package = doc.get("package").context("Missing [package] section")?;
+        let packager = doc.get("package")
+            .and_then(|p| p.get("metadata"))
+            .and_then(|m| m.get("packager"));
 
-            // Parse "Compiling crate_name v0.1.0" lines
-            if line.trim_start().starts_with("Compiling ") {
-                compiled_count += 1;
-                // Extract crate name from "Compiling crate_name v0.1.0 (path)"
-                let parts: Vec<&str> = line.trim_start().splitn(3, ' ').collect();
-                if parts.len() >= 2 {
-                    last_crate_name = parts[1].to_string();
-                }
+        let name = package.get("name")
+            .and_then(|v| v.as_str())
+            .unwrap_or("MyApp")
+            .to_string();
+        Ok(Self {
+            product_name: packager
+                .and_then(|p| p.get("product-name"))
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+                .unwrap_or_else(|| name.clone()),
+            name,
+            version: package.get("version")
+                .and_then(|v| v.as_str())
+                .unwrap_or("0.1.0")
+                .to_string(),
+            description: package.get("description")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string(),
+            author: packager
+                .and_then(|p| p.get("authors"))
+                .and_then(|a| a.as_array())
+                .and_then(|arr| arr.first())
+                .and_then(|v| v.as_str())
+                .unwrap_or("Unknown")
+                .to_string(),
+            identifier: packager
+                .and_then(|p| p.get("identifier"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("com.app.myapp")
+                .to_string(),
+            locked: false,
+            release_notes: None,
+        })
+    }
 
-                // Estimate total based on typical plugin build
-                if total_crates == 0 {
-                    total_crates = 150; // Default estimate
-                }
-                if compiled_count > total_crates {
-                    total_crates = compiled_count + 10; // Adjust if we exceeded estimate
-                }
+    /// Check for packaging mistakes up front, returning every problem found
+    fn validate(&self) -> Vec<String> {
+        let mut errors = Vec::new();
 
-                // Update progress display
-                let current = compiled_count;
-                let total = total_crates;
-                let crate_name = last_crate_name.clone();
-                with_build_progress(|p| {
-                    p.update_cargo_progress(current, total, Some(crate_name));
-                });
-            }
-            // Parse "Building [=====> ] N/M: crate" progress lines
-            else if line.contains("Building") && line.contains("/") {
-                // Try to extract N/M from progress line like "Building [=====> ] 50/100: crate"
-                if let Some(progress_part) = line.split(']').nth(1) {
-                    let parts: Vec<&str> = progress_part.trim().split(':').collect();
-                    if !parts.is_empty() {
-                        let nums: Vec<&str> = parts[0].trim().split('/').collect();
-                        if nums.len() == 2 {
-                            if let (Ok(current), Ok(total)) = (nums[0].parse::<usize>(), nums[1].parse::<usize>()) {
-                                total_crates = total;
-                                compiled_count = current;
-                                if parts.len() > 1 {
-                                    last_crate_name = parts[1].trim().to_string();
-                                }
-                                let c = compiled_count;
-                                let t = total_crates;
-                                let crate_name = last_crate_name.clone();
-                                with_build_progress(|p| {
-                                    p.update_cargo_progress(c, t, Some(crate_name));
-                                });
+        let version_re = regex::Regex::new(r"^\d+\.\d+\.\d+(-[0-9A-Za-z.-]+)?(\+[0-9A-Za-z.-]+)?$").unwrap();
+        if !version_re.is_match(&self.version) {
+            errors.push(format!("Version '{}' is not a valid semver version (e.g. 1.0.0)", self.version));
+        }
+
+        let identifier_re = regex::Regex::new(r"^[A-Za-z][A-Za-z0-9-]*(\.[A-Za-z][A-Za-z0-9-]*)+$").unwrap();
+        if !identifier_re.is_match(&self.identifier) {
+            errors.push(format!(
+                "Identifier '{}' is not a reverse-domain name (e.g. com.example.app)",
+                self.identifier
+            ));
+        }
+
+        // The name becomes the executable and installer file name
+        let illegal: Vec<char> = self.name
+            .chars()
+            .filter(|c| matches!(c, '<' | '>' | ':' | '"' | '/' | '\\' | '|' | '?' | '*') || c.is_control())
+            .collect();
+        let reserved = ["CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "LPT1", "LPT2", "LPT3"];
+        if self.name.trim().is_empty() {
+            errors.push("Name must not be empty".to_string());
+        } else if !illegal.is_empty() {
+            errors.push(format!(
+                "Name '{}' contains characters not allowed in Windows file names: {}",
+                self.name,
+                illegal.iter().map(|c| format!("{:?}", c)).collect::<Vec<_>>().join(" ")
+            ));
+        } else if self.name.ends_with('.') || self.name.ends_with(' ') {
+            errors.push(format!("Name '{}' must not end with a dot or space", self.name));
+        } else if reserved.contains(&self.name.to_uppercase().as_str()) {
+            errors.push(format!("Name '{}' is a reserved Windows file name", self.name));
+        }
+
+        if self.author.trim().is_empty() {
+            errors.push("Author must not be empty".to_string());
+        }
+
+        // Every plugin in webarcade.config.json needs a built artifact or source to build from
+        match (get_config_path(), get_dist_plugins_dir(), get_plugins_dir()) {
+            (Ok(config_path), Ok(dist_dir), Ok(plugins_dir)) if config_path.exists() => {
+                match WebArcadeConfig::load_or_create(&config_path) {
+                    Ok(config) => {
+                        let mut ids: Vec<&String> = config.plugins.keys().collect();
+                        ids.sort();
+                        for id in ids {
+                            let entry = &config.plugins[id];
+                            if !dist_dir.join(&entry.path).exists() && !plugins_dir.join(id).is_dir() {
+                                errors.push(format!(
+                                    "Plugin '{}' references {} which does not exist and has no source in plugins/",
+                                    id, entry.path
+                                ));
                             }
                         }
                     }
+                    Err(e) => errors.push(format!("Failed to load webarcade.config.json: {}", e)),
                 }
             }
-            // Capture error lines
-            else if line.contains("error") || line.contains("Error") {
-                error_output.push_str(&line);
-                error_output.push('\n');
-            }
+            _ => {}
         }
 
-        // Wait for the process to complete
-        let status = child.wait().context("Failed to wait for cargo build")?;
+        errors
+    }
 
-        if !status.success() {
-            if error_output.is_empty() {
-                error_output = "Cargo build failed (unknown error)".to_string();
-            }
-            anyhow::bail!("Cargo build failed:\n{}", error_output);
-        }
+    /// Write the app settings to app/Cargo.toml, and the name and version to app/package.json
+    /// and webarcade.config.json. Returns the files that were updated.
+    fn apply_to_project(&self, repo_root: &Path) -> Result<Vec<PathBuf>> {
+        let cargo_toml_path = repo_root.join("app").join("Cargo.toml");
+        let content = fs::read_to_string(&cargo_toml_path)?;
+        let mut doc: toml_edit::DocumentMut = content.parse()?;
 
-        // Copy compiled binary
-        self.copy_compiled_binary(&rust_build_dir)?;
+        // Update [package] section
+        doc["package"]["name"] = toml_edit::value(&self.name);
+        doc["package"]["version"] = toml_edit::value(&self.version);
+        doc["package"]["description"] = toml_edit::value(&self.description);
 
-        Ok(())
-    }
+        // Update [package.metadata.packager] section
+        if doc.get("package").is_none() {
+            doc["package"] = toml_edit::table();
+        }
+        if doc["package"].get("metadata").is_none() {
+            doc["package"]["metadata"] = toml_edit::table();
+        }
+        if doc["package"]["metadata"].get("packager").is_none() {
+            doc["package"]["metadata"]["packager"] = toml_edit::table();
+        }
 
-    fn copy_compiled_binary(&self, rust_build_dir: &Path) -> Result<()> {
-        let target_dir = if let Some(ref target) = self.target {
-            rust_build_dir.join("target").join(target).join("release")
-        } else {
-            rust_build_dir.join("target").join("release")
-        };
+        doc["package"]["metadata"]["packager"]["product-name"] = toml_edit::value(&self.product_name);
+        doc["package"]["metadata"]["packager"]["identifier"] = toml_edit::value(&self.identifier);
 
-        let lib_name = self.lib_name();
+        // Update authors array
+        let mut authors = toml_edit::Array::new();
+        authors.push(&self.author);
+        doc["package"]["metadata"]["packager"]["authors"] = toml_edit::value(authors);
 
-        let src_path = target_dir.join(&lib_name);
-        if src_path.exists() {
-            let dest_path = self.build_dir.join(&lib_name);
-            fs::copy(&src_path, &dest_path)?;
-            Ok(())
-        } else {
-            anyhow::bail!("Compiled library not found: {}", src_path.display())
+        // Update binaries path to match package name
+        if let Some(binaries) = doc["package"]["metadata"]["packager"].get_mut("binaries") {
+            if let Some(arr) = binaries.as_array_of_tables_mut() {
+                if let Some(first) = arr.iter_mut().next() {
+                    first["path"] = toml_edit::value(&self.name);
+                }
+            }
         }
-    }
 
-    fn bundle_frontend(&self) -> Result<()> {
-        let has_frontend = self.plugin_dir.join("index.jsx").exists()
-            || self.plugin_dir.join("index.js").exists();
+        // Update appdata-paths for cleanup on uninstall
+        let mut appdata = toml_edit::Array::new();
+        appdata.push(format!("$LOCALAPPDATA\\{}", &self.name));
+        doc["package"]["metadata"]["packager"]["nsis"]["appdata-paths"] = toml_edit::value(appdata);
 
-        if !has_frontend {
-            return Ok(());
+        // Release notes on the installer's welcome/finish page (MUI_WELCOMEFINISHPAGE_TEXT)
+        if let Some(ref notes) = self.release_notes {
+            doc["package"]["metadata"]["packager"]["nsis"]["welcome-finish-page-message"] = toml_edit::value(notes);
         }
 
-        // Install dependencies if needed
-        self.install_npm_dependencies()?;
+        fs::write(&cargo_toml_path, doc.to_string())?;
 
-        // Find bundler script
-        let bundler_script = self.repo_root.join("app").join("scripts").join("build.js");
+        let mut updated = vec![cargo_toml_path];
+        updated.extend(sync_version_manifests(repo_root, Some(&self.name), &self.version)?);
+        Ok(updated)
+    }
+}
 
-        if !bundler_script.exists() {
-            println!("    Warning: Frontend bundler not found at {}", bundler_script.display());
-            return Ok(());
+/// `name` as a valid npm package name: lowercase, spaces as `-`, only URL-safe characters,
+/// and no leading `.` or `_`
+fn npm_package_name(name: &str) -> String {
+    let name: String = name
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-")
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_' | '~'))
+        .collect();
+    let name = name.trim_start_matches(['.', '_']);
+    if name.is_empty() { "app".to_string() } else { name.to_string() }
+}
+
+/// Set `version` (and `name`, when given) in app/package.json if it exists, and `version`
+/// in webarcade.config.json. Returns the files that were updated.
+fn sync_version_manifests(repo_root: &Path, name: Option<&str>, version: &str) -> Result<Vec<PathBuf>> {
+    let mut updated = Vec::new();
+
+    let package_json_path = repo_root.join("app").join("package.json");
+    if package_json_path.exists() {
+        let content = fs::read_to_string(&package_json_path)?;
+        let mut package_json: serde_json::Value = serde_json::from_str(&content)
+            .with_context(|| format!("Failed to parse {}", package_json_path.display()))?;
+        if let Some(name) = name {
+            package_json["name"] = serde_json::json!(npm_package_name(name));
         }
+        package_json["version"] = serde_json::json!(version);
+        fs::write(&package_json_path, serde_json::to_string_pretty(&package_json)? + "\n")?;
+        updated.push(package_json_path);
+    }
 
-        let plugin_dir_str = self.plugin_dir.to_string_lossy();
-        let build_dir_str = self.build_dir.to_string_lossy();
+    let config_path = repo_root.join("webarcade.config.json");
+    if config_path.exists() {
+        let mut config = WebArcadeConfig::load_or_create(&config_path)?;
+        config.version = version.to_string();
+        config.save(&config_path)?;
+        updated.push(config_path);
+    }
 
-        let output = if Command::new("bun").arg("--version").output().is_ok() {
-            Command::new("bun")
-                .arg("run")
-                .arg(&bundler_script)
-                .arg(&*plugin_dir_str)
-                .arg(&*build_dir_str)
-                .output()
-                .context("Failed to run bundler with bun")?
-        } else {
-            Command::new("node")
-                .arg(&bundler_script)
-                .arg(&*plugin_dir_str)
-                .arg(&*build_dir_str)
-                .output()
-                .context("Failed to run bundler with node")?
-        };
+    Ok(updated)
+}
 
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            anyhow::bail!("Frontend bundling failed: {}", stderr);
-        }
+/// `webarcade version <version>`: set the app version everywhere without packaging
+fn set_project_version(version: &str) -> Result<()> {
+    semver::Version::parse(version)
+        .map_err(|e| anyhow::anyhow!("'{}' is not a semver version (e.g. 1.2.0): {}", version, e))?;
 
-        Ok(())
+    let repo_root = get_repo_root()?;
+    let cargo_toml_path = repo_root.join("app").join("Cargo.toml");
+    let mut doc: toml_edit::DocumentMut = fs::read_to_string(&cargo_toml_path)
+        .with_context(|| format!("Failed to read {}", cargo_toml_path.display()))?
+        .parse()
+        .with_context(|| format!("Failed to parse {}", cargo_toml_path.display()))?;
+    doc["package"]["version"] = toml_edit::value(version);
+    fs::write(&cargo_toml_path, doc.to_string())?;
+
+    let mut updated = vec![cargo_toml_path];
+    updated.extend(sync_version_manifests(&repo_root, None, version)?);
+    println!();
+    for path in updated {
+        let name = path.strip_prefix(&repo_root).unwrap_or(&path);
+        println!("  {} {} → {}", style("✓").green(), name.display(), style(version).green());
     }
+    println!();
+    Ok(())
+}
 
-    fn install_npm_dependencies(&self) -> Result<()> {
-        let package_json_path = self.plugin_dir.join("package.json");
-        if !package_json_path.exists() {
-            return Ok(());
-        }
+/// Command-line options for `webarcade package`
+#[derive(Debug, Clone, Default)]
+struct PackageOptions {
+    /// Skip interactive prompts and use current config
+    skip_prompts: bool,
+    /// Embed plugins in the binary
+    locked: bool,
+    /// Only rebuild plugins whose source changed
+    no_rebuild: bool,
+    /// Reuse the existing frontend and binary build
+    skip_binary: bool,
+    name: Option<String>,
+    version: Option<String>,
+    /// Derive the version from the latest v* git tag
+    auto_version: bool,
+    description: Option<String>,
+    author: Option<String>,
+    /// Markdown changelog to take release notes from
+    changelog: Option<PathBuf>,
+    /// Release notes given on the command line, replacing the changelog
+    release_notes: Option<String>,
+    /// File to read the release notes from, replacing the changelog
+    release_notes_file: Option<PathBuf>,
+    /// Publish a GitHub release with the packaged artifacts
+    github_release: bool,
+    /// Run `webarcade audit` before packaging
+    audit: bool,
+    /// `--define` constants passed through to plugin builds
+    defines: Vec<(String, String)>,
+    /// Abort if the CLI isn't the latest version
+    update_check: bool,
+    /// Never check the CLI version, even when the config requires it
+    skip_update_check: bool,
+    /// Leave running app processes alone
+    no_kill: bool,
+    /// Notarize and staple the .dmg after packaging (macOS)
+    notarize: bool,
+    /// Produce a portable ZIP instead of running cargo packager
+    portable: bool,
+    /// Cross-compile an installer for each of SPLIT_INSTALLER_PLATFORMS
+    split_installers: bool,
+}
 
-        let content = fs::read_to_string(&package_json_path)?;
-        let json: serde_json::Value = serde_json::from_str(&content)?;
+/// App version from `git describe --tags --match 'v*'`: the latest tag without its `v`,
+/// plus `-dev` when the working tree has uncommitted changes
+fn git_tag_version(repo_root: &Path) -> Result<String> {
+    let output = Command::new("git")
+        .current_dir(repo_root)
+        .args(["describe", "--tags", "--match", "v*", "--abbrev=0"])
+        .output()
+        .context("Failed to run git describe. Is git installed?")?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "--auto-version needs a v* tag (e.g. git tag v1.0.0): {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    let tag = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let mut version = tag.strip_prefix('v').unwrap_or(&tag).to_string();
 
-        let has_deps = json.get("dependencies").and_then(|d| d.as_object()).map(|o| !o.is_empty()).unwrap_or(false);
-        let has_dev_deps = json.get("devDependencies").and_then(|d| d.as_object()).map(|o| !o.is_empty()).unwrap_or(false);
+    let status = Command::new("git")
+        .current_dir(repo_root)
+        .args(["status", "--porcelain"])
+        .output()
+        .context("Failed to run git status")?;
+    if status.status.success() && !status.stdout.iter().all(u8::is_ascii_whitespace) {
+        version.push_str("-dev");
+    }
+    Ok(version)
+}
 
-        if !has_deps && !has_dev_deps {
-            return Ok(());
-        }
+/// Extract the release notes for `version` from a markdown changelog as plain text.
+/// Falls back to the first `## [x.y.z]` section when the version has no section of its own.
+fn extract_release_notes(changelog: &str, version: &str) -> Option<String> {
+    let lines: Vec<&str> = changelog.lines().collect();
+    let is_release_heading = |l: &str| l.starts_with("## ");
+    let matches_version = |l: &str| {
+        let heading = l.trim_start_matches('#').trim();
+        heading.starts_with(&format!("[{}]", version))
+            || heading.starts_with(&format!("[v{}]", version))
+            || heading.split_whitespace().next() == Some(version)
+    };
 
-        // Capture output to avoid cluttering progress display
-        let output = if Command::new("bun").arg("--version").output().is_ok() {
-            Command::new("bun")
-                .arg("install")
-                .current_dir(&self.plugin_dir)
-                .output()
-        } else {
-            Command::new("npm")
-                .arg("install")
-                .current_dir(&self.plugin_dir)
-                .output()
+    let start = lines.iter().position(|l| is_release_heading(l) && matches_version(l))
+        .or_else(|| lines.iter().position(|l| l.starts_with("## [")))?;
+    let end = lines.iter().skip(start + 1)
+        .position(|l| is_release_heading(l))
+        .map(|i| start + 1 + i)
+        .unwrap_or(lines.len());
+
+    let link_re = regex::Regex::new(r"\[([^\]]*)\]\([^)]*\)").ok()?;
+    let mut notes: Vec<String> = Vec::new();
+    for line in &lines[start + 1..end] {
+        let line = link_re.replace_all(line, "$1");
+        let line = line.replace("**", "").replace('`', "");
+        let text = match line.trim_start().strip_prefix("### ") {
+            Some(heading) => format!("{}:", heading.trim()),
+            None => line.trim_end().to_string(),
         };
+        // Collapse runs of blank lines
+        if text.trim().is_empty() && notes.last().map(|l| l.is_empty()).unwrap_or(true) {
+            continue;
+        }
+        notes.push(text);
+    }
+    while notes.last().map(|l| l.is_empty()).unwrap_or(false) {
+        notes.pop();
+    }
 
-        if let Ok(o) = output {
-            if !o.status.success() {
-                // Silently continue - npm install failures are often non-critical
+    if notes.is_empty() {
+        None
+    } else {
+        Some(notes.join("\n"))
+    }
+}
+
+/// Fail if a newer CLI is published. Network problems only warn, so offline packaging still works.
+fn ensure_latest_cli() -> Result<()> {
+    match check_latest_version() {
+        Some(latest) => {
+            if compare_cli_versions(CURRENT_VERSION, &latest) == std::cmp::Ordering::Less {
+                anyhow::bail!(
+                    "webarcade {} is outdated (latest is {}). Run 'webarcade update' or 'cargo install webarcade --force', \
+                     or pass --skip-update-check",
+                    CURRENT_VERSION,
+                    latest
+                );
             }
+            println!("  {} CLI is up to date ({})", style("✓").green(), CURRENT_VERSION);
+        }
+        None => {
+            println!(
+                "  {} Could not check for CLI updates (offline?); continuing with {}",
+                style("!").yellow(),
+                CURRENT_VERSION
+            );
         }
+    }
+    Ok(())
+}
 
-        Ok(())
+/// Zip the app binary (and, unless locked, the compiled plugins) as
+/// `<name>-<version>-portable/` into `<name>_<version>_x64-portable.zip`
+fn create_portable_zip(config: &AppConfig, binary: &Path, plugins_dir: &Path, output_dir: &Path) -> Result<PathBuf> {
+    use zip::write::SimpleFileOptions;
+
+    if !binary.exists() {
+        anyhow::bail!("App binary not found: {} (build it without --skip-binary)", binary.display());
     }
 
-    fn install_dll(&self) -> Result<()> {
-        // Source uses Rust naming (underscores)
-        let lib_name = self.lib_name();
-        let src_path = self.build_dir.join(&lib_name);
-        if !src_path.exists() {
-            anyhow::bail!("Compiled library not found: {}", src_path.display());
-        }
+    let zip_path = output_dir.join(format!("{}_{}_x64-portable.zip", config.name, config.version));
+    let root = format!("{}-{}-portable", config.name, config.version);
+    let file = fs::File::create(&zip_path)
+        .with_context(|| format!("Failed to create {}", zip_path.display()))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = SimpleFileOptions::default().compression_method(zip::CompressionMethod::Deflated);
 
-        // Destination uses plugin ID (may have hyphens) for loader compatibility
-        let dest_name = if cfg!(target_os = "windows") || self.target.as_ref().map(|t| t.contains("windows")).unwrap_or(false) {
-            format!("{}.dll", self.plugin_id)
-        } else if cfg!(target_os = "macos") || self.target.as_ref().map(|t| t.contains("apple") || t.contains("darwin")).unwrap_or(false) {
-            format!("lib{}.dylib", self.plugin_id)
-        } else {
-            format!("lib{}.so", self.plugin_id)
-        };
-        let dest_path = self.dist_plugins_dir.join(&dest_name);
-        fs::copy(&src_path, &dest_path)?;
+    let binary_name = binary.file_name().unwrap_or_default().to_string_lossy();
+    zip.start_file(format!("{}/{}", root, binary_name), options.unix_permissions(0o755))?;
+    std::io::copy(&mut fs::File::open(binary)?, &mut zip)?;
 
-        Ok(())
+    // Locked builds embed the plugins in the binary
+    if !config.locked && plugins_dir.exists() {
+        for entry in WalkDir::new(plugins_dir).into_iter().filter_map(|e| e.ok()) {
+            let path = entry.path();
+            // Hot reload sentinels are dev-only
+            if !entry.file_type().is_file() || path.extension().map(|e| e == "reload").unwrap_or(false) {
+                continue;
+            }
+            let relative = path.strip_prefix(plugins_dir)?.to_string_lossy().replace('\\', "/");
+            zip.start_file(format!("{}/plugins/{}", root, relative), options)?;
+            std::io::copy(&mut fs::File::open(path)?, &mut zip)?;
+        }
     }
 
-    fn create_manifest(&self) -> Result<String> {
-        let package_json_path = self.plugin_dir.join("package.json");
+    zip.finish()?;
+    Ok(zip_path)
+}
 
-        let mut package_json = if package_json_path.exists() {
-            let content = fs::read_to_string(&package_json_path)?;
-            serde_json::from_str::<serde_json::Value>(&content)?
-        } else {
-            serde_json::json!({
-                "name": self.plugin_id,
-                "version": "1.0.0"
-            })
-        };
+/// notarytool credentials. Secrets never go on the command line: either a keychain profile
+/// saved with `xcrun notarytool store-credentials`, or an App Store Connect API key file.
+enum NotaryCredentials {
+    KeychainProfile(String),
+    ApiKey { key_path: String, key_id: String, issuer: String },
+}
 
-        let routes = self.extract_routes()?;
+impl NotaryCredentials {
+    /// Read credentials from the environment, listing every missing variable at once
+    fn from_env() -> Result<Self> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.trim().is_empty());
+        if let Some(profile) = var("WEBARCADE_APPLE_KEYCHAIN_PROFILE") {
+            return Ok(Self::KeychainProfile(profile));
+        }
 
-        package_json["webarcade"] = serde_json::json!({
-            "id": self.plugin_id,
-            "routes": routes
-        });
+        const API_KEY_VARS: [&str; 3] = [
+            "WEBARCADE_APPLE_API_KEY",
+            "WEBARCADE_APPLE_API_KEY_ID",
+            "WEBARCADE_APPLE_API_ISSUER",
+        ];
+        let values: Vec<Option<String>> = API_KEY_VARS.iter().map(|name| var(name)).collect();
+        let missing: Vec<&str> = API_KEY_VARS
+            .iter()
+            .zip(&values)
+            .filter(|(_, value)| value.is_none())
+            .map(|(name, _)| *name)
+            .collect();
+        if !missing.is_empty() {
+            anyhow::bail!(
+                "--notarize needs WEBARCADE_APPLE_KEYCHAIN_PROFILE (a profile saved with \
+                 `xcrun notarytool store-credentials`) or these environment variables: {} (missing: {})",
+                API_KEY_VARS.join(", "),
+                missing.join(", ")
+            );
+        }
 
-        Ok(serde_json::to_string_pretty(&package_json)?)
+        let mut values = values.into_iter().flatten();
+        Ok(Self::ApiKey {
+            key_path: values.next().unwrap_or_default(),
+            key_id: values.next().unwrap_or_default(),
+            issuer: values.next().unwrap_or_default(),
+        })
     }
 
-    fn extract_routes(&self) -> Result<Vec<serde_json::Value>> {
-        let mut routes = Vec::new();
-
-        let cargo_toml_path = self.plugin_dir.join("Cargo.toml");
-        if cargo_toml_path.exists() {
-            let cargo_content = fs::read_to_string(&cargo_toml_path)?;
-            if let Ok(cargo_toml) = cargo_content.parse::<toml::Value>() {
-                if let Some(routes_table) = cargo_toml.get("routes").and_then(|r| r.as_table()) {
-                    for (key, value) in routes_table {
-                        if let Some(handler) = value.as_str() {
-                            let parts: Vec<&str> = key.splitn(2, ' ').collect();
-                            if parts.len() == 2 {
-                                routes.push(serde_json::json!({
-                                    "method": parts[0],
-                                    "path": parts[1],
-                                    "handler": handler
-                                }));
-                            }
-                        }
-                    }
-                }
+    fn args(&self) -> Vec<&str> {
+        match self {
+            Self::KeychainProfile(profile) => vec!["--keychain-profile", profile],
+            Self::ApiKey { key_path, key_id, issuer } => {
+                vec!["--key", key_path, "--key-id", key_id, "--issuer", issuer]
             }
         }
+    }
+}
 
-        Ok(routes)
+/// Run `xcrun notarytool <args> --output-format json` and parse its output
+fn notarytool(args: &[&str], credentials: &NotaryCredentials) -> Result<serde_json::Value> {
+    let output = Command::new("xcrun")
+        .arg("notarytool")
+        .args(args)
+        .args(credentials.args())
+        .args(["--output-format", "json"])
+        .output()
+        .context("Failed to run xcrun notarytool. Is Xcode installed?")?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        let details = if stderr.trim().is_empty() { stdout.trim() } else { stderr.trim() };
+        anyhow::bail!("notarytool {} failed: {}", args[0], details);
     }
+    serde_json::from_str(&stdout).with_context(|| format!("Unexpected notarytool output: {}", stdout.trim()))
 }
 
-// ============================================================================
-// PACKAGE COMMAND - Interactive app packaging
-// ============================================================================
+/// Newest .dmg produced by cargo packager under `output_dir`
+fn find_dmg(output_dir: &Path) -> Option<PathBuf> {
+    WalkDir::new(output_dir)
+        .max_depth(2)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().extension().map(|ext| ext == "dmg").unwrap_or(false))
+        .max_by_key(|e| e.metadata().ok().and_then(|m| m.modified().ok()))
+        .map(|e| e.into_path())
+}
 
-#[derive(Debug, Clone)]
-struct AppConfig {
-    name: String,
-    version: String,
-    description: String,
-    author: String,
-    identifier: String,
-    locked: bool,
+/// Notarize `dmg` and staple the ticket. The submission ID is kept in `.notarization_id`
+/// next to the .dmg (with the .dmg's hash) so an interrupted run resumes waiting on the
+/// same submission instead of uploading again.
+fn notarize_dmg(dmg: &Path, credentials: &NotaryCredentials) -> Result<()> {
+    let id_path = dmg.with_file_name(".notarization_id");
+    let dmg_hash = format!("{:x}", Sha256::digest(fs::read(dmg)?));
+
+    let previous_id = fs::read_to_string(&id_path).ok().and_then(|content| {
+        let (id, hash) = content.trim().split_once(' ')?;
+        (hash == dmg_hash).then(|| id.to_string())
+    });
+    let submission_id = match previous_id {
+        Some(id) => {
+            println!("  {} Resuming notarization request {}", style("→").dim(), id);
+            id
+        }
+        None => {
+            let dmg_str = dmg.to_string_lossy();
+            let submitted = notarytool(&["submit", &dmg_str, "--no-wait"], credentials)?;
+            let id = submitted["id"]
+                .as_str()
+                .context("notarytool submit did not return a request ID")?
+                .to_string();
+            write_atomic(&id_path, &format!("{} {}\n", id, dmg_hash))?;
+            println!("  {} Submitted for notarization ({})", style("✓").green(), id);
+            id
+        }
+    };
+
+    println!("  {} Waiting for Apple (this can take several minutes)...", style("→").dim());
+    let result = notarytool(&["wait", &submission_id], credentials)?;
+    let status = result["status"].as_str().unwrap_or("unknown");
+    if status != "Accepted" {
+        // A rejected submission can't be resumed; the next run submits again
+        let _ = fs::remove_file(&id_path);
+        anyhow::bail!(
+            "Notarization {} ({}). See the log with: xcrun notarytool log {}",
+            status.to_lowercase(),
+            result["message"].as_str().unwrap_or("no details"),
+            submission_id
+        );
+    }
+    println!("  {} Notarization accepted", style("✓").green());
+
+    let staple_status = Command::new("xcrun")
+        .args(["stapler", "staple"])
+        .arg(dmg)
+        .status()
+        .context("Failed to run xcrun stapler")?;
+    if !staple_status.success() {
+        anyhow::bail!("Stapling failed; rerun to retry without resubmitting");
+    }
+    let _ = fs::remove_file(&id_path);
+    println!("  {} Ticket stapled to {}", style("✓").green(), dmg.display());
+
+    Ok(())
 }
 
-impl AppConfig {
-    fn from_cargo_toml(cargo_toml_path: &Path) -> Result<Self> {
-        let content = fs::read_to_string(cargo_toml_path)?;
-        let doc: toml::Value = content.parse()?;
+/// `checksums.json` written next to packaged artifacts
+#[derive(Debug, Serialize, Deserialize)]
+struct ChecksumManifest {
+    artifacts: Vec<ArtifactChecksum>,
+}
 
-        let package = doc.get("package").context("Missing [package] section")?;
-        let packager = doc.get("package")
-            .and_then(|p| p.get("metadata"))
-            .and_then(|m| m.get("packager"));
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct ArtifactChecksum {
+    /// Path relative to the directory holding checksums.json
+    name: String,
+    sha256: String,
+    sha512: String,
+    size: u64,
+}
 
+impl ArtifactChecksum {
+    /// Hash `path`, reading it in chunks so large installers aren't loaded into memory
+    fn compute(name: String, path: &Path) -> Result<Self> {
+        let mut file = fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+        let mut sha256 = Sha256::new();
+        let mut sha512 = Sha512::new();
+        let mut size = 0u64;
+        let mut buffer = [0u8; 64 * 1024];
+        loop {
+            let read = file.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            sha256.update(&buffer[..read]);
+            sha512.update(&buffer[..read]);
+            size += read as u64;
+        }
         Ok(Self {
-            name: package.get("name")
-                .and_then(|v| v.as_str())
-                .unwrap_or("MyApp")
-                .to_string(),
-            version: package.get("version")
-                .and_then(|v| v.as_str())
-                .unwrap_or("0.1.0")
-                .to_string(),
-            description: package.get("description")
-                .and_then(|v| v.as_str())
-                .unwrap_or("")
-                .to_string(),
-            author: packager
-                .and_then(|p| p.get("authors"))
-                .and_then(|a| a.as_array())
-                .and_then(|arr| arr.first())
-                .and_then(|v| v.as_str())
-                .unwrap_or("Unknown")
-                .to_string(),
-            identifier: packager
-                .and_then(|p| p.get("identifier"))
-                .and_then(|v| v.as_str())
-                .unwrap_or("com.app.myapp")
-                .to_string(),
-            locked: false,
+            name,
+            sha256: format!("{:x}", sha256.finalize()),
+            sha512: format!("{:x}", sha512.finalize()),
+            size,
         })
     }
+}
 
-    fn write_to_cargo_toml(&self, cargo_toml_path: &Path) -> Result<()> {
-        let content = fs::read_to_string(cargo_toml_path)?;
-        let mut doc: toml_edit::DocumentMut = content.parse()?;
+/// Installers cargo packager wrote under `output_dir` since `since`. Older ones (e.g. from a
+/// previous version) are left out; `since` allows for filesystems with coarse timestamps.
+fn find_installers(output_dir: &Path, since: std::time::SystemTime) -> Vec<PathBuf> {
+    let since = since - std::time::Duration::from_secs(2);
+    let mut installers: Vec<PathBuf> = WalkDir::new(output_dir)
+        .max_depth(2)
+        .into_iter()
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_file())
+        .filter(|e| e.metadata().ok().and_then(|m| m.modified().ok()).is_some_and(|modified| modified >= since))
+        .map(|e| e.into_path())
+        .filter(|path| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            name.ends_with("-setup.exe")
+                || [".msi", ".dmg", ".deb", ".rpm", ".AppImage"].iter().any(|ext| name.ends_with(ext))
+        })
+        .collect();
+    installers.sort();
+    installers
+}
 
-        // Update [package] section
-        doc["package"]["name"] = toml_edit::value(&self.name);
-        doc["package"]["version"] = toml_edit::value(&self.version);
-        doc["package"]["description"] = toml_edit::value(&self.description);
+/// Free space `package` needs for the release build and installers
+const PACKAGE_MIN_FREE_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Check the packaging toolchain and disk space before anything is built, reporting every
+/// problem at once with where to get the missing tool
+fn package_preflight(app_dir: &Path, portable: bool, split_installers: bool) -> Result<()> {
+    let mut problems: Vec<(String, &str)> = Vec::new();
+
+    // A portable ZIP is assembled by the CLI itself
+    if !portable {
+        let packager = Command::new("cargo")
+            .args(["packager", "--version"])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+        if !packager {
+            problems.push((
+                "cargo-packager is not installed (cargo install cargo-packager --locked)".to_string(),
+                "https://github.com/crabnebula-dev/cargo-packager#installation",
+            ));
+        }
 
-        // Update [package.metadata.packager] section
-        if doc.get("package").is_none() {
-            doc["package"] = toml_edit::table();
+        let formats: Vec<String> = fs::read_to_string(app_dir.join("Cargo.toml"))
+            .ok()
+            .and_then(|content| content.parse::<toml::Value>().ok())
+            .and_then(|doc| {
+                let formats = doc.get("package")?.get("metadata")?.get("packager")?.get("formats")?.as_array()?.clone();
+                Some(formats.iter().filter_map(|f| f.as_str().map(String::from)).collect())
+            })
+            .unwrap_or_default();
+        // Without `formats`, cargo-packager builds every format the host supports, NSIS included.
+        // --split-installers always builds the Windows NSIS installer, on a macOS host.
+        let wants = |format: &str| formats.is_empty() || formats.iter().any(|f| f == format);
+        let wants_nsis = split_installers || (cfg!(target_os = "windows") && wants("nsis"));
+        if wants_nsis && find_on_path("makensis").is_none() {
+            problems.push((
+                "NSIS (makensis) is not in PATH".to_string(),
+                "https://nsis.sourceforge.io/Download",
+            ));
         }
-        if doc["package"].get("metadata").is_none() {
-            doc["package"]["metadata"] = toml_edit::table();
+        if cfg!(target_os = "windows") {
+            let wants_msi = formats.iter().any(|f| f == "wix" || f == "msi");
+            if wants_msi && find_on_path("candle").is_none() && find_on_path("wix").is_none() {
+                problems.push((
+                    "WiX toolset (candle or wix) is not in PATH, needed for the .msi format".to_string(),
+                    "https://wixtoolset.org/docs/intro/",
+                ));
+            }
         }
-        if doc["package"]["metadata"].get("packager").is_none() {
-            doc["package"]["metadata"]["packager"] = toml_edit::table();
+    }
+
+    // The release build goes to app/target/; measure the disk it (or app/) lives on
+    let target_dir = app_dir.join("target");
+    let measured = fs::canonicalize(&target_dir).or_else(|_| fs::canonicalize(app_dir))?;
+    let disks = sysinfo::Disks::new_with_refreshed_list();
+    let disk = disks
+        .iter()
+        .filter(|disk| measured.starts_with(disk.mount_point()))
+        .max_by_key(|disk| disk.mount_point().as_os_str().len());
+    if let Some(disk) = disk {
+        if disk.available_space() < PACKAGE_MIN_FREE_BYTES {
+            problems.push((
+                format!(
+                    "Only {} MB free on {} (packaging needs at least {} MB)",
+                    disk.available_space() / (1024 * 1024),
+                    disk.mount_point().display(),
+                    PACKAGE_MIN_FREE_BYTES / (1024 * 1024)
+                ),
+                "",
+            ));
         }
+    }
 
-        doc["package"]["metadata"]["packager"]["product-name"] = toml_edit::value(&self.name);
-        doc["package"]["metadata"]["packager"]["identifier"] = toml_edit::value(&self.identifier);
+    if problems.is_empty() {
+        println!("  {} Packaging requirements met", style("✓").green());
+        return Ok(());
+    }
+    for (problem, link) in &problems {
+        println!("  {} {}", style("✗").red(), problem);
+        if !link.is_empty() {
+            println!("    {}", style(link).dim());
+        }
+    }
+    println!();
+    anyhow::bail!("Found {} missing packaging requirement(s); nothing was built", problems.len())
+}
 
-        // Update authors array
-        let mut authors = toml_edit::Array::new();
-        authors.push(&self.author);
-        doc["package"]["metadata"]["packager"]["authors"] = toml_edit::value(authors);
+/// Write `checksums.json` to `output_dir` for `artifacts` (which must live under it)
+fn write_checksums(output_dir: &Path, artifacts: &[PathBuf]) -> Result<PathBuf> {
+    let mut manifest = ChecksumManifest { artifacts: Vec::new() };
+    for path in artifacts {
+        let name = path.strip_prefix(output_dir).unwrap_or(path).to_string_lossy().replace('\\', "/");
+        manifest.artifacts.push(ArtifactChecksum::compute(name, path)?);
+    }
+    let checksums_path = output_dir.join("checksums.json");
+    write_atomic(&checksums_path, &(serde_json::to_string_pretty(&manifest)? + "\n"))?;
+    Ok(checksums_path)
+}
 
-        // Update binaries path to match package name
-        if let Some(binaries) = doc["package"]["metadata"]["packager"].get_mut("binaries") {
-            if let Some(arr) = binaries.as_array_of_tables_mut() {
-                if let Some(first) = arr.iter_mut().next() {
-                    first["path"] = toml_edit::value(&self.name);
-                }
+/// Recompute every artifact in a checksum manifest and fail on any mismatch
+fn verify_package(checksums: &Path) -> Result<()> {
+    let content = fs::read_to_string(checksums)
+        .with_context(|| format!("Failed to read {}", checksums.display()))?;
+    let manifest: ChecksumManifest = serde_json::from_str(&content)
+        .with_context(|| format!("{} is not a valid checksum manifest", checksums.display()))?;
+    let base_dir = checksums.parent().unwrap_or(Path::new("."));
+
+    println!();
+    let mut failures = 0;
+    for expected in &manifest.artifacts {
+        let path = base_dir.join(&expected.name);
+        let problem = if !path.exists() {
+            Some("missing".to_string())
+        } else {
+            let actual = ArtifactChecksum::compute(expected.name.clone(), &path)?;
+            if actual.size != expected.size {
+                Some(format!("size {} bytes, expected {}", actual.size, expected.size))
+            } else if actual.sha256 != expected.sha256 {
+                Some("SHA-256 mismatch".to_string())
+            } else if actual.sha512 != expected.sha512 {
+                Some("SHA-512 mismatch".to_string())
+            } else {
+                None
+            }
+        };
+        match problem {
+            None => println!("  {} {}", style("✓").green(), expected.name),
+            Some(problem) => {
+                failures += 1;
+                println!("  {} {} ({})", style("✗").red(), expected.name, problem);
             }
         }
+    }
+    println!();
 
-        // Update appdata-paths for cleanup on uninstall
-        let mut appdata = toml_edit::Array::new();
-        appdata.push(format!("$LOCALAPPDATA\\{}", &self.name));
-        doc["package"]["metadata"]["packager"]["nsis"]["appdata-paths"] = toml_edit::value(appdata);
-
-        fs::write(cargo_toml_path, doc.to_string())?;
-        Ok(())
+    if failures > 0 {
+        anyhow::bail!("{} of {} artifact(s) failed verification", failures, manifest.artifacts.len());
     }
+    println!("  {} All {} artifact(s) verified", style("✓").green().bold(), manifest.artifacts.len());
+    println!();
+    Ok(())
 }
 
-fn package_app(
-    skip_prompts: bool,
-    locked: bool,
-    no_rebuild: bool,
-    skip_binary: bool,
-    name: Option<String>,
-    version: Option<String>,
-    description: Option<String>,
-    author: Option<String>,
-) -> Result<()> {
+fn package_app(options: PackageOptions) -> Result<()> {
+    let PackageOptions {
+        skip_prompts,
+        locked,
+        no_rebuild,
+        skip_binary,
+        name,
+        version,
+        auto_version,
+        description,
+        author,
+        changelog,
+        release_notes,
+        release_notes_file,
+        github_release,
+        audit,
+        defines,
+        update_check,
+        skip_update_check,
+        no_kill,
+        notarize,
+        portable,
+        split_installers,
+    } = options;
+
     let repo_root = get_repo_root()?;
     let app_dir = repo_root.join("app");
     let cargo_toml_path = app_dir.join("Cargo.toml");
@@ -3853,11 +13136,51 @@ fn package_app(
     println!("{}", style("╚══════════════════════════════════════════╝").cyan());
     println!();
 
+    package_preflight(&app_dir, portable, split_installers)?;
+
+    let require_latest_cli = update_check || WebArcadeConfig::load_or_create(&get_config_path()?)
+        .map(|c| c.package_require_latest_cli)
+        .unwrap_or(false);
+    if require_latest_cli && !skip_update_check {
+        ensure_latest_cli()?;
+    }
+
+    // Check notarization prerequisites before spending time on the build
+    let notary_credentials = if notarize {
+        if !cfg!(target_os = "macos") {
+            anyhow::bail!("--notarize is only supported on macOS");
+        }
+        Some(NotaryCredentials::from_env()?)
+    } else {
+        None
+    };
+
+    // DMGs can only be built (and notarized) on macOS; the other platforms are cross-compiled
+    if split_installers && !cfg!(target_os = "macos") {
+        anyhow::bail!("--split-installers needs a macOS host with cross-compilation toolchains for Windows and Linux");
+    }
+
+    // Fail before building when the release can't be published
+    let github_repo = if github_release {
+        if github_token().is_none() {
+            anyhow::bail!("--github-release needs a GitHub token: set WEBARCADE_GITHUB_TOKEN or run `webarcade config set github.token <token>`");
+        }
+        Some(github_repo_from_remote(&repo_root)?)
+    } else {
+        None
+    };
+
     // Load existing config
     let mut config = AppConfig::from_cargo_toml(&cargo_toml_path)?;
     config.locked = locked;
+    if auto_version {
+        config.version = git_tag_version(&repo_root)?;
+        println!("  {} Version from git tags: {}", style("→").dim(), style(&config.version).green());
+    }
 
     let theme = ColorfulTheme::default();
+    // The display name follows the app name only when the name is changed here
+    let cargo_name = config.name.clone();
 
     if !skip_prompts {
         // Interactive prompts
@@ -3869,6 +13192,9 @@ fn package_app(
                 .default(config.name)
                 .interact_text()?
         };
+        if config.name != cargo_name {
+            config.product_name = config.name.clone();
+        }
 
         config.version = if let Some(v) = version {
             v
@@ -3901,7 +13227,7 @@ fn package_app(
         // Generate identifier from name
         let default_identifier = format!(
             "com.{}.app",
-            config.name.to_lowercase().replace(' ', "").replace('-', "")
+            config.name.to_lowercase().replace([' ', '-'], "")
         );
         config.identifier = Input::with_theme(&theme)
             .with_prompt("Identifier")
@@ -3936,7 +13262,10 @@ fn package_app(
         }
     } else {
         // Use provided args or defaults
-        if let Some(n) = name { config.name = n; }
+        if let Some(n) = name {
+            config.product_name = n.clone();
+            config.name = n;
+        }
         if let Some(v) = version { config.version = v; }
         if let Some(d) = description { config.description = d; }
         if let Some(a) = author { config.author = a; }
@@ -3944,17 +13273,80 @@ fn package_app(
 
     println!();
 
+    // Release notes for the installer: --release-notes, --release-notes-file, or the section for
+    // this version in --changelog / CHANGELOG.md at the repo root
+    let changelog_path = match changelog {
+        Some(path) => {
+            if !path.exists() {
+                anyhow::bail!("Changelog not found: {}", path.display());
+            }
+            Some(path)
+        }
+        None if release_notes.is_none() && release_notes_file.is_none() => {
+            Some(repo_root.join("CHANGELOG.md")).filter(|p| p.exists())
+        }
+        None => None,
+    };
+    if let Some(notes) = release_notes {
+        config.release_notes = Some(notes.trim().to_string()).filter(|n| !n.is_empty());
+    } else if let Some(ref path) = release_notes_file {
+        let notes = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read release notes from {}", path.display()))?;
+        config.release_notes = Some(notes.trim().to_string()).filter(|n| !n.is_empty());
+    } else if let Some(ref path) = changelog_path {
+        config.release_notes = extract_release_notes(&fs::read_to_string(path)?, &config.version);
+    }
+
+    println!("{} Validating configuration...", style("[0/5]").bold().dim());
+    let errors = config.validate();
+    if !errors.is_empty() {
+        for error in &errors {
+            println!("  {} {}", style("✗").red(), error);
+        }
+        println!();
+        anyhow::bail!("Found {} configuration problem(s); nothing was built", errors.len());
+    }
+    println!("  {} Configuration valid", style("✓").green());
+
+    if audit {
+        let report = run_audit()?;
+        report.print();
+        if report.vulnerability_count() > 0 {
+            anyhow::bail!("Refusing to package: plugin dependencies have known vulnerabilities");
+        }
+    }
+
     // Kill any running app processes before building
-    kill_running_app_processes()?;
+    if !no_kill {
+        kill_running_app_processes()?;
+    }
 
     println!("{} Updating configuration...", style("[1/5]").bold().dim());
-    config.write_to_cargo_toml(&cargo_toml_path)?;
-    println!("  {} Cargo.toml updated", style("✓").green());
+    for path in config.apply_to_project(&repo_root)? {
+        let name = path.strip_prefix(&repo_root).unwrap_or(&path);
+        println!("  {} {} updated", style("✓").green(), name.display());
+    }
+    if let (Some(path), Some(_)) = (changelog_path.as_ref().or(release_notes_file.as_ref()), &config.release_notes) {
+        println!("  {} Release notes from {}", style("✓").green(), path.display());
+    }
+
+    if split_installers {
+        return package_split_installers(
+            &config,
+            &repo_root,
+            no_rebuild,
+            &defines,
+            skip_binary,
+            notary_credentials.as_ref(),
+            github_repo.as_deref(),
+        );
+    }
 
     println!("{} Building all plugins{}...", style("[2/5]").bold().dim(),
         if no_rebuild { " (using cache)" } else { "" });
     // Force rebuild unless --no-rebuild is specified
-    match build_all_plugins(!no_rebuild, None) {
+    let build_options = BuildOptions { force: !no_rebuild, defines, ..Default::default() };
+    match build_all_plugins(&build_options) {
         Ok(_) => println!("  {} All plugins built", style("✓").green()),
         Err(e) => {
             println!("  {} Plugin build failed: {}", style("✗").red(), e);
@@ -4000,7 +13392,41 @@ fn package_app(
         println!("  {} Binary compiled", style("✓").green());
     }
 
+    // Find the output file
+    let output_dir = app_dir.join("target").join("release");
+    let binary_name = if cfg!(target_os = "windows") { format!("{}.exe", config.name) } else { config.name.clone() };
+
+    if portable {
+        println!("{} Creating portable ZIP...", style("[5/5]").bold().dim());
+        let zip_path = create_portable_zip(&config, &output_dir.join(&binary_name), &app_dir.join("plugins"), &output_dir)?;
+        println!("  {} Portable ZIP created", style("✓").green());
+        let checksums_path = write_checksums(&output_dir, &[output_dir.join(&binary_name), zip_path.clone()])?;
+        let notes_path = write_release_notes(&output_dir, config.release_notes.as_deref())?;
+        let release_url = match github_repo {
+            Some(ref repo) => Some(publish_github_release(repo, &config, &[zip_path.clone(), checksums_path.clone()])?),
+            None => None,
+        };
+
+        println!();
+        println!("{}", style("╔══════════════════════════════════════════╗").green());
+        println!("{}", style("║           Packaging Complete!            ║").green());
+        println!("{}", style("╚══════════════════════════════════════════╝").green());
+        println!();
+        println!("  {} {}", style("Binary:").bold(), output_dir.join(&binary_name).display());
+        println!("  {} {}", style("Portable:").bold(), zip_path.display());
+        println!("  {} {}", style("Checksums:").bold(), checksums_path.display());
+        if let Some(path) = notes_path {
+            println!("  {} {}", style("Release notes:").bold(), path.display());
+        }
+        if let Some(url) = release_url {
+            println!("  {} {}", style("GitHub release:").bold(), url);
+        }
+        println!();
+        return Ok(());
+    }
+
     println!("{} Creating installer...", style("[5/5]").bold().dim());
+    let packaging_started = std::time::SystemTime::now();
     let packager_status = Command::new("cargo")
         .current_dir(&app_dir)
         .args(["packager", "--release"])
@@ -4012,11 +13438,29 @@ fn package_app(
     }
     println!("  {} Installer created", style("✓").green());
 
-    // Find the output file
-    let output_dir = app_dir.join("target").join("release");
+    if let Some(credentials) = &notary_credentials {
+        let dmg = find_dmg(&output_dir)
+            .with_context(|| format!("No .dmg found in {}; is \"dmg\" among the packager formats?", output_dir.display()))?;
+        println!("  {} Notarizing {}...", style("→").dim(), dmg.display());
+        notarize_dmg(&dmg, credentials)?;
+    }
     let installer_name = format!("{}_{}_x64-setup.exe", config.name, config.version);
     let installer_path = output_dir.join(&installer_name);
 
+    let installers = find_installers(&output_dir, packaging_started);
+    let mut artifacts = vec![output_dir.join(&binary_name)];
+    artifacts.extend(installers.iter().cloned());
+    let checksums_path = write_checksums(&output_dir, &artifacts)?;
+    let notes_path = write_release_notes(&output_dir, config.release_notes.as_deref())?;
+    let release_url = match github_repo {
+        Some(ref repo) => {
+            let mut assets = installers;
+            assets.push(checksums_path.clone());
+            Some(publish_github_release(repo, &config, &assets)?)
+        }
+        None => None,
+    };
+
     println!();
     println!("{}", style("╔══════════════════════════════════════════╗").green());
     println!("{}", style("║           Packaging Complete!            ║").green());
@@ -4028,7 +13472,400 @@ fn package_app(
     } else {
         println!("  {} {}", style("Installer:").bold(), output_dir.display());
     }
+    println!("  {} {}", style("Checksums:").bold(), checksums_path.display());
+    if let Some(path) = notes_path {
+        println!("  {} {}", style("Release notes:").bold(), path.display());
+    }
+    if let Some(url) = release_url {
+        println!("  {} {}", style("GitHub release:").bold(), url);
+    }
+    println!();
+
+    Ok(())
+}
+
+/// A platform `package --split-installers` builds an installer for
+struct SplitPlatform {
+    /// Platform part of the installer name, e.g. `MyApp_1.0.0_windows_x64.exe`
+    label: &'static str,
+    triple: &'static str,
+    /// `cargo packager --formats` value
+    format: &'static str,
+    /// How the packager's output file name ends
+    packager_suffix: &'static str,
+    extension: &'static str,
+}
+
+const SPLIT_INSTALLER_PLATFORMS: &[SplitPlatform] = &[
+    SplitPlatform { label: "windows_x64", triple: "x86_64-pc-windows-msvc", format: "nsis", packager_suffix: "-setup.exe", extension: "exe" },
+    SplitPlatform { label: "macos_arm64", triple: "aarch64-apple-darwin", format: "dmg", packager_suffix: ".dmg", extension: "dmg" },
+    SplitPlatform { label: "linux_x64", triple: "x86_64-unknown-linux-gnu", format: "appimage", packager_suffix: ".AppImage", extension: "AppImage" },
+];
+
+/// The rest of `package --split-installers`: build the frontend once, then for each platform in
+/// turn clear app/plugins/ and build its plugins, binary and installer in their own cargo
+/// invocations with that platform's target triple.
+/// Installers are collected in app/target/installers/ as `<name>_<version>_<platform>.<ext>`,
+/// with one checksums.json covering them all.
+fn package_split_installers(
+    config: &AppConfig,
+    repo_root: &Path,
+    no_rebuild: bool,
+    defines: &[(String, String)],
+    skip_binary: bool,
+    notary_credentials: Option<&NotaryCredentials>,
+    github_repo: Option<&str>,
+) -> Result<()> {
+    let app_dir = repo_root.join("app");
+    let installers_dir = app_dir.join("target").join("installers");
+    if installers_dir.exists() {
+        fs::remove_dir_all(&installers_dir)?;
+    }
+    fs::create_dir_all(&installers_dir)?;
+
+    if skip_binary {
+        println!("{} Skipping frontend build (using existing)", style("[2/5]").bold().dim());
+        println!("  {} Skipped", style("→").dim());
+    } else {
+        println!("{} Building frontend...", style("[2/5]").bold().dim());
+        let frontend_status = Command::new("bun")
+            .current_dir(repo_root)
+            .args(["run", "build:prod"])
+            .status()
+            .context("Failed to run bun")?;
+        if !frontend_status.success() {
+            anyhow::bail!("Frontend build failed");
+        }
+        println!("  {} Frontend built", style("✓").green());
+    }
+
+    // One platform at a time: app/plugins/ and the embedded plugins only hold one platform's
+    // libraries, so each installer is packaged before the next platform's plugins are built
+    let dist_plugins_dir = app_dir.join("plugins");
+    println!("{} Building and packaging {} platforms...", style("[3/5]").bold().dim(), SPLIT_INSTALLER_PLATFORMS.len());
+    let mut installers = Vec::new();
+    for platform in SPLIT_INSTALLER_PLATFORMS {
+        println!("  {} {} ({})", style("→").dim(), platform.label, platform.triple);
+        if dist_plugins_dir.exists() {
+            fs::remove_dir_all(&dist_plugins_dir)
+                .with_context(|| format!("Failed to clear {}", dist_plugins_dir.display()))?;
+        }
+        fs::create_dir_all(&dist_plugins_dir)?;
+
+        let build_options = BuildOptions {
+            force: !no_rebuild,
+            target: Some(platform.triple.to_string()),
+            defines: defines.to_vec(),
+            ..Default::default()
+        };
+        build_all_plugins(&build_options)
+            .with_context(|| format!("Plugin build for {} failed", platform.triple))?;
+
+        if !skip_binary {
+            let mut cargo_args = vec!["build", "--release", "--target", platform.triple];
+            if config.locked {
+                cargo_args.extend(["--features", "locked-plugins"]);
+            }
+            let cargo_status = Command::new("cargo")
+                .current_dir(&app_dir)
+                .args(&cargo_args)
+                .status()
+                .context("Failed to run cargo build")?;
+            if !cargo_status.success() {
+                anyhow::bail!("Cargo build for {} failed", platform.triple);
+            }
+        }
+
+        let packaging_started = std::time::SystemTime::now();
+        let packager_status = Command::new("cargo")
+            .current_dir(&app_dir)
+            .args(["packager", "--release", "--target", platform.triple, "--formats", platform.format])
+            .status()
+            .context("Failed to run cargo packager")?;
+        if !packager_status.success() {
+            anyhow::bail!("Packaging for {} failed", platform.triple);
+        }
+
+        let output_dir = app_dir.join("target").join(platform.triple).join("release");
+        let produced = find_installers(&output_dir, packaging_started)
+            .into_iter()
+            .filter(|path| path.to_string_lossy().ends_with(platform.packager_suffix))
+            .max_by_key(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+            .with_context(|| format!("cargo packager left no {} installer in {}", platform.format, output_dir.display()))?;
+        let installer = installers_dir.join(format!("{}_{}_{}.{}", config.name, config.version, platform.label, platform.extension));
+        fs::copy(&produced, &installer)
+            .with_context(|| format!("Failed to copy {} to {}", produced.display(), installer.display()))?;
+        if let (Some(credentials), "dmg") = (notary_credentials, platform.format) {
+            println!("  {} Notarizing {}...", style("→").dim(), installer.display());
+            notarize_dmg(&installer, credentials)?;
+        }
+        println!("  {} {}", style("✓").green(), installer.file_name().unwrap_or_default().to_string_lossy());
+        installers.push(installer);
+    }
+
+    println!("{} Writing checksums...", style("[4/5]").bold().dim());
+    let checksums_path = write_checksums(&installers_dir, &installers)?;
+    let notes_path = write_release_notes(&installers_dir, config.release_notes.as_deref())?;
+    println!("  {} {}", style("✓").green(), checksums_path.display());
+    if github_repo.is_some() {
+        println!("{} Publishing GitHub release...", style("[5/5]").bold().dim());
+    }
+    let release_url = match github_repo {
+        Some(repo) => {
+            let mut assets = installers.clone();
+            assets.push(checksums_path.clone());
+            Some(publish_github_release(repo, config, &assets)?)
+        }
+        None => None,
+    };
+
+    println!();
+    println!("{}", style("╔══════════════════════════════════════════╗").green());
+    println!("{}", style("║           Packaging Complete!            ║").green());
+    println!("{}", style("╚══════════════════════════════════════════╝").green());
+    println!();
+    for installer in &installers {
+        println!("  {} {}", style("Installer:").bold(), installer.display());
+    }
+    println!("  {} {}", style("Checksums:").bold(), checksums_path.display());
+    if let Some(path) = notes_path {
+        println!("  {} {}", style("Release notes:").bold(), path.display());
+    }
+    if let Some(url) = release_url {
+        println!("  {} {}", style("GitHub release:").bold(), url);
+    }
     println!();
 
     Ok(())
 }
+
+/// Write the release notes to release-notes.md next to the packaged artifacts
+fn write_release_notes(output_dir: &Path, notes: Option<&str>) -> Result<Option<PathBuf>> {
+    let Some(notes) = notes else {
+        return Ok(None);
+    };
+    let path = output_dir.join("release-notes.md");
+    fs::write(&path, format!("{}\n", notes))?;
+    Ok(Some(path))
+}
+
+/// `owner/repo` of the origin remote, which must be on github.com
+fn github_repo_from_remote(repo_root: &Path) -> Result<String> {
+    let url = run_git(repo_root, &["remote", "get-url", "origin"])
+        .context("--github-release needs an origin remote on GitHub")?;
+    // https://github.com/owner/repo.git, git@github.com:owner/repo.git, ssh://git@github.com/owner/repo
+    let path = url
+        .split_once("github.com")
+        .map(|(_, rest)| rest.trim_start_matches([':', '/']))
+        .with_context(|| format!("origin ({}) is not a GitHub repository", url))?;
+    let repo = path.trim_end_matches('/').trim_end_matches(".git");
+    if repo.split('/').count() != 2 {
+        anyhow::bail!("Could not read owner/repo from the origin URL {}", url);
+    }
+    Ok(repo.to_string())
+}
+
+/// Percent-encode everything but RFC 3986 unreserved characters, for a URL query value
+fn percent_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (b as char).to_string(),
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Create the GitHub release `v<version>` with the release notes as its body and upload `assets`.
+/// GitHub creates the tag on the default branch if it doesn't exist yet. Returns the release page URL.
+fn publish_github_release(repo: &str, config: &AppConfig, assets: &[PathBuf]) -> Result<String> {
+    let tag = format!("v{}", config.version);
+    println!("  {} Creating GitHub release {} in {}...", style("→").dim(), tag, repo);
+    let body = serde_json::json!({
+        "tag_name": tag,
+        "name": format!("{} {}", config.name, tag),
+        "body": config.release_notes.clone().unwrap_or_default(),
+    });
+    let url = format!("https://api.github.com/repos/{}/releases", repo);
+    let response = match github_api_request("POST", &url).send_string(&body.to_string()) {
+        Ok(response) => response.into_string()?,
+        Err(ureq::Error::Status(422, _)) => {
+            anyhow::bail!("GitHub release {} already exists in {}; delete it or bump the version", tag, repo)
+        }
+        Err(e) => anyhow::bail!("Failed to create GitHub release: {}", scrub_token(&e.to_string())),
+    };
+    let release: serde_json::Value = serde_json::from_str(&response).context("Failed to parse the GitHub release")?;
+    // upload_url is a URI template ending in {?name,label}
+    let upload_url = release["upload_url"].as_str().context("GitHub release has no upload URL")?;
+    let upload_url = upload_url.split('{').next().unwrap_or(upload_url);
+
+    for asset in assets {
+        let name = asset.file_name().unwrap_or_default().to_string_lossy().to_string();
+        let file = fs::File::open(asset).with_context(|| format!("Failed to open {}", asset.display()))?;
+        let size = file.metadata()?.len();
+        github_api_request("POST", &format!("{}?name={}", upload_url, percent_encode(&name)))
+            .set("Content-Type", "application/octet-stream")
+            .set("Content-Length", &size.to_string())
+            .send(file)
+            .map_err(|e| anyhow::anyhow!("Failed to upload {}: {}", name, scrub_token(&e.to_string())))?;
+        println!("  {} Uploaded {}", style("✓").green(), name);
+    }
+    Ok(release["html_url"].as_str().unwrap_or_default().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cmp::Ordering;
+
+    #[test]
+    fn compare_versions_orders_major_minor_patch() {
+        assert_eq!(compare_versions("1.2.3", "1.2.3"), Some(Ordering::Equal));
+        assert_eq!(compare_versions("1.2.4", "1.2.3"), Some(Ordering::Greater));
+        assert_eq!(compare_versions("1.10.0", "1.9.9"), Some(Ordering::Greater));
+        assert_eq!(compare_versions("1.9.9", "2.0.0"), Some(Ordering::Less));
+        assert_eq!(compare_versions("v1.2", "1.2.0"), Some(Ordering::Equal));
+    }
+
+    #[test]
+    fn compare_versions_orders_pre_releases_first() {
+        assert_eq!(compare_versions("1.0.0-alpha", "1.0.0"), Some(Ordering::Less));
+        assert_eq!(compare_versions("1.0.0-alpha", "1.0.0-beta"), Some(Ordering::Less));
+        assert_eq!(compare_versions("1.0.0-rc.1", "0.9.0"), Some(Ordering::Greater));
+    }
+
+    #[test]
+    fn compare_versions_ignores_build_metadata() {
+        assert_eq!(compare_versions("1.0.0+build.1", "1.0.0+build.2"), Some(Ordering::Equal));
+        assert_eq!(compare_versions("1.0.0+abc", "1.0.0"), Some(Ordering::Equal));
+    }
+
+    #[test]
+    fn compare_versions_rejects_unparsable_versions() {
+        assert_eq!(compare_versions("latest", "1.0.0"), None);
+        assert_eq!(compare_versions("1.0.0", "1.x"), None);
+        assert_eq!(compare_versions("", "1.0.0"), None);
+    }
+
+    #[test]
+    fn find_closing_skips_char_and_string_literals() {
+        let body = "{ if c == '}' || s == \"}\" { x('\\'') } }";
+        assert_eq!(find_closing(body, 0, '{', '}'), Some(body.len()));
+
+        let with_lifetime = "fn f<'a>(x: &'a str) { x } tail";
+        let open = with_lifetime.find('{').unwrap();
+        assert_eq!(find_closing(with_lifetime, open, '{', '}'), Some(with_lifetime.len() - " tail".len()));
+    }
+
+    #[test]
+    fn license_allowed_follows_spdx_precedence() {
+        let allowed = vec!["MIT".to_string(), "Apache-2.0 WITH LLVM-exception".to_string()];
+        assert!(license_allowed("MIT", &allowed));
+        assert!(license_allowed("MIT OR Apache-2.0", &allowed));
+        assert!(license_allowed("MIT/Apache-2.0", &allowed));
+        assert!(license_allowed("Apache-2.0 WITH LLVM-exception", &allowed));
+        assert!(license_allowed("Unicode-3.0 AND MIT OR MIT", &allowed));
+        assert!(!license_allowed("(MIT OR Apache-2.0) AND Unicode-3.0", &allowed));
+        assert!(!license_allowed("MIT AND (Unicode-3.0 OR Apache-2.0)", &allowed));
+        assert!(!license_allowed("Apache-2.0", &allowed));
+        assert!(!license_allowed("(MIT", &allowed));
+        assert!(!license_allowed("", &allowed));
+    }
+
+    #[test]
+    fn license_text_fills_only_the_copyright_line() {
+        let mit = license_text("MIT", "2026", "Jane Doe").unwrap();
+        assert!(mit.contains("Copyright (c) 2026 Jane Doe\n"));
+        assert!(!mit.contains("<year>"));
+        let apache = license_text("Apache-2.0", "2026", "Jane Doe").unwrap();
+        assert!(apache.contains("Copyright 2026 Jane Doe\n"));
+        assert!(!apache.contains("[yyyy]"));
+        let gpl = license_text("GPL-3.0-or-later", "2026", "Jane Doe").unwrap();
+        assert!(gpl.contains("Copyright (C) <year>  <name of author>"));
+        assert!(!gpl.contains("Jane Doe"));
+        assert!(license_text("Unlicense", "2026", "Jane Doe").is_none());
+    }
+
+    #[test]
+    fn migrate_config_converts_v1_to_v2() {
+        let mut config = serde_json::json!({
+            "default_layout": "welcome",
+            "allowed_licenses": ["MIT"],
+            "plugins": {
+                "chat": { "has_backend": true, "has_frontend": true, "priority": 100 },
+                "theme": { "has_backend": false, "wasm_path": "theme_bg.wasm" }
+            }
+        });
+
+        assert_eq!(migrate_config(&mut config), Some(1));
+        assert_eq!(config["$schemaVersion"], CONFIG_SCHEMA_VERSION);
+        assert_eq!(config["defaultLayout"], "welcome");
+        assert_eq!(config["allowedLicenses"], serde_json::json!(["MIT"]));
+        assert!(config.get("default_layout").is_none());
+        assert_eq!(config["name"], "WebArcade");
+        assert_eq!(config["version"], "0.1.0");
+
+        let chat = &config["plugins"]["chat"];
+        assert_eq!(chat["hasBackend"], true);
+        assert_eq!(chat["name"], "chat");
+        assert_eq!(chat["version"], "1.0.0");
+        assert_eq!(chat["path"], "chat.dll");
+        assert!(chat.get("has_backend").is_none());
+        let theme = &config["plugins"]["theme"];
+        assert_eq!(theme["path"], "theme.js");
+        assert_eq!(theme["wasmPath"], "theme_bg.wasm");
+
+        // The migrated value is what the config struct expects
+        serde_json::from_value::<WebArcadeConfig>(config).unwrap();
+    }
+
+    #[test]
+    fn migrate_config_bumps_schema_version() {
+        let mut config = serde_json::json!({ "$schemaVersion": 1, "name": "App", "version": "1.0.0" });
+        assert_eq!(migrate_config(&mut config), Some(1));
+        assert_eq!(config["$schemaVersion"], CONFIG_SCHEMA_VERSION);
+        assert_eq!(config["name"], "App");
+    }
+
+    #[test]
+    fn migrate_config_leaves_current_config_unchanged() {
+        let original = serde_json::json!({
+            "$schemaVersion": CONFIG_SCHEMA_VERSION,
+            "name": "App",
+            "version": "1.0.0",
+            "default_layout": "kept as-is",
+            "plugins": {}
+        });
+        let mut config = original.clone();
+        assert_eq!(migrate_config(&mut config), None);
+        assert_eq!(config, original);
+    }
+
+    #[test]
+    fn load_layered_writes_migrations_only_when_asked() {
+        let dir = std::env::temp_dir().join(format!("webarcade-migrate-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("webarcade.config.json");
+        let v1 = "{\"name\": \"App\", \"version\": \"1.0.0\", \"default_layout\": \"welcome\", \"plugins\": {}}";
+        fs::write(&config_path, v1).unwrap();
+
+        WebArcadeConfig::load_layered(&config_path, ConfigMigration::Skip, None).unwrap();
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), v1);
+        let config = WebArcadeConfig::load_layered(&config_path, ConfigMigration::InMemory, None).unwrap();
+        assert_eq!(config.default_layout.as_deref(), Some("welcome"));
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), v1);
+
+        WebArcadeConfig::load_layered(&config_path, ConfigMigration::Write, None).unwrap();
+        let migrated: serde_json::Value = serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(migrated["$schemaVersion"], CONFIG_SCHEMA_VERSION);
+        assert_eq!(migrated["defaultLayout"], "welcome");
+
+        // A current config without $schemaVersion only gains the version in memory
+        let current = "{\"name\": \"App\", \"version\": \"1.0.0\", \"plugins\": {}}";
+        fs::write(&config_path, current).unwrap();
+        WebArcadeConfig::load_layered(&config_path, ConfigMigration::Write, None).unwrap();
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), current);
+
+        let _ = fs::remove_dir_all(&dir);
+    }
+}